@@ -393,3 +393,42 @@ impl AgentVerseAdapterConfig {
         }
     }
 }
+
+// ---------- 8. Delegation: orchestrator → sub-agent chords ---------------
+
+impl AgentVerseIdentity {
+    /// Whether this identity may hand a chord of `kind` to a `to` agent.
+    /// Only the orchestrator delegates, and only non-actuating text work.
+    pub fn may_delegate(&self, kind: &tsafe_cortex_gate::CapabilityKind, to: &AgentVerseRole) -> bool {
+        matches!(
+            (&self.role, kind, to),
+            (
+                AgentVerseRole::SystemOrchestrator,
+                tsafe_cortex_gate::CapabilityKind::SummarizeText,
+                AgentVerseRole::ChatAgent
+            )
+        )
+    }
+
+    /// Narrow one of this identity's sealed chords for a sub-agent. The
+    /// caveats can only restrict; the gate re-checks them on every use.
+    pub fn delegate_chord(
+        &self,
+        parent: &tsafe_cortex_gate::CapabilityChord,
+        to: &AgentVerseIdentity,
+        caveats: Vec<chord_seal::Caveat>,
+    ) -> anyhow::Result<tsafe_cortex_gate::CapabilityChord> {
+        if !self.may_delegate(&parent.kind, &to.role) {
+            anyhow::bail!("{:?} may not delegate {:?} to {:?}", self.role, parent.kind, to.role);
+        }
+        // Compared as subjects, so a differently-cased address still matches.
+        if parent.subject_id != *to.subject_bostrom.as_str() {
+            anyhow::bail!("sub-agent acts for {}, chord is bound to {}", to.subject_bostrom, parent.subject_id);
+        }
+        let mut chord = parent.clone();
+        for c in caveats {
+            chord = chord.attenuate(c)?;
+        }
+        Ok(chord)
+    }
+}
@@ -1,5 +1,6 @@
 use anyhow::Result;
 use cortex_gate::policy::{Decision, PolicyEngine, SovereignAction, SovereignActionKind};
+use policy_bundle::StakeAnchor;
 use serde::{Deserialize, Serialize;
 
 #[derive(Clone)]
@@ -33,9 +34,13 @@ struct ChatResponse {
 }
 
 impl AiShell {
-    pub async fn new(cfg: AiShellConfig, policy_dir: &std::path::Path) -> Result<Self> {
+    pub async fn new(
+        cfg: AiShellConfig,
+        policy_dir: &std::path::Path,
+        anchor: &StakeAnchor,
+    ) -> Result<Self> {
         let client = reqwest::Client::new();
-        let policy = PolicyEngine::load_from_dir(policy_dir)?;
+        let policy = PolicyEngine::load_from_dir(policy_dir, anchor)?;
         Ok(Self { client, cfg, policy })
     }
 
@@ -51,7 +56,7 @@ impl AiShell {
         };
 
         match self.policy.evaluate(&action) {
-            Decision::Deny { reason } => {
+            Decision::Deny { reason, .. } => {
                 return Err(anyhow::anyhow!(
                     "Tsafe denied AI-chat interaction: {}",
                     reason
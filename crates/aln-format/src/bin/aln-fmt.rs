@@ -0,0 +1,41 @@
+use std::io::Read;
+
+const USAGE: &str = "usage: aln-fmt [--canonical | --canonical-json] [FILE]
+
+Reads FILE (or stdin) and writes it to stdout: pretty ALN by default,
+or the canonical ALN / JSON form that hexstamps are computed over.";
+
+fn main() -> anyhow::Result<()> {
+    let mut mode = "pretty";
+    let mut file = None;
+    for arg in std::env::args().skip(1) {
+        match arg.as_str() {
+            "--canonical" => mode = "canonical",
+            "--canonical-json" => mode = "json",
+            "-h" | "--help" => {
+                println!("{}", USAGE);
+                return Ok(());
+            }
+            _ if file.is_none() && !arg.starts_with('-') => file = Some(arg),
+            _ => anyhow::bail!("{}", USAGE),
+        }
+    }
+
+    let src = match &file {
+        Some(path) => std::fs::read_to_string(path)?,
+        None => {
+            let mut s = String::new();
+            std::io::stdin().read_to_string(&mut s)?;
+            s
+        }
+    };
+    let value = aln_format::parse(&src)
+        .map_err(|e| anyhow::anyhow!("{}: {}", file.as_deref().unwrap_or("<stdin>"), e))?
+        .value;
+    match mode {
+        "canonical" => println!("{}", value.to_canonical()),
+        "json" => println!("{}", value.to_canonical_json()),
+        _ => print!("{}", value.to_pretty()),
+    }
+    Ok(())
+}
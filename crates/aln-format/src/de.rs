@@ -0,0 +1,388 @@
+//! serde `Deserializer` over a parsed ALN [`Node`] tree.
+//!
+//! Mapping rules:
+//! - records deserialize as structs or maps; a type tag, when present, must
+//!   match the target struct name (`TsafeKernel { ... }` into `TsafeKernel`),
+//! - a tagged record or a bare identifier selects an enum variant,
+//! - integers widen to floats, never the other way round,
+//! - `null` maps to `None` / unit.
+
+use serde::de::{
+    self, DeserializeSeed, EnumAccess, IntoDeserializer, MapAccess, SeqAccess, VariantAccess,
+    Visitor,
+};
+
+use crate::error::{Error, Result};
+use crate::value::{Field, Node, Record, Value};
+
+pub struct NodeDeserializer<'a> {
+    node: &'a Node,
+}
+
+impl<'a> NodeDeserializer<'a> {
+    pub fn new(node: &'a Node) -> Self {
+        Self { node }
+    }
+
+    fn mismatch(&self, expected: &str) -> Error {
+        Error::new(
+            format!("expected {}, found {}", expected, self.node.value.kind()),
+            self.node.pos,
+        )
+    }
+
+    fn int(&self) -> Result<i64> {
+        match self.node.value {
+            Value::Int(i) => Ok(i),
+            _ => Err(self.mismatch("integer")),
+        }
+    }
+
+    fn float(&self) -> Result<f64> {
+        match self.node.value {
+            Value::Int(i) => Ok(i as f64),
+            Value::Float(f) => Ok(f),
+            _ => Err(self.mismatch("number")),
+        }
+    }
+}
+
+macro_rules! deserialize_int {
+    ($method:ident, $visit:ident, $ty:ty) => {
+        fn $method<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+            let i = self.int()?;
+            let v = <$ty>::try_from(i).map_err(|_| {
+                Error::new(
+                    format!("integer {} out of range for {}", i, stringify!($ty)),
+                    self.node.pos,
+                )
+            })?;
+            visitor.$visit(v).map_err(|e: Error| e.at(self.node.pos))
+        }
+    };
+}
+
+impl<'de, 'a> de::Deserializer<'de> for NodeDeserializer<'a> {
+    type Error = Error;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        let pos = self.node.pos;
+        match &self.node.value {
+            Value::Null => visitor.visit_unit(),
+            Value::Bool(b) => visitor.visit_bool(*b),
+            Value::Int(i) => visitor.visit_i64(*i),
+            Value::Float(f) => visitor.visit_f64(*f),
+            Value::Str(s) | Value::Ident(s) => visitor.visit_str(s),
+            Value::List(items) => visitor.visit_seq(SeqDeserializer::new(items)),
+            Value::Record(r) => visitor.visit_map(MapDeserializer::new(r)),
+        }
+        .map_err(|e: Error| e.at(pos))
+    }
+
+    fn deserialize_bool<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        match self.node.value {
+            Value::Bool(b) => visitor.visit_bool(b).map_err(|e: Error| e.at(self.node.pos)),
+            _ => Err(self.mismatch("bool")),
+        }
+    }
+
+    deserialize_int!(deserialize_i8, visit_i8, i8);
+    deserialize_int!(deserialize_i16, visit_i16, i16);
+    deserialize_int!(deserialize_i32, visit_i32, i32);
+    deserialize_int!(deserialize_i64, visit_i64, i64);
+    deserialize_int!(deserialize_u8, visit_u8, u8);
+    deserialize_int!(deserialize_u16, visit_u16, u16);
+    deserialize_int!(deserialize_u32, visit_u32, u32);
+    deserialize_int!(deserialize_u64, visit_u64, u64);
+
+    fn deserialize_f32<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        let f = self.float()?;
+        visitor.visit_f32(f as f32).map_err(|e: Error| e.at(self.node.pos))
+    }
+
+    fn deserialize_f64<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        let f = self.float()?;
+        visitor.visit_f64(f).map_err(|e: Error| e.at(self.node.pos))
+    }
+
+    fn deserialize_char<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        self.deserialize_str(visitor)
+    }
+
+    fn deserialize_str<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        match &self.node.value {
+            Value::Str(s) | Value::Ident(s) => {
+                visitor.visit_str(s).map_err(|e: Error| e.at(self.node.pos))
+            }
+            _ => Err(self.mismatch("string")),
+        }
+    }
+
+    fn deserialize_string<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        self.deserialize_str(visitor)
+    }
+
+    fn deserialize_bytes<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        self.deserialize_any(visitor)
+    }
+
+    fn deserialize_byte_buf<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        self.deserialize_any(visitor)
+    }
+
+    fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        match self.node.value {
+            Value::Null => visitor.visit_none(),
+            _ => visitor.visit_some(self),
+        }
+    }
+
+    fn deserialize_unit<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        match self.node.value {
+            Value::Null => visitor.visit_unit(),
+            _ => Err(self.mismatch("null")),
+        }
+    }
+
+    fn deserialize_unit_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value> {
+        self.deserialize_unit(visitor)
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_seq<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        match &self.node.value {
+            Value::List(items) => visitor
+                .visit_seq(SeqDeserializer::new(items))
+                .map_err(|e: Error| e.at(self.node.pos)),
+            _ => Err(self.mismatch("list")),
+        }
+    }
+
+    fn deserialize_tuple<V: Visitor<'de>>(self, _len: usize, visitor: V) -> Result<V::Value> {
+        self.deserialize_seq(visitor)
+    }
+
+    fn deserialize_tuple_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        _len: usize,
+        visitor: V,
+    ) -> Result<V::Value> {
+        self.deserialize_seq(visitor)
+    }
+
+    fn deserialize_map<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        match &self.node.value {
+            Value::Record(r) => visitor
+                .visit_map(MapDeserializer::new(r))
+                .map_err(|e: Error| e.at(self.node.pos)),
+            _ => Err(self.mismatch("record")),
+        }
+    }
+
+    fn deserialize_struct<V: Visitor<'de>>(
+        self,
+        name: &'static str,
+        _fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value> {
+        if let Value::Record(Record { tag: Some(tag), .. }) = &self.node.value {
+            if tag != name {
+                return Err(Error::new(
+                    format!("record tagged `{}` cannot be read as `{}`", tag, name),
+                    self.node.pos,
+                ));
+            }
+        }
+        self.deserialize_map(visitor)
+    }
+
+    fn deserialize_enum<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        _variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value> {
+        let pos = self.node.pos;
+        match &self.node.value {
+            Value::Str(s) | Value::Ident(s) => visitor.visit_enum(s.as_str().into_deserializer()),
+            Value::Record(r) => visitor.visit_enum(EnumDeserializer::from_record(r, pos)?),
+            _ => Err(self.mismatch("enum variant")),
+        }
+        .map_err(|e: Error| e.at(pos))
+    }
+
+    fn deserialize_identifier<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        self.deserialize_str(visitor)
+    }
+
+    fn deserialize_ignored_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        visitor.visit_unit()
+    }
+}
+
+struct SeqDeserializer<'a> {
+    iter: std::slice::Iter<'a, Node>,
+}
+
+impl<'a> SeqDeserializer<'a> {
+    fn new(items: &'a [Node]) -> Self {
+        Self { iter: items.iter() }
+    }
+}
+
+impl<'de, 'a> SeqAccess<'de> for SeqDeserializer<'a> {
+    type Error = Error;
+
+    fn next_element_seed<T: DeserializeSeed<'de>>(&mut self, seed: T) -> Result<Option<T::Value>> {
+        match self.iter.next() {
+            Some(node) => seed
+                .deserialize(NodeDeserializer::new(node))
+                .map(Some)
+                .map_err(|e: Error| e.at(node.pos)),
+            None => Ok(None),
+        }
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.iter.len())
+    }
+}
+
+struct MapDeserializer<'a> {
+    iter: std::slice::Iter<'a, Field>,
+    pending: Option<&'a Node>,
+}
+
+impl<'a> MapDeserializer<'a> {
+    fn new(record: &'a Record) -> Self {
+        Self {
+            iter: record.fields.iter(),
+            pending: None,
+        }
+    }
+}
+
+impl<'de, 'a> MapAccess<'de> for MapDeserializer<'a> {
+    type Error = Error;
+
+    fn next_key_seed<K: DeserializeSeed<'de>>(&mut self, seed: K) -> Result<Option<K::Value>> {
+        match self.iter.next() {
+            Some(field) => {
+                self.pending = Some(&field.value);
+                let key: de::value::StrDeserializer<'_, Error> = field.key.as_str().into_deserializer();
+                seed.deserialize(key).map(Some).map_err(|e: Error| e.at(field.key_pos))
+            }
+            None => Ok(None),
+        }
+    }
+
+    fn next_value_seed<V: DeserializeSeed<'de>>(&mut self, seed: V) -> Result<V::Value> {
+        let node = self
+            .pending
+            .take()
+            .ok_or_else(|| <Error as de::Error>::custom("value requested before key"))?;
+        seed.deserialize(NodeDeserializer::new(node)).map_err(|e: Error| e.at(node.pos))
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.iter.len())
+    }
+}
+
+/// Externally tagged enum access: `Variant { ... }` or `{ Variant: value }`.
+struct EnumDeserializer<'a> {
+    variant: &'a str,
+    content: Content<'a>,
+}
+
+enum Content<'a> {
+    /// Tagged record: the record body is the variant payload.
+    Body(&'a Record, crate::error::Position),
+    /// Single-field map: the field value is the payload.
+    Node(&'a Node),
+}
+
+impl<'a> EnumDeserializer<'a> {
+    fn from_record(r: &'a Record, pos: crate::error::Position) -> Result<Self> {
+        if let Some(tag) = &r.tag {
+            return Ok(Self {
+                variant: tag,
+                content: Content::Body(r, pos),
+            });
+        }
+        match r.fields.as_slice() {
+            [field] => Ok(Self {
+                variant: &field.key,
+                content: Content::Node(&field.value),
+            }),
+            _ => Err(Error::new(
+                "enum record must be tagged or have exactly one field",
+                pos,
+            )),
+        }
+    }
+}
+
+impl<'de, 'a> EnumAccess<'de> for EnumDeserializer<'a> {
+    type Error = Error;
+    type Variant = Self;
+
+    fn variant_seed<V: DeserializeSeed<'de>>(self, seed: V) -> Result<(V::Value, Self)> {
+        let key: de::value::StrDeserializer<'_, Error> = self.variant.into_deserializer();
+        let v = seed.deserialize(key)?;
+        Ok((v, self))
+    }
+}
+
+impl<'de, 'a> VariantAccess<'de> for EnumDeserializer<'a> {
+    type Error = Error;
+
+    fn unit_variant(self) -> Result<()> {
+        match self.content {
+            Content::Body(r, _) if r.fields.is_empty() => Ok(()),
+            Content::Node(Node { value: Value::Null, .. }) => Ok(()),
+            Content::Body(_, pos) | Content::Node(&Node { pos, .. }) => {
+                Err(Error::new("unit variant cannot carry a payload", pos))
+            }
+        }
+    }
+
+    fn newtype_variant_seed<T: DeserializeSeed<'de>>(self, seed: T) -> Result<T::Value> {
+        match self.content {
+            Content::Node(node) => seed.deserialize(NodeDeserializer::new(node)),
+            Content::Body(r, pos) => seed
+                .deserialize(de::value::MapAccessDeserializer::new(MapDeserializer::new(r)))
+                .map_err(|e: Error| e.at(pos)),
+        }
+    }
+
+    fn tuple_variant<V: Visitor<'de>>(self, _len: usize, visitor: V) -> Result<V::Value> {
+        match self.content {
+            Content::Node(node) => de::Deserializer::deserialize_seq(NodeDeserializer::new(node), visitor),
+            Content::Body(_, pos) => Err(Error::new("tuple variant expects a list payload", pos)),
+        }
+    }
+
+    fn struct_variant<V: Visitor<'de>>(
+        self,
+        _fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value> {
+        match self.content {
+            Content::Node(node) => de::Deserializer::deserialize_map(NodeDeserializer::new(node), visitor),
+            Content::Body(r, pos) => visitor.visit_map(MapDeserializer::new(r)).map_err(|e: Error| e.at(pos)),
+        }
+    }
+}
@@ -0,0 +1,76 @@
+use std::fmt;
+
+/// 1-based source position inside an ALN document.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Position {
+    pub line: usize,
+    pub column: usize,
+}
+
+impl fmt::Display for Position {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}", self.line, self.column)
+    }
+}
+
+/// Lexing, parsing or deserialization failure, located where possible.
+#[derive(Debug, Clone, PartialEq, thiserror::Error)]
+pub struct Error {
+    pub message: String,
+    /// `None` only for errors raised outside any ALN node (e.g. I/O wrappers).
+    pub position: Option<Position>,
+}
+
+impl Error {
+    pub fn new(message: impl Into<String>, position: Position) -> Self {
+        Self {
+            message: message.into(),
+            position: Some(position),
+        }
+    }
+
+    /// Attach a position unless a more precise (inner) one is already set.
+    pub(crate) fn at(mut self, position: Position) -> Self {
+        if self.position.is_none() {
+            self.position = Some(position);
+        }
+        self
+    }
+
+    pub fn line(&self) -> Option<usize> {
+        self.position.map(|p| p.line)
+    }
+
+    pub fn column(&self) -> Option<usize> {
+        self.position.map(|p| p.column)
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.position {
+            Some(pos) => write!(f, "ALN error at line {} column {}: {}", pos.line, pos.column, self.message),
+            None => write!(f, "ALN error: {}", self.message),
+        }
+    }
+}
+
+impl serde::de::Error for Error {
+    fn custom<T: fmt::Display>(msg: T) -> Self {
+        Self {
+            message: msg.to_string(),
+            position: None,
+        }
+    }
+}
+
+impl serde::ser::Error for Error {
+    fn custom<T: fmt::Display>(msg: T) -> Self {
+        Self {
+            message: msg.to_string(),
+            position: None,
+        }
+    }
+}
+
+pub type Result<T> = std::result::Result<T, Error>;
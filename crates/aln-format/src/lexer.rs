@@ -0,0 +1,227 @@
+//! Tokenizer for ALN shards: braces, brackets, `:`/`,`, identifiers,
+//! double-quoted strings, numbers and `#` line comments.
+
+use crate::error::{Error, Position, Result};
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Token {
+    LBrace,
+    RBrace,
+    LBracket,
+    RBracket,
+    Colon,
+    Comma,
+    Ident(String),
+    Str(String),
+    Int(i64),
+    Float(f64),
+    Eof,
+}
+
+impl Token {
+    pub fn describe(&self) -> String {
+        match self {
+            Token::LBrace => "`{`".into(),
+            Token::RBrace => "`}`".into(),
+            Token::LBracket => "`[`".into(),
+            Token::RBracket => "`]`".into(),
+            Token::Colon => "`:`".into(),
+            Token::Comma => "`,`".into(),
+            Token::Ident(s) => format!("identifier `{}`", s),
+            Token::Str(_) => "string".into(),
+            Token::Int(_) | Token::Float(_) => "number".into(),
+            Token::Eof => "end of input".into(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Spanned {
+    pub token: Token,
+    pub pos: Position,
+}
+
+pub struct Lexer<'a> {
+    chars: std::iter::Peekable<std::str::Chars<'a>>,
+    line: usize,
+    column: usize,
+}
+
+impl<'a> Lexer<'a> {
+    pub fn new(src: &'a str) -> Self {
+        Self {
+            chars: src.chars().peekable(),
+            line: 1,
+            column: 1,
+        }
+    }
+
+    fn pos(&self) -> Position {
+        Position {
+            line: self.line,
+            column: self.column,
+        }
+    }
+
+    fn bump(&mut self) -> Option<char> {
+        let c = self.chars.next()?;
+        if c == '\n' {
+            self.line += 1;
+            self.column = 1;
+        } else {
+            self.column += 1;
+        }
+        Some(c)
+    }
+
+    fn skip_trivia(&mut self) {
+        while let Some(&c) = self.chars.peek() {
+            if c == '#' {
+                while let Some(&c) = self.chars.peek() {
+                    if c == '\n' {
+                        break;
+                    }
+                    self.bump();
+                }
+            } else if c.is_whitespace() {
+                self.bump();
+            } else {
+                break;
+            }
+        }
+    }
+
+    /// Tokenize the whole input; the last token is always `Eof`.
+    pub fn tokenize(mut self) -> Result<Vec<Spanned>> {
+        let mut out = Vec::new();
+        loop {
+            self.skip_trivia();
+            let pos = self.pos();
+            let Some(&c) = self.chars.peek() else {
+                out.push(Spanned { token: Token::Eof, pos });
+                return Ok(out);
+            };
+            let token = match c {
+                '{' => self.single(Token::LBrace),
+                '}' => self.single(Token::RBrace),
+                '[' => self.single(Token::LBracket),
+                ']' => self.single(Token::RBracket),
+                ':' => self.single(Token::Colon),
+                ',' => self.single(Token::Comma),
+                '"' => self.string(pos)?,
+                '-' | '+' | '0'..='9' => self.number(pos)?,
+                c if c.is_ascii_alphabetic() || c == '_' => self.ident(),
+                other => {
+                    return Err(Error::new(format!("unexpected character `{}`", other), pos));
+                }
+            };
+            out.push(Spanned { token, pos });
+        }
+    }
+
+    fn single(&mut self, token: Token) -> Token {
+        self.bump();
+        token
+    }
+
+    fn ident(&mut self) -> Token {
+        let mut s = String::new();
+        while let Some(&c) = self.chars.peek() {
+            if c.is_ascii_alphanumeric() || matches!(c, '_' | '-' | '.') {
+                s.push(c);
+                self.bump();
+            } else {
+                break;
+            }
+        }
+        Token::Ident(s)
+    }
+
+    fn number(&mut self, start: Position) -> Result<Token> {
+        let mut s = String::new();
+        while let Some(&c) = self.chars.peek() {
+            if c.is_ascii_digit() || matches!(c, '-' | '+' | '.' | 'e' | 'E' | '_') {
+                if c != '_' {
+                    s.push(c);
+                }
+                self.bump();
+            } else {
+                break;
+            }
+        }
+        let is_float = s.contains(['.', 'e', 'E']);
+        if !is_float {
+            if let Ok(i) = s.parse::<i64>() {
+                return Ok(Token::Int(i));
+            }
+        }
+        s.parse::<f64>()
+            .ok()
+            .filter(|f| f.is_finite())
+            .map(Token::Float)
+            .ok_or_else(|| Error::new(format!("invalid number `{}`", s), start))
+    }
+
+    fn string(&mut self, start: Position) -> Result<Token> {
+        self.bump(); // opening quote
+        let mut s = String::new();
+        loop {
+            let pos = self.pos();
+            match self.bump() {
+                None => return Err(Error::new("unterminated string", start)),
+                Some('"') => return Ok(Token::Str(s)),
+                Some('\n') => return Err(Error::new("newline in string literal", pos)),
+                Some('\\') => {
+                    let esc = self
+                        .bump()
+                        .ok_or_else(|| Error::new("unterminated escape sequence", pos))?;
+                    match esc {
+                        '"' => s.push('"'),
+                        '\\' => s.push('\\'),
+                        '/' => s.push('/'),
+                        'n' => s.push('\n'),
+                        't' => s.push('\t'),
+                        'r' => s.push('\r'),
+                        'b' => s.push('\u{0008}'),
+                        'f' => s.push('\u{000C}'),
+                        'u' => s.push(self.unicode_escape(pos)?),
+                        other => {
+                            return Err(Error::new(format!("invalid escape `\\{}`", other), pos));
+                        }
+                    }
+                }
+                Some(c) => s.push(c),
+            }
+        }
+    }
+
+    fn unicode_escape(&mut self, pos: Position) -> Result<char> {
+        let high = self.hex4(pos)?;
+        let code = if (0xD800..0xDC00).contains(&high) {
+            // Surrogate pair, JSON style (\uD83D\uDE00).
+            if self.bump() != Some('\\') || self.bump() != Some('u') {
+                return Err(Error::new("unpaired surrogate in \\u escape", pos));
+            }
+            let low = self.hex4(pos)?;
+            if !(0xDC00..0xE000).contains(&low) {
+                return Err(Error::new("invalid low surrogate in \\u escape", pos));
+            }
+            0x10000 + ((high - 0xD800) << 10) + (low - 0xDC00)
+        } else {
+            high
+        };
+        char::from_u32(code).ok_or_else(|| Error::new("invalid \\u escape", pos))
+    }
+
+    fn hex4(&mut self, pos: Position) -> Result<u32> {
+        let mut v = 0u32;
+        for _ in 0..4 {
+            let d = self
+                .bump()
+                .and_then(|c| c.to_digit(16))
+                .ok_or_else(|| Error::new("expected 4 hex digits in \\u escape", pos))?;
+            v = v * 16 + d;
+        }
+        Ok(v)
+    }
+}
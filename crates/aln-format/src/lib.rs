@@ -0,0 +1,261 @@
+//! ALN shard format: lexer, parser, serde `Deserializer` and `Serializer`,
+//! plus canonical and pretty writers.
+//!
+//! ALN is the record syntax used by `.tsafe.aln`, `.vkernel.aln`,
+//! `.rohmodel.aln` and `neuro-workspace.manifest.aln`: type-tagged records
+//! (`TsafeKernel { ... }`), unquoted keys, `#` line comments and trailing
+//! commas. Plain JSON is a valid subset, so JSON-shaped shards keep loading.
+//!
+//! Errors always carry a line and column so a broken shard can be fixed
+//! without guessing.
+//!
+//! Hexstamps hash the canonical form ([`to_canonical_json`] for JSON
+//! records, [`to_canonical_string`] for ALN), never whatever bytes a writer
+//! happened to produce, so two nodes agree on the stamp of the same record.
+
+mod de;
+mod error;
+mod lexer;
+mod parser;
+mod ser;
+mod value;
+mod write;
+
+use std::path::Path;
+
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+
+pub use de::NodeDeserializer;
+pub use error::{Error, Position, Result};
+pub use ser::{to_value, Serializer};
+pub use value::{Field, Node, Record, Value};
+
+/// Parse an ALN document into a positioned value tree.
+pub fn parse(src: &str) -> Result<Node> {
+    parser::parse(src)
+}
+
+/// Parse and deserialize an ALN document into `T`.
+pub fn from_str<T: DeserializeOwned>(src: &str) -> Result<T> {
+    let node = parse(src)?;
+    from_node(&node)
+}
+
+/// Deserialize `T` from an already parsed node (e.g. a sub-record).
+pub fn from_node<T: DeserializeOwned>(node: &Node) -> Result<T> {
+    T::deserialize(NodeDeserializer::new(node)).map_err(|e| e.at(node.pos))
+}
+
+/// Read and deserialize an ALN shard from disk.
+pub fn from_path<T: DeserializeOwned, P: AsRef<Path>>(path: P) -> anyhow::Result<T> {
+    let path = path.as_ref();
+    let text = std::fs::read_to_string(path)?;
+    from_str(&text).map_err(|e| anyhow::anyhow!("{}: {}", path.display(), e))
+}
+
+/// Pretty ALN for `value`, outer struct tagged with its type name.
+pub fn to_string<T: Serialize + ?Sized>(value: &T) -> Result<String> {
+    Ok(to_value(value)?.to_pretty())
+}
+
+/// Canonical ALN for `value`: sorted keys, normalized floats, no whitespace.
+pub fn to_canonical_string<T: Serialize + ?Sized>(value: &T) -> Result<String> {
+    Ok(to_value(value)?.to_canonical())
+}
+
+/// Canonical JSON for `value`, the preimage for JSON-stored hexstamps.
+pub fn to_canonical_json<T: Serialize + ?Sized>(value: &T) -> Result<String> {
+    Ok(to_value(value)?.to_canonical_json())
+}
+
+/// Lowercase SHA-256 hex of `value`'s canonical JSON, with the named
+/// top-level fields left out. Records that store their own hexstamp (and
+/// any signature over it) pass those field names in `omit`.
+pub fn canonical_hexstamp<T: Serialize + ?Sized>(value: &T, omit: &[&str]) -> Result<String> {
+    let mut value = to_value(value)?;
+    if let Value::Record(r) = &mut value {
+        r.fields.retain(|f| !omit.contains(&f.key.as_str()));
+    }
+    Ok(hex::encode(Sha256::digest(value.to_canonical_json())))
+}
+
+/// Reformat an ALN (or JSON) document, keeping field order.
+pub fn format(src: &str) -> Result<String> {
+    Ok(parse(src)?.value.to_pretty())
+}
+
+/// Rewrite an ALN (or JSON) document in canonical ALN.
+pub fn canonicalize(src: &str) -> Result<String> {
+    Ok(parse(src)?.value.to_canonical())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde::{Deserialize, Serialize};
+    use std::collections::HashMap;
+
+    #[derive(Debug, Deserialize)]
+    struct TsafeAxis {
+        name: String,
+        min: f32,
+        max: f32,
+    }
+
+    #[derive(Debug, Deserialize)]
+    struct TsafeKernel {
+        axes: Vec<TsafeAxis>,
+        #[serde(default)]
+        tags: Vec<String>,
+    }
+
+    #[test]
+    fn parses_shipped_tsafe_shard() {
+        let src = include_str!("../../../shards/root/tsafe.aln");
+        let kernel: TsafeKernel = from_str(src).unwrap();
+        assert_eq!(kernel.axes.len(), 6);
+        let roh = kernel.axes.iter().find(|a| a.name == "roh_global").unwrap();
+        assert_eq!((roh.min, roh.max), (0.0, 0.30));
+        assert!(kernel.tags.contains(&"PostQuantumRequired".to_string()));
+    }
+
+    #[derive(Debug, Deserialize)]
+    struct ViabilityConstraint {
+        expr: String,
+    }
+
+    #[derive(Debug, Deserialize)]
+    struct ViabilityKernel {
+        constraints: Vec<ViabilityConstraint>,
+        #[serde(default)]
+        labels: Vec<String>,
+    }
+
+    #[derive(Debug, Deserialize)]
+    struct SovereignConfigPaths {
+        vkernel: String,
+    }
+
+    #[derive(Debug, Deserialize)]
+    struct NeuroWorkspaceManifest {
+        shardsroot: String,
+        sovereign_config: SovereignConfigPaths,
+        #[serde(default)]
+        routes: Vec<String>,
+    }
+
+    #[test]
+    fn parses_all_shipped_shards() {
+        let vkernel: ViabilityKernel =
+            from_str(include_str!("../../../shards/root/vkernel.aln")).unwrap();
+        assert_eq!(vkernel.constraints.len(), 5);
+        assert_eq!(vkernel.constraints[0].expr, "roh_global <= 0.30");
+        assert!(vkernel.labels.contains(&"QuantumSovereigntyEnvelope".to_string()));
+
+        let manifest: NeuroWorkspaceManifest =
+            from_str(include_str!("../../../neuroworkspace/neuro-workspace.manifest.aln"))
+                .unwrap();
+        assert_eq!(manifest.shardsroot, "shards/root");
+        assert_eq!(manifest.sovereign_config.vkernel, "shards/root/vkernel.aln");
+        assert!(manifest.routes.contains(&"CHAT".to_string()));
+    }
+
+    #[test]
+    fn json_is_a_subset() {
+        #[derive(Deserialize)]
+        struct RohModel {
+            ceiling: f32,
+            weights: HashMap<String, f32>,
+        }
+        let m: RohModel =
+            from_str(r#"{"ceiling": 0.3, "weights": {"lifeforce_load": 0.5}}"#).unwrap();
+        assert_eq!(m.ceiling, 0.3);
+        assert_eq!(m.weights["lifeforce_load"], 0.5);
+    }
+
+    #[test]
+    fn errors_carry_line_and_column() {
+        let src = "TsafeKernel {\n  axes: [\n    { name: \"x\" min: 0.0 },\n  ]\n}";
+        let err = from_str::<TsafeKernel>(src).unwrap_err();
+        assert_eq!((err.line(), err.column()), (Some(3), Some(17)));
+
+        let err = from_str::<TsafeKernel>("TsafeKernel { axes: [ { name: 1, min: 0, max: 1 } ] }")
+            .unwrap_err();
+        assert_eq!((err.line(), err.column()), (Some(1), Some(31)));
+        assert!(err.message.contains("expected string"));
+    }
+
+    #[test]
+    fn tag_must_match_target_struct() {
+        let err = from_str::<TsafeKernel>("ViabilityKernel { axes: [] }").unwrap_err();
+        assert!(err.message.contains("ViabilityKernel"));
+    }
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    enum Mode {
+        Normal,
+        Degraded { since: u32 },
+        Pinned(String),
+    }
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Proposal {
+        roh_after: f32,
+        mode: Mode,
+        modes: Vec<Mode>,
+        note: Option<String>,
+        weights: HashMap<String, f32>,
+        bounds: (f32, f32),
+    }
+
+    fn proposal() -> Proposal {
+        Proposal {
+            roh_after: 0.3,
+            mode: Mode::Normal,
+            modes: vec![
+                Mode::Degraded { since: 3 },
+                Mode::Pinned("a \"b\"\n".into()),
+            ],
+            note: None,
+            weights: [("z".to_string(), 0.5), ("a".to_string(), -0.0)].into(),
+            bounds: (0.0, 1.0),
+        }
+    }
+
+    #[test]
+    fn canonical_forms_are_sorted_and_normalized() {
+        let json = to_canonical_json(&proposal()).unwrap();
+        assert_eq!(
+            json,
+            r#"{"bounds":[0.0,1.0],"mode":"Normal","modes":[{"Degraded":{"since":3}},{"Pinned":"a \"b\"\n"}],"note":null,"roh_after":0.3,"weights":{"a":0.0,"z":0.5}}"#
+        );
+        // The same record read back from differently laid out JSON hashes the same.
+        let relaid: Proposal = from_str(&format(&json).unwrap()).unwrap();
+        assert_eq!(to_canonical_json(&relaid).unwrap(), json);
+
+        let aln = to_canonical_string(&proposal()).unwrap();
+        assert!(aln.starts_with("Proposal{bounds:[0.0,1.0],mode:Normal,"));
+        assert_eq!(from_str::<Proposal>(&aln).unwrap(), proposal());
+    }
+
+    #[test]
+    fn formatter_round_trips_shipped_shards() {
+        for src in [
+            include_str!("../../../shards/root/tsafe.aln"),
+            include_str!("../../../shards/root/vkernel.aln"),
+            include_str!("../../../neuroworkspace/neuro-workspace.manifest.aln"),
+        ] {
+            let pretty = format(src).unwrap();
+            assert_eq!(format(&pretty).unwrap(), pretty);
+            assert_eq!(canonicalize(&pretty).unwrap(), canonicalize(src).unwrap());
+            // Only a lone long string may overrun the width.
+            assert!(pretty
+                .lines()
+                .all(|l| l.len() <= 80 || l.trim_start().starts_with('"')));
+        }
+        let pretty = to_string(&proposal()).unwrap();
+        assert_eq!(from_str::<Proposal>(&pretty).unwrap(), proposal());
+    }
+}
@@ -0,0 +1,176 @@
+//! Recursive-descent parser producing a positioned [`Node`] tree.
+//!
+//! Grammar (informal):
+//!
+//! ```text
+//! document := value EOF
+//! value    := record | list | STRING | NUMBER | IDENT
+//! record   := IDENT? "{" (key ":" value ("," key ":" value)* ","?)? "}"
+//! list     := "[" (value ("," value)* ","?)? "]"
+//! key      := IDENT | STRING
+//! ```
+//!
+//! `true`, `false` and `null` are reserved identifiers.
+
+use crate::error::{Error, Result};
+use crate::lexer::{Lexer, Spanned, Token};
+use crate::value::{Field, Node, Record, Value};
+
+/// Nesting limit; shards are shallow and this keeps hostile input bounded.
+const MAX_DEPTH: usize = 128;
+
+pub fn parse(src: &str) -> Result<Node> {
+    let tokens = Lexer::new(src).tokenize()?;
+    let mut p = Parser { tokens, idx: 0, depth: 0 };
+    let node = p.value()?;
+    let tail = p.peek();
+    if tail.token != Token::Eof {
+        return Err(Error::new(
+            format!("trailing {} after document", tail.token.describe()),
+            tail.pos,
+        ));
+    }
+    Ok(node)
+}
+
+struct Parser {
+    tokens: Vec<Spanned>,
+    idx: usize,
+    depth: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> &Spanned {
+        &self.tokens[self.idx]
+    }
+
+    fn peek_at(&self, offset: usize) -> &Token {
+        let i = (self.idx + offset).min(self.tokens.len() - 1);
+        &self.tokens[i].token
+    }
+
+    fn next(&mut self) -> Spanned {
+        let t = self.tokens[self.idx].clone();
+        if self.idx < self.tokens.len() - 1 {
+            self.idx += 1;
+        }
+        t
+    }
+
+    fn expect(&mut self, want: Token) -> Result<Spanned> {
+        let t = self.next();
+        if t.token != want {
+            return Err(Error::new(
+                format!("expected {}, found {}", want.describe(), t.token.describe()),
+                t.pos,
+            ));
+        }
+        Ok(t)
+    }
+
+    fn value(&mut self) -> Result<Node> {
+        let start = self.peek().pos;
+        self.depth += 1;
+        if self.depth > MAX_DEPTH {
+            return Err(Error::new("nesting too deep", start));
+        }
+        let value = match self.peek().token.clone() {
+            Token::LBrace => Value::Record(self.record(None)?),
+            Token::LBracket => self.list()?,
+            Token::Ident(name) if *self.peek_at(1) == Token::LBrace => {
+                self.next();
+                Value::Record(self.record(Some(name))?)
+            }
+            Token::Ident(name) => {
+                self.next();
+                match name.as_str() {
+                    "true" => Value::Bool(true),
+                    "false" => Value::Bool(false),
+                    "null" => Value::Null,
+                    _ => Value::Ident(name),
+                }
+            }
+            Token::Str(s) => {
+                self.next();
+                Value::Str(s)
+            }
+            Token::Int(i) => {
+                self.next();
+                Value::Int(i)
+            }
+            Token::Float(f) => {
+                self.next();
+                Value::Float(f)
+            }
+            other => {
+                return Err(Error::new(
+                    format!("expected a value, found {}", other.describe()),
+                    start,
+                ));
+            }
+        };
+        self.depth -= 1;
+        Ok(Node { value, pos: start })
+    }
+
+    fn record(&mut self, tag: Option<String>) -> Result<Record> {
+        self.expect(Token::LBrace)?;
+        let mut fields: Vec<Field> = Vec::new();
+        loop {
+            let t = self.next();
+            let key = match t.token {
+                Token::RBrace => break,
+                Token::Ident(k) | Token::Str(k) => k,
+                other => {
+                    return Err(Error::new(
+                        format!("expected field name or `}}`, found {}", other.describe()),
+                        t.pos,
+                    ));
+                }
+            };
+            if fields.iter().any(|f| f.key == key) {
+                return Err(Error::new(format!("duplicate field `{}`", key), t.pos));
+            }
+            self.expect(Token::Colon)?;
+            let value = self.value()?;
+            fields.push(Field { key, key_pos: t.pos, value });
+
+            let sep = self.next();
+            match sep.token {
+                Token::Comma => continue,
+                Token::RBrace => break,
+                other => {
+                    return Err(Error::new(
+                        format!("expected `,` or `}}`, found {}", other.describe()),
+                        sep.pos,
+                    ));
+                }
+            }
+        }
+        Ok(Record { tag, fields })
+    }
+
+    fn list(&mut self) -> Result<Value> {
+        self.expect(Token::LBracket)?;
+        let mut items = Vec::new();
+        loop {
+            if self.peek().token == Token::RBracket {
+                self.next();
+                break;
+            }
+            items.push(self.value()?);
+            let sep = self.next();
+            match sep.token {
+                Token::Comma => continue,
+                Token::RBracket => break,
+                other => {
+                    return Err(Error::new(
+                        format!("expected `,` or `]`, found {}", other.describe()),
+                        sep.pos,
+                    ));
+                }
+            }
+        }
+        Ok(Value::List(items))
+    }
+}
@@ -0,0 +1,396 @@
+//! serde `Serializer` producing an ALN [`Value`] tree.
+//!
+//! Mapping rules (the inverse of `de`):
+//! - structs become records; only the outermost one carries its type tag,
+//! - maps become untagged records and need string-like keys,
+//! - enums are externally tagged by their serde variant name: a unit
+//!   variant is a bare identifier, any other variant is `{ Variant: payload }`,
+//! - `f32` is widened through its shortest decimal form, so `0.3f32` is
+//!   `0.3` rather than `0.30000001192092896`; `-0.0` becomes `0.0`,
+//! - `None` and `()` are `null`, `Some(x)` is `x`.
+
+use serde::ser::{self, Serialize};
+
+use crate::error::{Error, Position, Result};
+use crate::value::{Field, Node, Record, Value};
+
+/// Serialize `value` into an ALN value tree.
+pub fn to_value<T: Serialize + ?Sized>(value: &T) -> Result<Value> {
+    value.serialize(Serializer { top: true })
+}
+
+fn nested<T: Serialize + ?Sized>(value: &T) -> Result<Node> {
+    Ok(node(value.serialize(Serializer { top: false })?))
+}
+
+fn node(value: Value) -> Node {
+    Node {
+        value,
+        pos: Position::default(),
+    }
+}
+
+fn float(f: f64) -> Result<Value> {
+    if !f.is_finite() {
+        return Err(ser::Error::custom(format!("non-finite float {}", f)));
+    }
+    Ok(Value::Float(if f == 0.0 { 0.0 } else { f }))
+}
+
+fn variant_record(variant: &str, payload: Value) -> Value {
+    Value::Record(Record {
+        tag: None,
+        fields: vec![Field {
+            key: variant.to_string(),
+            key_pos: Position::default(),
+            value: node(payload),
+        }],
+    })
+}
+
+pub struct Serializer {
+    /// Whether this is the document root, the only record that is tagged.
+    top: bool,
+}
+
+impl ser::Serializer for Serializer {
+    type Ok = Value;
+    type Error = Error;
+    type SerializeSeq = SeqSerializer;
+    type SerializeTuple = SeqSerializer;
+    type SerializeTupleStruct = SeqSerializer;
+    type SerializeTupleVariant = SeqSerializer;
+    type SerializeMap = MapSerializer;
+    type SerializeStruct = MapSerializer;
+    type SerializeStructVariant = MapSerializer;
+
+    fn serialize_bool(self, v: bool) -> Result<Value> {
+        Ok(Value::Bool(v))
+    }
+
+    fn serialize_i8(self, v: i8) -> Result<Value> {
+        Ok(Value::Int(v.into()))
+    }
+
+    fn serialize_i16(self, v: i16) -> Result<Value> {
+        Ok(Value::Int(v.into()))
+    }
+
+    fn serialize_i32(self, v: i32) -> Result<Value> {
+        Ok(Value::Int(v.into()))
+    }
+
+    fn serialize_i64(self, v: i64) -> Result<Value> {
+        Ok(Value::Int(v))
+    }
+
+    fn serialize_u8(self, v: u8) -> Result<Value> {
+        Ok(Value::Int(v.into()))
+    }
+
+    fn serialize_u16(self, v: u16) -> Result<Value> {
+        Ok(Value::Int(v.into()))
+    }
+
+    fn serialize_u32(self, v: u32) -> Result<Value> {
+        Ok(Value::Int(v.into()))
+    }
+
+    fn serialize_u64(self, v: u64) -> Result<Value> {
+        i64::try_from(v)
+            .map(Value::Int)
+            .map_err(|_| ser::Error::custom(format!("integer {} out of range for i64", v)))
+    }
+
+    fn serialize_f32(self, v: f32) -> Result<Value> {
+        float(v.to_string().parse().unwrap_or(v as f64))
+    }
+
+    fn serialize_f64(self, v: f64) -> Result<Value> {
+        float(v)
+    }
+
+    fn serialize_char(self, v: char) -> Result<Value> {
+        Ok(Value::Str(v.to_string()))
+    }
+
+    fn serialize_str(self, v: &str) -> Result<Value> {
+        Ok(Value::Str(v.to_string()))
+    }
+
+    fn serialize_bytes(self, v: &[u8]) -> Result<Value> {
+        Ok(Value::List(
+            v.iter().map(|b| node(Value::Int((*b).into()))).collect(),
+        ))
+    }
+
+    fn serialize_none(self) -> Result<Value> {
+        Ok(Value::Null)
+    }
+
+    fn serialize_some<T: Serialize + ?Sized>(self, value: &T) -> Result<Value> {
+        value.serialize(self)
+    }
+
+    fn serialize_unit(self) -> Result<Value> {
+        Ok(Value::Null)
+    }
+
+    fn serialize_unit_struct(self, _name: &'static str) -> Result<Value> {
+        Ok(Value::Null)
+    }
+
+    fn serialize_unit_variant(
+        self,
+        _name: &'static str,
+        _index: u32,
+        variant: &'static str,
+    ) -> Result<Value> {
+        Ok(Value::Ident(variant.to_string()))
+    }
+
+    fn serialize_newtype_struct<T: Serialize + ?Sized>(
+        self,
+        _name: &'static str,
+        value: &T,
+    ) -> Result<Value> {
+        value.serialize(self)
+    }
+
+    fn serialize_newtype_variant<T: Serialize + ?Sized>(
+        self,
+        _name: &'static str,
+        _index: u32,
+        variant: &'static str,
+        value: &T,
+    ) -> Result<Value> {
+        Ok(variant_record(variant, nested(value)?.value))
+    }
+
+    fn serialize_seq(self, len: Option<usize>) -> Result<SeqSerializer> {
+        Ok(SeqSerializer {
+            items: Vec::with_capacity(len.unwrap_or(0)),
+            variant: None,
+        })
+    }
+
+    fn serialize_tuple(self, len: usize) -> Result<SeqSerializer> {
+        self.serialize_seq(Some(len))
+    }
+
+    fn serialize_tuple_struct(self, _name: &'static str, len: usize) -> Result<SeqSerializer> {
+        self.serialize_seq(Some(len))
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        _name: &'static str,
+        _index: u32,
+        variant: &'static str,
+        len: usize,
+    ) -> Result<SeqSerializer> {
+        Ok(SeqSerializer {
+            items: Vec::with_capacity(len),
+            variant: Some(variant),
+        })
+    }
+
+    fn serialize_map(self, _len: Option<usize>) -> Result<MapSerializer> {
+        Ok(MapSerializer::new(None, None))
+    }
+
+    fn serialize_struct(self, name: &'static str, _len: usize) -> Result<MapSerializer> {
+        Ok(MapSerializer::new(self.top.then(|| name.to_string()), None))
+    }
+
+    fn serialize_struct_variant(
+        self,
+        _name: &'static str,
+        _index: u32,
+        variant: &'static str,
+        _len: usize,
+    ) -> Result<MapSerializer> {
+        Ok(MapSerializer::new(None, Some(variant)))
+    }
+}
+
+pub struct SeqSerializer {
+    items: Vec<Node>,
+    variant: Option<&'static str>,
+}
+
+impl SeqSerializer {
+    fn push<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<()> {
+        self.items.push(nested(value)?);
+        Ok(())
+    }
+
+    fn finish(self) -> Value {
+        let list = Value::List(self.items);
+        match self.variant {
+            Some(variant) => variant_record(variant, list),
+            None => list,
+        }
+    }
+}
+
+impl ser::SerializeSeq for SeqSerializer {
+    type Ok = Value;
+    type Error = Error;
+
+    fn serialize_element<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<()> {
+        self.push(value)
+    }
+
+    fn end(self) -> Result<Value> {
+        Ok(self.finish())
+    }
+}
+
+impl ser::SerializeTuple for SeqSerializer {
+    type Ok = Value;
+    type Error = Error;
+
+    fn serialize_element<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<()> {
+        self.push(value)
+    }
+
+    fn end(self) -> Result<Value> {
+        Ok(self.finish())
+    }
+}
+
+impl ser::SerializeTupleStruct for SeqSerializer {
+    type Ok = Value;
+    type Error = Error;
+
+    fn serialize_field<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<()> {
+        self.push(value)
+    }
+
+    fn end(self) -> Result<Value> {
+        Ok(self.finish())
+    }
+}
+
+impl ser::SerializeTupleVariant for SeqSerializer {
+    type Ok = Value;
+    type Error = Error;
+
+    fn serialize_field<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<()> {
+        self.push(value)
+    }
+
+    fn end(self) -> Result<Value> {
+        Ok(self.finish())
+    }
+}
+
+pub struct MapSerializer {
+    record: Record,
+    variant: Option<&'static str>,
+    pending_key: Option<String>,
+}
+
+impl MapSerializer {
+    fn new(tag: Option<String>, variant: Option<&'static str>) -> Self {
+        Self {
+            record: Record {
+                tag,
+                fields: Vec::new(),
+            },
+            variant,
+            pending_key: None,
+        }
+    }
+
+    fn insert(&mut self, key: String, value: Node) -> Result<()> {
+        if self.record.fields.iter().any(|f| f.key == key) {
+            return Err(ser::Error::custom(format!("duplicate field `{}`", key)));
+        }
+        self.record.fields.push(Field {
+            key,
+            key_pos: Position::default(),
+            value,
+        });
+        Ok(())
+    }
+
+    fn finish(self) -> Value {
+        let record = Value::Record(self.record);
+        match self.variant {
+            Some(variant) => variant_record(variant, record),
+            None => record,
+        }
+    }
+}
+
+impl ser::SerializeMap for MapSerializer {
+    type Ok = Value;
+    type Error = Error;
+
+    fn serialize_key<T: Serialize + ?Sized>(&mut self, key: &T) -> Result<()> {
+        let key = match nested(key)?.value {
+            Value::Str(s) | Value::Ident(s) => s,
+            Value::Int(i) => i.to_string(),
+            Value::Bool(b) => b.to_string(),
+            other => {
+                return Err(ser::Error::custom(format!(
+                    "record keys must be strings, found {}",
+                    other.kind()
+                )));
+            }
+        };
+        self.pending_key = Some(key);
+        Ok(())
+    }
+
+    fn serialize_value<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<()> {
+        let key = self
+            .pending_key
+            .take()
+            .ok_or_else(|| <Error as ser::Error>::custom("value serialized before key"))?;
+        let value = nested(value)?;
+        self.insert(key, value)
+    }
+
+    fn end(self) -> Result<Value> {
+        Ok(self.finish())
+    }
+}
+
+impl ser::SerializeStruct for MapSerializer {
+    type Ok = Value;
+    type Error = Error;
+
+    fn serialize_field<T: Serialize + ?Sized>(
+        &mut self,
+        key: &'static str,
+        value: &T,
+    ) -> Result<()> {
+        let value = nested(value)?;
+        self.insert(key.to_string(), value)
+    }
+
+    fn end(self) -> Result<Value> {
+        Ok(self.finish())
+    }
+}
+
+impl ser::SerializeStructVariant for MapSerializer {
+    type Ok = Value;
+    type Error = Error;
+
+    fn serialize_field<T: Serialize + ?Sized>(
+        &mut self,
+        key: &'static str,
+        value: &T,
+    ) -> Result<()> {
+        let value = nested(value)?;
+        self.insert(key.to_string(), value)
+    }
+
+    fn end(self) -> Result<Value> {
+        Ok(self.finish())
+    }
+}
@@ -0,0 +1,64 @@
+use crate::error::Position;
+
+/// Parsed ALN value tree. Every node remembers where it started so that
+/// deserialization errors can point back into the shard.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Node {
+    pub value: Value,
+    pub pos: Position,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Value {
+    Null,
+    Bool(bool),
+    Int(i64),
+    Float(f64),
+    Str(String),
+    /// Bare identifier in value position, e.g. `mode: Normal`.
+    Ident(String),
+    List(Vec<Node>),
+    Record(Record),
+}
+
+/// `{ key: value, ... }`, optionally prefixed by a type tag (`TsafeKernel { ... }`).
+#[derive(Debug, Clone, PartialEq)]
+pub struct Record {
+    pub tag: Option<String>,
+    pub fields: Vec<Field>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Field {
+    pub key: String,
+    pub key_pos: Position,
+    pub value: Node,
+}
+
+impl Record {
+    pub fn get(&self, key: &str) -> Option<&Node> {
+        self.fields.iter().find(|f| f.key == key).map(|f| &f.value)
+    }
+}
+
+impl Value {
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Value::Null => "null",
+            Value::Bool(_) => "bool",
+            Value::Int(_) => "integer",
+            Value::Float(_) => "float",
+            Value::Str(_) => "string",
+            Value::Ident(_) => "identifier",
+            Value::List(_) => "list",
+            Value::Record(_) => "record",
+        }
+    }
+
+    pub fn as_record(&self) -> Option<&Record> {
+        match self {
+            Value::Record(r) => Some(r),
+            _ => None,
+        }
+    }
+}
@@ -0,0 +1,222 @@
+//! Writers for [`Value`] trees: canonical ALN, canonical JSON and a
+//! pretty-printer.
+//!
+//! Canonical output is the hashing preimage for hexstamps, so it is fully
+//! determined by the value: record fields sorted by key (byte order), no
+//! whitespace, floats in shortest round-trip form (always with a `.` or an
+//! exponent, so they never read back as integers), strings escaped only
+//! where JSON requires it. The JSON form drops record tags and writes
+//! identifiers as strings; it is what `serde_json` would read back.
+//!
+//! The pretty form keeps field order and is meant for people; it parses
+//! back to the same value. Comments are not part of the value tree and do
+//! not survive formatting.
+
+use std::fmt::Write;
+
+use crate::value::{Field, Record, Value};
+
+/// Lines wider than this are broken over several lines by the pretty-printer.
+const WIDTH: usize = 80;
+const INDENT: &str = "  ";
+
+impl Value {
+    /// Canonical ALN text, e.g. `TsafeKernel{axes:[{max:0.3,min:0.0,name:"x"}]}`.
+    pub fn to_canonical(&self) -> String {
+        let mut out = String::new();
+        canonical(self, &mut out, false);
+        out
+    }
+
+    /// Canonical JSON text: sorted keys, no whitespace, no tags.
+    pub fn to_canonical_json(&self) -> String {
+        let mut out = String::new();
+        canonical(self, &mut out, true);
+        out
+    }
+
+    /// Indented ALN text with the fields in their original order.
+    pub fn to_pretty(&self) -> String {
+        let mut out = String::new();
+        pretty(self, &mut out, 0, 0);
+        out.push('\n');
+        out
+    }
+}
+
+fn sorted(record: &Record) -> Vec<&Field> {
+    let mut fields: Vec<&Field> = record.fields.iter().collect();
+    fields.sort_by(|a, b| a.key.as_bytes().cmp(b.key.as_bytes()));
+    fields
+}
+
+fn canonical(value: &Value, out: &mut String, json: bool) {
+    match value {
+        Value::Record(r) => {
+            if let (Some(tag), false) = (&r.tag, json) {
+                out.push_str(tag);
+            }
+            out.push('{');
+            for (i, f) in sorted(r).into_iter().enumerate() {
+                if i > 0 {
+                    out.push(',');
+                }
+                if json {
+                    string(&f.key, out);
+                } else {
+                    key(&f.key, out);
+                }
+                out.push(':');
+                canonical(&f.value.value, out, json);
+            }
+            out.push('}');
+        }
+        Value::List(items) => {
+            out.push('[');
+            for (i, item) in items.iter().enumerate() {
+                if i > 0 {
+                    out.push(',');
+                }
+                canonical(&item.value, out, json);
+            }
+            out.push(']');
+        }
+        Value::Ident(s) if json => string(s, out),
+        scalar => self::scalar(scalar, out),
+    }
+}
+
+/// `used` is the width already taken on the current line (indent, key).
+fn pretty(value: &Value, out: &mut String, depth: usize, used: usize) {
+    let inline = inline(value);
+    // One column for the trailing separator.
+    if used + inline.len() < WIDTH {
+        out.push_str(&inline);
+        return;
+    }
+    let pad = INDENT.repeat(depth + 1);
+    match value {
+        Value::Record(r) => {
+            if let Some(tag) = &r.tag {
+                out.push_str(tag);
+                out.push(' ');
+            }
+            out.push_str("{\n");
+            for (i, f) in r.fields.iter().enumerate() {
+                let start = out.len();
+                out.push_str(&pad);
+                key(&f.key, out);
+                out.push_str(": ");
+                let used = out.len() - start;
+                pretty(&f.value.value, out, depth + 1, used);
+                out.push_str(if i + 1 < r.fields.len() { ",\n" } else { "\n" });
+            }
+            out.push_str(&INDENT.repeat(depth));
+            out.push('}');
+        }
+        Value::List(items) => {
+            out.push_str("[\n");
+            for (i, item) in items.iter().enumerate() {
+                out.push_str(&pad);
+                pretty(&item.value, out, depth + 1, pad.len());
+                out.push_str(if i + 1 < items.len() { ",\n" } else { "\n" });
+            }
+            out.push_str(&INDENT.repeat(depth));
+            out.push(']');
+        }
+        // A long string stays on one line.
+        _ => out.push_str(&inline),
+    }
+}
+
+/// Single-line pretty form, `Tag { a: 1, b: [1, 2] }`.
+fn inline(value: &Value) -> String {
+    let mut out = String::new();
+    match value {
+        Value::Record(r) => {
+            if let Some(tag) = &r.tag {
+                out.push_str(tag);
+                out.push(' ');
+            }
+            if r.fields.is_empty() {
+                out.push_str("{}");
+                return out;
+            }
+            out.push_str("{ ");
+            for (i, f) in r.fields.iter().enumerate() {
+                if i > 0 {
+                    out.push_str(", ");
+                }
+                key(&f.key, &mut out);
+                out.push_str(": ");
+                out.push_str(&inline(&f.value.value));
+            }
+            out.push_str(" }");
+        }
+        Value::List(items) => {
+            out.push('[');
+            for (i, item) in items.iter().enumerate() {
+                if i > 0 {
+                    out.push_str(", ");
+                }
+                out.push_str(&inline(&item.value));
+            }
+            out.push(']');
+        }
+        scalar => self::scalar(scalar, &mut out),
+    }
+    out
+}
+
+fn scalar(value: &Value, out: &mut String) {
+    match value {
+        Value::Null => out.push_str("null"),
+        Value::Bool(b) => out.push_str(if *b { "true" } else { "false" }),
+        Value::Int(i) => write!(out, "{}", i).unwrap(),
+        Value::Float(f) => float(*f, out),
+        Value::Str(s) => string(s, out),
+        Value::Ident(s) if is_ident(s) => out.push_str(s),
+        Value::Ident(s) => string(s, out),
+        Value::List(_) | Value::Record(_) => unreachable!("not a scalar"),
+    }
+}
+
+/// Shortest round-trip form; `{:?}` keeps the `.0` on integral values.
+fn float(f: f64, out: &mut String) {
+    let f = if f == 0.0 { 0.0 } else { f };
+    write!(out, "{:?}", f).unwrap();
+}
+
+fn string(s: &str, out: &mut String) {
+    out.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            '\u{0008}' => out.push_str("\\b"),
+            '\u{000C}' => out.push_str("\\f"),
+            c if (c as u32) < 0x20 => write!(out, "\\u{:04x}", c as u32).unwrap(),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+}
+
+fn key(k: &str, out: &mut String) {
+    if is_ident(k) {
+        out.push_str(k);
+    } else {
+        string(k, out);
+    }
+}
+
+/// Whether `s` lexes back as the same bare identifier.
+fn is_ident(s: &str) -> bool {
+    let mut chars = s.chars();
+    matches!(chars.next(), Some(c) if c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || matches!(c, '_' | '-' | '.'))
+        && !matches!(s, "true" | "false" | "null")
+}
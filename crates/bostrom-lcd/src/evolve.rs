@@ -0,0 +1,104 @@
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+use thiserror::Error;
+
+use crate::{LcdClient, LcdError};
+
+#[derive(Debug, Error)]
+pub enum InclusionError {
+    #[error("EVOLVE token carries no Bostrom tx hash")]
+    NoTxHash,
+    #[error("tx {0} is not included on chain")]
+    NotIncluded(String),
+    #[error("tx {hash} failed on chain with code {code}")]
+    Failed { hash: String, code: u32 },
+    #[error("tx {hash} memo `{memo}` does not commit to hexstamp {hexstamp}")]
+    MemoMismatch {
+        hash: String,
+        memo: String,
+        hexstamp: String,
+    },
+    #[error("tx {hash} has {confirmations} confirmation(s), {required} required")]
+    NotFinal {
+        hash: String,
+        confirmations: u64,
+        required: u64,
+    },
+    #[error(transparent)]
+    Lcd(#[from] LcdError),
+}
+
+/// Checks that an EVOLVE token's hexstamp was committed on Bostrom by a
+/// successful, finalized tx. The tx memo must equal the token hexstamp, so a
+/// token cannot borrow someone else's tx.
+///
+/// Finalized results are cached by hexstamp; anything else is re-queried.
+/// Transport errors and timeouts are errors, never approvals.
+pub struct LcdEvolveVerifier {
+    client: LcdClient,
+    min_confirmations: u64,
+    /// hexstamp -> tx hash that finalized it.
+    finalized: Mutex<HashMap<String, String>>,
+}
+
+impl LcdEvolveVerifier {
+    pub fn new(client: LcdClient, min_confirmations: u64) -> Self {
+        Self {
+            client,
+            min_confirmations: min_confirmations.max(1),
+            finalized: Mutex::new(HashMap::new()),
+        }
+    }
+
+    pub fn verify_inclusion(&self, hexstamp: &str, tx_hash: &str) -> Result<(), InclusionError> {
+        if tx_hash.is_empty() {
+            return Err(InclusionError::NoTxHash);
+        }
+        if self.cached(hexstamp).as_deref() == Some(tx_hash) {
+            return Ok(());
+        }
+
+        let tx = self
+            .client
+            .tx(tx_hash)?
+            .ok_or_else(|| InclusionError::NotIncluded(tx_hash.to_string()))?;
+        if tx.code != 0 {
+            return Err(InclusionError::Failed {
+                hash: tx.hash,
+                code: tx.code,
+            });
+        }
+        if tx.memo.trim() != hexstamp {
+            return Err(InclusionError::MemoMismatch {
+                hash: tx.hash,
+                memo: tx.memo,
+                hexstamp: hexstamp.to_string(),
+            });
+        }
+
+        let latest = self.client.latest_height()?;
+        let confirmations = (latest + 1).saturating_sub(tx.height);
+        if confirmations < self.min_confirmations {
+            return Err(InclusionError::NotFinal {
+                hash: tx.hash,
+                confirmations,
+                required: self.min_confirmations,
+            });
+        }
+
+        self.finalized
+            .lock()
+            .expect("evolve cache lock poisoned")
+            .insert(hexstamp.to_string(), tx_hash.to_string());
+        Ok(())
+    }
+
+    fn cached(&self, hexstamp: &str) -> Option<String> {
+        self.finalized
+            .lock()
+            .expect("evolve cache lock poisoned")
+            .get(hexstamp)
+            .cloned()
+    }
+}
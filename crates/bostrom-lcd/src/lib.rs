@@ -0,0 +1,221 @@
+//! Blocking client for the Bostrom (Cosmos SDK) LCD REST endpoints used by
+//! EVOLVE and stake verification: bank balances, staking delegations, tx
+//! lookup by hash and latest block height.
+
+mod evolve;
+
+use std::time::Duration;
+
+use serde::Deserialize;
+use thiserror::Error;
+
+pub use evolve::{InclusionError, LcdEvolveVerifier};
+
+#[derive(Debug, Error)]
+pub enum LcdError {
+    #[error("LCD request to {url} failed: {message}")]
+    Transport { url: String, message: String },
+    #[error("LCD returned HTTP {status} for {url}")]
+    Status { url: String, status: u16 },
+    #[error("LCD response from {url} is malformed: {message}")]
+    Malformed { url: String, message: String },
+    #[error("`{0}` is not a 64-hex-digit tx hash")]
+    BadTxHash(String),
+}
+
+/// A coin amount; Cosmos encodes amounts as decimal strings.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct Coin {
+    pub denom: String,
+    pub amount: String,
+}
+
+impl Coin {
+    fn value(&self) -> Option<u128> {
+        self.amount.parse().ok()
+    }
+}
+
+/// The parts of a tx lookup EVOLVE verification cares about.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TxInfo {
+    pub hash: String,
+    pub height: u64,
+    /// ABCI result code; `0` means the tx executed successfully.
+    pub code: u32,
+    pub memo: String,
+}
+
+#[derive(Deserialize)]
+struct BalancesResponse {
+    balances: Vec<Coin>,
+}
+
+#[derive(Deserialize)]
+struct DelegationsResponse {
+    delegation_responses: Vec<DelegationResponse>,
+}
+
+#[derive(Deserialize)]
+struct DelegationResponse {
+    balance: Coin,
+}
+
+#[derive(Deserialize)]
+struct TxResponseEnvelope {
+    tx: TxEnvelope,
+    tx_response: TxResponse,
+}
+
+#[derive(Deserialize)]
+struct TxEnvelope {
+    body: TxBody,
+}
+
+#[derive(Deserialize)]
+struct TxBody {
+    #[serde(default)]
+    memo: String,
+}
+
+#[derive(Deserialize)]
+struct TxResponse {
+    txhash: String,
+    height: String,
+    code: u32,
+}
+
+#[derive(Deserialize)]
+struct LatestBlockResponse {
+    block: Block,
+}
+
+#[derive(Deserialize)]
+struct Block {
+    header: Header,
+}
+
+#[derive(Deserialize)]
+struct Header {
+    height: String,
+}
+
+/// LCD endpoint client. Every request is bounded by `timeout`.
+#[derive(Debug, Clone)]
+pub struct LcdClient {
+    base_url: String,
+    agent: ureq::Agent,
+}
+
+impl LcdClient {
+    pub fn new(base_url: &str, timeout: Duration) -> Self {
+        let agent = ureq::AgentBuilder::new().timeout(timeout).build();
+        Self {
+            base_url: base_url.trim_end_matches('/').to_string(),
+            agent,
+        }
+    }
+
+    pub fn base_url(&self) -> &str {
+        &self.base_url
+    }
+
+    /// `GET path`; `Ok(None)` on 404, which the LCD uses for "not found".
+    fn get<T: for<'de> Deserialize<'de>>(&self, path: &str) -> Result<Option<T>, LcdError> {
+        let url = format!("{}{}", self.base_url, path);
+        match self.agent.get(&url).call() {
+            Ok(resp) => resp
+                .into_json::<T>()
+                .map(Some)
+                .map_err(|e| LcdError::Malformed {
+                    url,
+                    message: e.to_string(),
+                }),
+            Err(ureq::Error::Status(404, _)) => Ok(None),
+            Err(ureq::Error::Status(status, _)) => Err(LcdError::Status { url, status }),
+            Err(e) => Err(LcdError::Transport {
+                url,
+                message: e.to_string(),
+            }),
+        }
+    }
+
+    fn amount(
+        path: &str,
+        coins: impl Iterator<Item = Coin>,
+        denom: &str,
+    ) -> Result<u128, LcdError> {
+        let mut total: u128 = 0;
+        for c in coins.filter(|c| c.denom == denom) {
+            let v = c.value().ok_or_else(|| LcdError::Malformed {
+                url: path.to_string(),
+                message: format!("amount `{}` is not an integer", c.amount),
+            })?;
+            total = total.saturating_add(v);
+        }
+        Ok(total)
+    }
+
+    /// Spendable balance of `denom` held by `address`.
+    pub fn balance(&self, address: &str, denom: &str) -> Result<u128, LcdError> {
+        let path = format!("/cosmos/bank/v1beta1/balances/{}", address);
+        let resp: Option<BalancesResponse> = self.get(&path)?;
+        let coins = resp.map(|r| r.balances).unwrap_or_default();
+        Self::amount(&path, coins.into_iter(), denom)
+    }
+
+    /// Total `denom` delegated by `address` across all validators.
+    pub fn delegated_stake(&self, address: &str, denom: &str) -> Result<u128, LcdError> {
+        let path = format!("/cosmos/staking/v1beta1/delegations/{}", address);
+        let resp: Option<DelegationsResponse> = self.get(&path)?;
+        let coins = resp
+            .map(|r| r.delegation_responses)
+            .unwrap_or_default()
+            .into_iter()
+            .map(|d| d.balance);
+        Self::amount(&path, coins, denom)
+    }
+
+    /// Look up a tx by hash; `Ok(None)` if the chain does not know it.
+    pub fn tx(&self, hash: &str) -> Result<Option<TxInfo>, LcdError> {
+        // The hash becomes a URL path segment; never let it carry anything else.
+        if hash.len() != 64 || !hash.bytes().all(|b| b.is_ascii_hexdigit()) {
+            return Err(LcdError::BadTxHash(hash.to_string()));
+        }
+        let path = format!("/cosmos/tx/v1beta1/txs/{}", hash);
+        let Some(resp) = self.get::<TxResponseEnvelope>(&path)? else {
+            return Ok(None);
+        };
+        let height = resp
+            .tx_response
+            .height
+            .parse()
+            .map_err(|_| LcdError::Malformed {
+                url: path.clone(),
+                message: format!("height `{}` is not an integer", resp.tx_response.height),
+            })?;
+        Ok(Some(TxInfo {
+            hash: resp.tx_response.txhash,
+            height,
+            code: resp.tx_response.code,
+            memo: resp.tx.body.memo,
+        }))
+    }
+
+    /// Height of the latest committed block.
+    pub fn latest_height(&self) -> Result<u64, LcdError> {
+        let path = "/cosmos/base/tendermint/v1beta1/blocks/latest";
+        let resp: LatestBlockResponse = self.get(path)?.ok_or_else(|| LcdError::Status {
+            url: format!("{}{}", self.base_url, path),
+            status: 404,
+        })?;
+        resp.block
+            .header
+            .height
+            .parse()
+            .map_err(|_| LcdError::Malformed {
+                url: path.to_string(),
+                message: format!("height `{}` is not an integer", resp.block.header.height),
+            })
+    }
+}
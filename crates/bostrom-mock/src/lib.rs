@@ -0,0 +1,244 @@
+//! Loopback stand-in for the Bostrom LCD endpoints, for EVOLVE and stake
+//! verification tests without network.
+//!
+//! Serves balances, delegations, tx lookup by hash and latest block height
+//! from JSON fixtures. Txs become visible only once the chain height reaches
+//! their inclusion height (`advance_blocks`), responses can be delayed
+//! (`set_latency`), and `reorg` rolls the tip back, dropping recent txs.
+
+use std::collections::HashMap;
+use std::io;
+use std::path::Path;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread::JoinHandle;
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use tiny_http::{Header, Response, Server};
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CoinFixture {
+    pub denom: String,
+    pub amount: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DelegationFixture {
+    pub validator_address: String,
+    pub denom: String,
+    pub amount: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TxFixture {
+    pub hash: String,
+    /// Block the tx is included in; invisible while the chain is below it.
+    pub height: u64,
+    #[serde(default)]
+    pub code: u32,
+    #[serde(default)]
+    pub memo: String,
+}
+
+/// Chain state loaded from JSON.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Fixtures {
+    pub height: u64,
+    #[serde(default)]
+    pub balances: HashMap<String, Vec<CoinFixture>>,
+    #[serde(default)]
+    pub delegations: HashMap<String, Vec<DelegationFixture>>,
+    #[serde(default)]
+    pub txs: Vec<TxFixture>,
+}
+
+impl Fixtures {
+    pub fn from_json_str(text: &str) -> serde_json::Result<Self> {
+        serde_json::from_str(text)
+    }
+
+    pub fn from_json_file<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        let text = std::fs::read_to_string(path)?;
+        Self::from_json_str(&text).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+    }
+}
+
+struct ChainState {
+    fixtures: Fixtures,
+    latency: Duration,
+}
+
+/// Running mock LCD bound to `127.0.0.1` on an ephemeral port. Stops on drop.
+pub struct MockChain {
+    base_url: String,
+    state: Arc<Mutex<ChainState>>,
+    stop: Arc<AtomicBool>,
+    handle: Option<JoinHandle<()>>,
+}
+
+impl MockChain {
+    pub fn start(fixtures: Fixtures) -> io::Result<Self> {
+        let server = Server::http("127.0.0.1:0").map_err(io::Error::other)?;
+        let port = server
+            .server_addr()
+            .to_ip()
+            .map(|a| a.port())
+            .ok_or_else(|| io::Error::other("mock LCD has no IP address"))?;
+
+        let state = Arc::new(Mutex::new(ChainState {
+            fixtures,
+            latency: Duration::ZERO,
+        }));
+        let stop = Arc::new(AtomicBool::new(false));
+
+        let handle = {
+            let state = Arc::clone(&state);
+            let stop = Arc::clone(&stop);
+            std::thread::spawn(move || serve(server, state, stop))
+        };
+
+        Ok(Self {
+            base_url: format!("http://127.0.0.1:{}", port),
+            state,
+            stop,
+            handle: Some(handle),
+        })
+    }
+
+    pub fn base_url(&self) -> &str {
+        &self.base_url
+    }
+
+    /// Delay every response by `latency`, as a slow or distant node would.
+    pub fn set_latency(&self, latency: Duration) {
+        self.lock().latency = latency;
+    }
+
+    pub fn height(&self) -> u64 {
+        self.lock().fixtures.height
+    }
+
+    /// Produce `n` blocks; txs at or below the new height become visible.
+    pub fn advance_blocks(&self, n: u64) -> u64 {
+        let mut s = self.lock();
+        s.fixtures.height += n;
+        s.fixtures.height
+    }
+
+    /// Add a tx; it is visible once the chain reaches `tx.height`.
+    pub fn include_tx(&self, tx: TxFixture) {
+        self.lock().fixtures.txs.push(tx);
+    }
+
+    /// Roll the tip back `depth` blocks. Txs above the new tip are dropped
+    /// and their hashes returned, as an orphaned fork would lose them.
+    pub fn reorg(&self, depth: u64) -> Vec<String> {
+        let mut s = self.lock();
+        let tip = s.fixtures.height.saturating_sub(depth);
+        s.fixtures.height = tip;
+        let (kept, dropped): (Vec<_>, Vec<_>) =
+            s.fixtures.txs.drain(..).partition(|t| t.height <= tip);
+        s.fixtures.txs = kept;
+        dropped.into_iter().map(|t| t.hash).collect()
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, ChainState> {
+        self.state.lock().expect("mock chain lock poisoned")
+    }
+}
+
+impl Drop for MockChain {
+    fn drop(&mut self) {
+        self.stop.store(true, Ordering::SeqCst);
+        if let Some(h) = self.handle.take() {
+            let _ = h.join();
+        }
+    }
+}
+
+fn serve(server: Server, state: Arc<Mutex<ChainState>>, stop: Arc<AtomicBool>) {
+    while !stop.load(Ordering::SeqCst) {
+        let req = match server.recv_timeout(Duration::from_millis(20)) {
+            Ok(Some(req)) => req,
+            Ok(None) => continue,
+            Err(_) => break,
+        };
+        let (status, body, latency) = {
+            let s = state.lock().expect("mock chain lock poisoned");
+            let (status, body) = route(&s.fixtures, req.url());
+            (status, body, s.latency)
+        };
+        if !latency.is_zero() {
+            std::thread::sleep(latency);
+        }
+        let header = Header::from_bytes("Content-Type", "application/json").expect("static header");
+        let resp = Response::from_string(body.to_string())
+            .with_status_code(status)
+            .with_header(header);
+        let _ = req.respond(resp);
+    }
+}
+
+fn not_found(message: &str) -> (u16, serde_json::Value) {
+    (404, json!({ "code": 5, "message": message, "details": [] }))
+}
+
+fn route(chain: &Fixtures, url: &str) -> (u16, serde_json::Value) {
+    let path = url.split('?').next().unwrap_or(url);
+    if let Some(addr) = path.strip_prefix("/cosmos/bank/v1beta1/balances/") {
+        let balances = chain.balances.get(addr).cloned().unwrap_or_default();
+        return (
+            200,
+            json!({ "balances": balances, "pagination": { "total": "0" } }),
+        );
+    }
+    if let Some(addr) = path.strip_prefix("/cosmos/staking/v1beta1/delegations/") {
+        let responses: Vec<_> = chain
+            .delegations
+            .get(addr)
+            .map(|ds| ds.as_slice())
+            .unwrap_or_default()
+            .iter()
+            .map(|d| {
+                json!({
+                    "delegation": {
+                        "delegator_address": addr,
+                        "validator_address": d.validator_address,
+                        "shares": d.amount,
+                    },
+                    "balance": { "denom": d.denom, "amount": d.amount },
+                })
+            })
+            .collect();
+        return (200, json!({ "delegation_responses": responses }));
+    }
+    if let Some(hash) = path.strip_prefix("/cosmos/tx/v1beta1/txs/") {
+        return match chain
+            .txs
+            .iter()
+            .find(|t| t.hash.eq_ignore_ascii_case(hash) && t.height <= chain.height)
+        {
+            Some(t) => (
+                200,
+                json!({
+                    "tx": { "body": { "memo": t.memo, "messages": [] } },
+                    "tx_response": {
+                        "txhash": t.hash,
+                        "height": t.height.to_string(),
+                        "code": t.code,
+                    },
+                }),
+            ),
+            None => not_found(&format!("tx not found: {}", hash)),
+        };
+    }
+    if path == "/cosmos/base/tendermint/v1beta1/blocks/latest" {
+        return (
+            200,
+            json!({ "block": { "header": { "chain_id": "bostrom", "height": chain.height.to_string() } } }),
+        );
+    }
+    not_found("unknown LCD route")
+}
@@ -0,0 +1,95 @@
+use std::time::Duration;
+
+use bostrom_lcd::{InclusionError, LcdClient, LcdEvolveVerifier};
+use bostrom_mock::{Fixtures, MockChain, TxFixture};
+
+const HEXSTAMP: &str = "5f2c0e9a1d";
+const AA: &str = "AA00000000000000000000000000000000000000000000000000000000000000";
+const BB: &str = "BB00000000000000000000000000000000000000000000000000000000000000";
+const CC: &str = "CC00000000000000000000000000000000000000000000000000000000000000";
+const DD: &str = "DD00000000000000000000000000000000000000000000000000000000000000";
+
+fn setup(min_confirmations: u64) -> (MockChain, LcdEvolveVerifier) {
+    let chain = MockChain::start(Fixtures {
+        height: 100,
+        ..Fixtures::default()
+    })
+    .unwrap();
+    let lcd = LcdClient::new(chain.base_url(), Duration::from_millis(500));
+    (chain, LcdEvolveVerifier::new(lcd, min_confirmations))
+}
+
+fn evolve_tx(hash: &str, height: u64, code: u32, memo: &str) -> TxFixture {
+    TxFixture {
+        hash: hash.into(),
+        height,
+        code,
+        memo: memo.into(),
+    }
+}
+
+#[test]
+fn accepts_only_finalized_successful_commitments() {
+    let (chain, v) = setup(3);
+    chain.include_tx(evolve_tx(AA, 100, 0, HEXSTAMP));
+    chain.include_tx(evolve_tx(BB, 90, 11, HEXSTAMP));
+    chain.include_tx(evolve_tx(CC, 90, 0, "someone-else"));
+
+    assert!(matches!(
+        v.verify_inclusion(HEXSTAMP, AA),
+        Err(InclusionError::NotFinal {
+            confirmations: 1,
+            required: 3,
+            ..
+        })
+    ));
+    chain.advance_blocks(2);
+    v.verify_inclusion(HEXSTAMP, AA).unwrap();
+
+    assert!(matches!(
+        v.verify_inclusion(HEXSTAMP, BB),
+        Err(InclusionError::Failed { code: 11, .. })
+    ));
+    assert!(matches!(
+        v.verify_inclusion(HEXSTAMP, CC),
+        Err(InclusionError::MemoMismatch { .. })
+    ));
+    assert!(matches!(
+        v.verify_inclusion(HEXSTAMP, DD),
+        Err(InclusionError::NotIncluded(_))
+    ));
+    assert!(matches!(
+        v.verify_inclusion(HEXSTAMP, ""),
+        Err(InclusionError::NoTxHash)
+    ));
+}
+
+#[test]
+fn reorged_tx_is_not_final() {
+    let (chain, v) = setup(2);
+    chain.include_tx(evolve_tx(AA, 101, 0, HEXSTAMP));
+    chain.advance_blocks(1);
+    assert!(v.verify_inclusion(HEXSTAMP, AA).is_err());
+    chain.reorg(1);
+    chain.advance_blocks(5);
+    assert!(matches!(
+        v.verify_inclusion(HEXSTAMP, AA),
+        Err(InclusionError::NotIncluded(_))
+    ));
+}
+
+#[test]
+fn caches_finalized_hexstamps_and_fails_closed_on_timeout() {
+    let (chain, v) = setup(1);
+    chain.include_tx(evolve_tx(AA, 100, 0, HEXSTAMP));
+    v.verify_inclusion(HEXSTAMP, AA).unwrap();
+
+    chain.set_latency(Duration::from_secs(2));
+    // Cached: no round-trip, so the slow node does not matter.
+    v.verify_inclusion(HEXSTAMP, AA).unwrap();
+    // Uncached: the request times out and verification fails.
+    assert!(matches!(
+        v.verify_inclusion("other", AA),
+        Err(InclusionError::Lcd(_))
+    ));
+}
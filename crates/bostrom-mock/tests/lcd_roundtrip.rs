@@ -0,0 +1,83 @@
+use std::time::{Duration, Instant};
+
+use bostrom_lcd::LcdClient;
+use bostrom_mock::{Fixtures, MockChain, TxFixture};
+
+const SUBJECT: &str = "bostrom18sd2ujv24ual9c9pshtxys6j8knh6xaead9ye7";
+const TX: &str = "A1B2C3D4E5F60718293A4B5C6D7E8F90A1B2C3D4E5F60718293A4B5C6D7E8F90";
+const FEED: &str = "FEED000000000000000000000000000000000000000000000000000000000000";
+
+fn chain() -> MockChain {
+    let fixtures =
+        Fixtures::from_json_file(concat!(env!("CARGO_MANIFEST_DIR"), "/fixtures/chain.json"))
+            .expect("fixtures load");
+    MockChain::start(fixtures).expect("mock LCD starts")
+}
+
+#[test]
+fn serves_balances_stake_and_txs() {
+    let chain = chain();
+    let lcd = LcdClient::new(chain.base_url(), Duration::from_secs(2));
+
+    assert_eq!(lcd.balance(SUBJECT, "boot").unwrap(), 5_000_000);
+    assert_eq!(lcd.balance("bostrom1nobody", "boot").unwrap(), 0);
+    assert_eq!(lcd.delegated_stake(SUBJECT, "boot").unwrap(), 1_000_000);
+    assert_eq!(lcd.latest_height().unwrap(), 100);
+
+    let tx = lcd.tx(TX).unwrap().expect("fixture tx is included");
+    assert_eq!(
+        (tx.height, tx.code, tx.memo.as_str()),
+        (95, 0, "evolve-0001")
+    );
+    assert!(lcd
+        .tx("DEADBEEF00000000000000000000000000000000000000000000000000000000")
+        .unwrap()
+        .is_none());
+    assert!(matches!(
+        lcd.tx("../../bank/v1beta1/balances/x"),
+        Err(bostrom_lcd::LcdError::BadTxHash(_))
+    ));
+    assert!(matches!(lcd.tx("DEADBEEF"), Err(bostrom_lcd::LcdError::BadTxHash(_))));
+}
+
+#[test]
+fn txs_confirm_with_blocks_and_vanish_on_reorg() {
+    let chain = chain();
+    let lcd = LcdClient::new(chain.base_url(), Duration::from_secs(2));
+
+    chain.include_tx(TxFixture {
+        hash: FEED.into(),
+        height: 102,
+        code: 0,
+        memo: "evolve-0002".into(),
+    });
+    assert!(
+        lcd.tx(FEED).unwrap().is_none(),
+        "pending tx must not be visible"
+    );
+
+    chain.advance_blocks(2);
+    assert_eq!(lcd.tx(FEED).unwrap().unwrap().height, 102);
+
+    assert_eq!(chain.reorg(3), vec![FEED.to_string()]);
+    assert_eq!(lcd.latest_height().unwrap(), 99);
+    assert!(lcd.tx(FEED).unwrap().is_none());
+    assert!(
+        lcd.tx(TX).unwrap().is_some(),
+        "deep txs survive a shallow reorg"
+    );
+}
+
+#[test]
+fn latency_is_simulated_and_bounded_by_client_timeout() {
+    let chain = chain();
+    chain.set_latency(Duration::from_millis(150));
+
+    let patient = LcdClient::new(chain.base_url(), Duration::from_secs(2));
+    let start = Instant::now();
+    patient.latest_height().unwrap();
+    assert!(start.elapsed() >= Duration::from_millis(150));
+
+    let hasty = LcdClient::new(chain.base_url(), Duration::from_millis(30));
+    assert!(hasty.latest_height().is_err());
+}
@@ -0,0 +1,112 @@
+//! Restrict-only caveats and the grant they narrow.
+
+use serde::{Deserialize, Serialize};
+
+use crate::{hmac, SealError};
+
+/// One restriction appended to a chord. Each may only narrow the grant.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "caveat", rename_all = "snake_case")]
+pub enum Caveat {
+    /// Expire no later than `unix`.
+    ExpiresAt {
+        unix: i64,
+    },
+    MaxTokens {
+        max: u32,
+    },
+    MaxCalls {
+        max: u32,
+    },
+    /// Confine to `route` or a sub-route of it, e.g. `CHAT/summary`.
+    Route {
+        route: String,
+    },
+    /// Network targets allowed by `AuraBoundaryGuard`.
+    Domains {
+        allow: Vec<String>,
+    },
+}
+
+/// What a chord actually permits once its caveats are applied. Limits a
+/// chord type does not carry start as `None`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Grant {
+    pub expires_at_unix: i64,
+    pub route: String,
+    pub max_tokens: Option<u32>,
+    pub max_calls: Option<u32>,
+    pub domains: Option<Vec<String>>,
+}
+
+fn narrower(current: Option<u32>, next: u32) -> bool {
+    current.is_none_or(|c| next <= c)
+}
+
+impl Grant {
+    /// `route` is the granted route or one of its sub-routes.
+    pub fn covers_route(&self, route: &str) -> bool {
+        route == self.route
+            || route
+                .strip_prefix(self.route.as_str())
+                .is_some_and(|rest| rest.starts_with('/'))
+    }
+
+    pub fn allows_domain(&self, domain: &str) -> bool {
+        self.domains
+            .as_ref()
+            .is_none_or(|d| d.iter().any(|x| x == domain))
+    }
+
+    /// Apply `caveat`, refusing any that would widen this grant.
+    pub fn restrict(&mut self, caveat: &Caveat) -> Result<(), SealError> {
+        let widens = |what: String| Err(SealError::Widens(what));
+        match caveat {
+            Caveat::ExpiresAt { unix } => {
+                if *unix > self.expires_at_unix {
+                    return widens(format!(
+                        "expiry {} is later than {}",
+                        unix, self.expires_at_unix
+                    ));
+                }
+                self.expires_at_unix = *unix;
+            }
+            Caveat::MaxTokens { max } => {
+                if !narrower(self.max_tokens, *max) {
+                    return widens(format!("max_tokens {} exceeds {:?}", max, self.max_tokens));
+                }
+                self.max_tokens = Some(*max);
+            }
+            Caveat::MaxCalls { max } => {
+                if !narrower(self.max_calls, *max) {
+                    return widens(format!("max_calls {} exceeds {:?}", max, self.max_calls));
+                }
+                self.max_calls = Some(*max);
+            }
+            Caveat::Route { route } => {
+                if !self.covers_route(route) {
+                    return widens(format!("route {} is outside {}", route, self.route));
+                }
+                self.route = route.clone();
+            }
+            Caveat::Domains { allow } => {
+                if let Some(d) = allow.iter().find(|d| !self.allows_domain(d)) {
+                    return widens(format!("domain {} is not in the parent allowlist", d));
+                }
+                self.domains = Some(allow.clone());
+            }
+        }
+        Ok(())
+    }
+}
+
+pub(crate) fn caveat_bytes(caveat: &Caveat) -> Result<Vec<u8>, SealError> {
+    Ok(serde_json::to_vec(caveat)?)
+}
+
+/// Next link of a chord's tag chain after appending `caveat`. Needs no key:
+/// any holder can narrow, but nobody can recover the previous tag.
+pub fn extend_tag(tag: &str, caveat: &Caveat) -> Result<String, SealError> {
+    let key = hex::decode(tag).map_err(|e| SealError::Malformed(e.to_string()))?;
+    Ok(hex::encode(hmac(&key, &caveat_bytes(caveat)?)))
+}
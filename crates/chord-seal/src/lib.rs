@@ -0,0 +1,296 @@
+//! Ed25519 seals for capability chords.
+//!
+//! A chord is only as trustworthy as its issuer: anything that can emit JSON
+//! can fill in a chord struct. The node's issuer key signs the chord claims
+//! (everything but the seal itself) and gates verify the seal with the public
+//! half before honouring any field.
+//!
+//! Holders can narrow a chord before handing it on by appending caveats
+//! (see [`caveat`]). An attenuated chord drops the Ed25519 seal and carries
+//! only a macaroon-style HMAC tag chain, so the narrower chord cannot be
+//! stripped back to the grant it came from.
+//!
+//! [`ChordStore`] persists usage and revocations across restarts and guards.
+
+pub mod caveat;
+pub mod store;
+
+use std::path::Path;
+
+use ed25519_dalek::{Signature, Signer, SigningKey, VerifyingKey};
+use hmac::{Hmac, Mac};
+use serde::Serialize;
+use sha2::Sha256;
+use thiserror::Error;
+
+pub use caveat::{extend_tag, Caveat, Grant};
+pub use store::{ChordRef, ChordStore, ChordUsage, RevokeTarget, StoreError};
+
+type HmacSha256 = Hmac<Sha256>;
+
+/// Domain separation tag, so a chord seal is never valid for other payloads.
+const SEAL_DOMAIN: &[u8] = b"rust-party/capability-chord/v1\n";
+
+#[derive(Debug, Error)]
+pub enum SealError {
+    #[error("chord carries no seal")]
+    Missing,
+    #[error("chord seal is malformed: {0}")]
+    Malformed(String),
+    #[error("chord seal does not verify against the issuer key")]
+    Invalid,
+    #[error("issuer key: {0}")]
+    Key(String),
+    #[error("caveat widens the parent grant: {0}")]
+    Widens(String),
+    #[error("attenuated chord cannot be checked without the issuer caveat root")]
+    CaveatsUnverifiable,
+    #[error("chord claims do not serialize: {0}")]
+    Claims(#[from] serde_json::Error),
+}
+
+fn preimage<T: Serialize>(claims: &T) -> Result<Vec<u8>, SealError> {
+    let mut bytes = SEAL_DOMAIN.to_vec();
+    serde_json::to_writer(&mut bytes, claims)?;
+    Ok(bytes)
+}
+
+fn hmac(key: &[u8], data: &[u8]) -> [u8; 32] {
+    let mut mac = HmacSha256::new_from_slice(key).expect("HMAC takes keys of any length");
+    mac.update(data);
+    mac.finalize().into_bytes().into()
+}
+
+/// Node-held signing key that mints chords.
+pub struct ChordIssuer {
+    key: SigningKey,
+    /// HMAC root for caveat tag chains, derived from the signing secret.
+    caveat_root: [u8; 32],
+}
+
+impl ChordIssuer {
+    pub fn from_secret(secret: [u8; 32]) -> Self {
+        Self {
+            key: SigningKey::from_bytes(&secret),
+            caveat_root: hmac(&secret, b"rust-party/chord-caveat-root/v1"),
+        }
+    }
+
+    /// Load a 32-byte secret stored as hex.
+    pub fn from_key_file<P: AsRef<Path>>(path: P) -> Result<Self, SealError> {
+        let text = std::fs::read_to_string(path).map_err(|e| SealError::Key(e.to_string()))?;
+        let raw = hex::decode(text.trim()).map_err(|e| SealError::Key(e.to_string()))?;
+        let secret: [u8; 32] = raw
+            .try_into()
+            .map_err(|_| SealError::Key("secret must be 32 bytes".into()))?;
+        Ok(Self::from_secret(secret))
+    }
+
+    /// Verifier for gates on this node; it can also check attenuated chords.
+    pub fn verifier(&self) -> ChordVerifier {
+        ChordVerifier {
+            key: self.key.verifying_key(),
+            caveat_root: Some(self.caveat_root),
+        }
+    }
+
+    /// Hex seal over `claims`.
+    pub fn seal<T: Serialize>(&self, claims: &T) -> Result<String, SealError> {
+        Ok(hex::encode(self.key.sign(&preimage(claims)?).to_bytes()))
+    }
+
+    /// First link of the caveat tag chain, minted alongside the seal.
+    pub fn caveat_tag<T: Serialize>(&self, claims: &T) -> Result<String, SealError> {
+        Ok(hex::encode(hmac(&self.caveat_root, &preimage(claims)?)))
+    }
+}
+
+/// Public half of the issuer key, held by every gate that accepts chords.
+/// Built from the public key alone it accepts only unattenuated chords.
+#[derive(Debug, Clone)]
+pub struct ChordVerifier {
+    key: VerifyingKey,
+    caveat_root: Option<[u8; 32]>,
+}
+
+impl ChordVerifier {
+    pub fn from_public_hex(hex_key: &str) -> Result<Self, SealError> {
+        let raw = hex::decode(hex_key.trim()).map_err(|e| SealError::Key(e.to_string()))?;
+        let bytes: [u8; 32] = raw
+            .try_into()
+            .map_err(|_| SealError::Key("public key must be 32 bytes".into()))?;
+        let key = VerifyingKey::from_bytes(&bytes).map_err(|e| SealError::Key(e.to_string()))?;
+        Ok(Self {
+            key,
+            caveat_root: None,
+        })
+    }
+
+    pub fn public_hex(&self) -> String {
+        hex::encode(self.key.as_bytes())
+    }
+
+    pub fn verify<T: Serialize>(&self, claims: &T, seal: &str) -> Result<(), SealError> {
+        if seal.is_empty() {
+            return Err(SealError::Missing);
+        }
+        let raw = hex::decode(seal).map_err(|e| SealError::Malformed(e.to_string()))?;
+        let sig = Signature::from_slice(&raw).map_err(|e| SealError::Malformed(e.to_string()))?;
+        self.key
+            .verify_strict(&preimage(claims)?, &sig)
+            .map_err(|_| SealError::Invalid)
+    }
+
+    /// Effective grant of a chord: `base` if it carries no caveats and a
+    /// valid seal, otherwise `base` narrowed by each caveat once the tag
+    /// chain checks out. Caveats that widen the grant are rejected.
+    pub fn grant<T: Serialize>(
+        &self,
+        claims: &T,
+        seal: &str,
+        base: Grant,
+        caveats: &[Caveat],
+        tag: &str,
+    ) -> Result<Grant, SealError> {
+        if caveats.is_empty() {
+            self.verify(claims, seal)?;
+            return Ok(base);
+        }
+        let root = self.caveat_root.ok_or(SealError::CaveatsUnverifiable)?;
+        let (last, init) = caveats.split_last().expect("caveats are non-empty");
+        let mut chain = hmac(&root, &preimage(claims)?).to_vec();
+        let mut grant = base;
+        for c in init {
+            grant.restrict(c)?;
+            chain = hmac(&chain, &caveat::caveat_bytes(c)?).to_vec();
+        }
+        grant.restrict(last)?;
+        let tag = hex::decode(tag).map_err(|e| SealError::Malformed(e.to_string()))?;
+        let mut mac = HmacSha256::new_from_slice(&chain).expect("HMAC takes keys of any length");
+        mac.update(&caveat::caveat_bytes(last)?);
+        mac.verify_slice(&tag).map_err(|_| SealError::Invalid)?;
+        Ok(grant)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Serialize)]
+    struct Claims<'a> {
+        subject_id: &'a str,
+        route: &'a str,
+        kind: &'a str,
+    }
+
+    const CHAT: Claims<'static> = Claims {
+        subject_id: "bostrom18sd2u",
+        route: "CHAT",
+        kind: "ExplainPolicy",
+    };
+
+    #[test]
+    fn seal_binds_every_claim() {
+        let issuer = ChordIssuer::from_secret([7; 32]);
+        let v = issuer.verifier();
+        let seal = issuer.seal(&CHAT).unwrap();
+        v.verify(&CHAT, &seal).unwrap();
+
+        let rerouted = Claims {
+            route: "OTA",
+            ..CHAT
+        };
+        assert!(matches!(
+            v.verify(&rerouted, &seal),
+            Err(SealError::Invalid)
+        ));
+        assert!(matches!(v.verify(&CHAT, ""), Err(SealError::Missing)));
+        assert!(matches!(
+            v.verify(&CHAT, "zz"),
+            Err(SealError::Malformed(_))
+        ));
+    }
+
+    #[test]
+    fn other_issuers_cannot_mint() {
+        let node = ChordIssuer::from_secret([7; 32]).verifier();
+        let rogue = ChordIssuer::from_secret([8; 32]);
+        let seal = rogue.seal(&CHAT).unwrap();
+        assert!(matches!(node.verify(&CHAT, &seal), Err(SealError::Invalid)));
+
+        let v = ChordVerifier::from_public_hex(&node.public_hex()).unwrap();
+        assert!(v.verify(&CHAT, &seal).is_err());
+    }
+
+    fn base() -> Grant {
+        Grant {
+            expires_at_unix: 1_000,
+            route: "CHAT".into(),
+            max_tokens: Some(2048),
+            max_calls: None,
+            domains: None,
+        }
+    }
+
+    #[test]
+    fn caveats_narrow_and_cannot_be_stripped() {
+        let issuer = ChordIssuer::from_secret([7; 32]);
+        let v = issuer.verifier();
+        let mut tag = issuer.caveat_tag(&CHAT).unwrap();
+        let caveats = vec![
+            Caveat::MaxTokens { max: 256 },
+            Caveat::Route {
+                route: "CHAT/summary".into(),
+            },
+        ];
+        for c in &caveats {
+            tag = extend_tag(&tag, c).unwrap();
+        }
+
+        let grant = v.grant(&CHAT, "", base(), &caveats, &tag).unwrap();
+        assert_eq!(grant.max_tokens, Some(256));
+        assert!(grant.covers_route("CHAT/summary") && !grant.covers_route("CHAT"));
+
+        // Dropping a caveat while keeping the tag breaks the chain.
+        assert!(matches!(
+            v.grant(&CHAT, "", base(), &caveats[..1], &tag),
+            Err(SealError::Invalid)
+        ));
+        let public = ChordVerifier::from_public_hex(&v.public_hex()).unwrap();
+        assert!(matches!(
+            public.grant(&CHAT, "", base(), &caveats, &tag),
+            Err(SealError::CaveatsUnverifiable)
+        ));
+    }
+
+    #[test]
+    fn widening_caveats_are_rejected() {
+        let issuer = ChordIssuer::from_secret([7; 32]);
+        let v = issuer.verifier();
+        for c in [
+            Caveat::MaxTokens { max: 4096 },
+            Caveat::ExpiresAt { unix: 2_000 },
+            Caveat::Route {
+                route: "OTA".into(),
+            },
+        ] {
+            let tag = extend_tag(&issuer.caveat_tag(&CHAT).unwrap(), &c).unwrap();
+            assert!(matches!(
+                v.grant(&CHAT, "", base(), &[c], &tag),
+                Err(SealError::Widens(_))
+            ));
+        }
+
+        let mut g = base();
+        g.restrict(&Caveat::Domains {
+            allow: vec!["lcd.bostrom.cybernode.ai".into()],
+        })
+        .unwrap();
+        assert!(g
+            .restrict(&Caveat::Domains {
+                allow: vec!["example.com".into()],
+            })
+            .is_err());
+    }
+}
@@ -0,0 +1,383 @@
+//! Persistent chord accounting: issuance, call and token usage, revocation.
+//!
+//! A [`Journal`] of store events. Every check first reads whatever other
+//! handles or processes appended since, so a revocation is seen by the very
+//! next check in every guard sharing the file, not at chord expiry.
+
+use std::collections::{HashMap, HashSet};
+use std::path::Path;
+
+use jsonl_journal::{Journal, JournalError, Replay};
+use serde::{Deserialize, Serialize};
+use subject_id::SubjectId;
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum StoreError {
+    #[error("chord store I/O: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("chord store line {line} is corrupt: {message}")]
+    Corrupt { line: usize, message: String },
+    #[error("chord {0} is revoked")]
+    Revoked(String),
+    #[error("chord {id} exhausted its {limit} budget")]
+    Exhausted { id: String, limit: &'static str },
+}
+
+impl From<JournalError> for StoreError {
+    fn from(e: JournalError) -> Self {
+        match e {
+            JournalError::Io(e) => StoreError::Io(e),
+            JournalError::Corrupt { line, message } => StoreError::Corrupt { line, message },
+        }
+    }
+}
+
+/// What a revocation applies to. Subjects are matched in canonical form,
+/// so any spelling of an address revokes the same chords.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(tag = "by", content = "value", rename_all = "snake_case")]
+pub enum RevokeTarget {
+    Id(String),
+    Subject(SubjectId),
+    Kind(String),
+}
+
+/// Identity of a chord as the store sees it; `kind` is the kind's name and
+/// `subject_id` the subject the chord resolves to, not the address it names.
+#[derive(Debug, Clone, Copy)]
+pub struct ChordRef<'a> {
+    pub id: &'a str,
+    pub subject_id: &'a SubjectId,
+    pub kind: &'a str,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ChordUsage {
+    pub calls: u32,
+    pub tokens: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "event", rename_all = "snake_case")]
+enum StoreEvent {
+    Issued {
+        id: String,
+        subject_id: SubjectId,
+        kind: String,
+        expires_at_unix: i64,
+    },
+    Used {
+        id: String,
+        calls: u32,
+        tokens: u64,
+    },
+    Revoked {
+        target: RevokeTarget,
+        reason: String,
+    },
+}
+
+#[derive(Default)]
+struct State {
+    issued: HashSet<String>,
+    usage: HashMap<String, ChordUsage>,
+    revoked: HashSet<RevokeTarget>,
+}
+
+impl Replay for State {
+    type Event = StoreEvent;
+
+    fn apply(&mut self, event: StoreEvent) {
+        match event {
+            StoreEvent::Issued { id, .. } => {
+                self.issued.insert(id);
+            }
+            StoreEvent::Used { id, calls, tokens } => {
+                let u = self.usage.entry(id).or_default();
+                u.calls = u.calls.saturating_add(calls);
+                u.tokens = u.tokens.saturating_add(tokens);
+            }
+            StoreEvent::Revoked { target, .. } => {
+                self.revoked.insert(target);
+            }
+        }
+    }
+}
+
+impl State {
+    /// Usage after charging `calls` and `tokens`, if the chord may take them.
+    fn admit(
+        &self,
+        chord: ChordRef<'_>,
+        calls: u32,
+        tokens: u64,
+        max_calls: Option<u32>,
+        max_tokens: Option<u32>,
+    ) -> Result<ChordUsage, StoreError> {
+        if self.revoked(chord) {
+            return Err(StoreError::Revoked(chord.id.into()));
+        }
+        let used = self.usage.get(chord.id).copied().unwrap_or_default();
+        let exhausted = |limit| StoreError::Exhausted {
+            id: chord.id.into(),
+            limit,
+        };
+        if max_calls.is_some_and(|m| used.calls.saturating_add(calls) > m) {
+            return Err(exhausted("max_calls"));
+        }
+        if max_tokens.is_some_and(|m| used.tokens.saturating_add(tokens) > u64::from(m)) {
+            return Err(exhausted("max_tokens"));
+        }
+        Ok(ChordUsage {
+            calls: used.calls.saturating_add(calls),
+            tokens: used.tokens.saturating_add(tokens),
+        })
+    }
+
+    fn revoked(&self, chord: ChordRef<'_>) -> bool {
+        self.revoked.contains(&RevokeTarget::Id(chord.id.into()))
+            || self
+                .revoked
+                .contains(&RevokeTarget::Subject(chord.subject_id.clone()))
+            || self
+                .revoked
+                .contains(&RevokeTarget::Kind(chord.kind.into()))
+    }
+}
+
+pub struct ChordStore {
+    journal: Journal<State>,
+}
+
+impl std::fmt::Debug for ChordStore {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ChordStore")
+            .field("path", &self.path())
+            .finish()
+    }
+}
+
+impl ChordStore {
+    pub fn open<P: AsRef<Path>>(path: P) -> Result<Self, StoreError> {
+        Ok(Self {
+            journal: Journal::open(path)?,
+        })
+    }
+
+    pub fn path(&self) -> &Path {
+        self.journal.path()
+    }
+
+    pub fn record_issued(
+        &self,
+        chord: ChordRef<'_>,
+        expires_at_unix: i64,
+    ) -> Result<(), StoreError> {
+        self.journal.with(|txn| {
+            txn.append(&StoreEvent::Issued {
+                id: chord.id.into(),
+                subject_id: chord.subject_id.clone(),
+                kind: chord.kind.into(),
+                expires_at_unix,
+            })
+            .map_err(StoreError::from)
+        })
+    }
+
+    pub fn revoke(&self, target: RevokeTarget, reason: &str) -> Result<(), StoreError> {
+        self.journal.with(|txn| {
+            txn.append(&StoreEvent::Revoked {
+                target,
+                reason: reason.into(),
+            })
+            .map_err(StoreError::from)
+        })
+    }
+
+    pub fn is_revoked(&self, chord: ChordRef<'_>) -> Result<bool, StoreError> {
+        Ok(self.journal.read(|state| state.revoked(chord))?)
+    }
+
+    pub fn was_issued(&self, id: &str) -> Result<bool, StoreError> {
+        Ok(self.journal.read(|state| state.issued.contains(id))?)
+    }
+
+    pub fn usage(&self, id: &str) -> Result<ChordUsage, StoreError> {
+        Ok(self
+            .journal
+            .read(|state| state.usage.get(id).copied().unwrap_or_default())?)
+    }
+
+    /// Whether charging `calls` and `tokens` would succeed right now, without
+    /// charging them. A later [`consume`](Self::consume) still decides.
+    pub fn check(
+        &self,
+        chord: ChordRef<'_>,
+        calls: u32,
+        tokens: u64,
+        max_calls: Option<u32>,
+        max_tokens: Option<u32>,
+    ) -> Result<ChordUsage, StoreError> {
+        self.journal
+            .read(|state| state.admit(chord, calls, tokens, max_calls, max_tokens))?
+    }
+
+    /// Charge `calls` and `tokens` to the chord if it is not revoked and
+    /// stays within `max_calls` / `max_tokens`; returns usage afterwards.
+    /// Exact within one process; another process charging at the same
+    /// instant can overshoot a budget by its own in-flight charge.
+    pub fn consume(
+        &self,
+        chord: ChordRef<'_>,
+        calls: u32,
+        tokens: u64,
+        max_calls: Option<u32>,
+        max_tokens: Option<u32>,
+    ) -> Result<ChordUsage, StoreError> {
+        self.journal.with(|txn| {
+            txn.state()
+                .admit(chord, calls, tokens, max_calls, max_tokens)?;
+            txn.append(&StoreEvent::Used {
+                id: chord.id.into(),
+                calls,
+                tokens,
+            })?;
+            Ok(txn.state().usage[chord.id])
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs::OpenOptions;
+    use std::io::Write;
+
+    const BOSTROM: &str = "bostrom18sd2ujv24ual9c9pshtxys6j8knh6xaead9ye7";
+
+    fn subject() -> SubjectId {
+        SubjectId::parse(BOSTROM).unwrap()
+    }
+
+    fn chord(subject_id: &SubjectId) -> ChordRef<'_> {
+        ChordRef {
+            id: "c1",
+            subject_id,
+            kind: "SummarizeText",
+        }
+    }
+
+    #[test]
+    fn usage_survives_restart_and_budgets_hold() {
+        let subject = subject();
+        let chord = chord(&subject);
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("chords.jsonl");
+        {
+            let store = ChordStore::open(&path).unwrap();
+            store.record_issued(chord, 1_000).unwrap();
+            store.consume(chord, 1, 100, Some(2), Some(150)).unwrap();
+        }
+        let store = ChordStore::open(&path).unwrap();
+        assert!(store.was_issued("c1").unwrap());
+        assert_eq!(
+            store.usage("c1").unwrap(),
+            ChordUsage {
+                calls: 1,
+                tokens: 100
+            }
+        );
+        assert!(matches!(
+            store.consume(chord, 1, 100, Some(2), Some(150)),
+            Err(StoreError::Exhausted {
+                limit: "max_tokens",
+                ..
+            })
+        ));
+        store.check(chord, 1, 50, Some(2), Some(150)).unwrap();
+        assert!(matches!(
+            store.check(chord, 1, 51, Some(2), Some(150)),
+            Err(StoreError::Exhausted {
+                limit: "max_tokens",
+                ..
+            })
+        ));
+        store.consume(chord, 1, 50, Some(2), Some(150)).unwrap();
+        assert!(matches!(
+            store.consume(chord, 1, 0, Some(2), None),
+            Err(StoreError::Exhausted {
+                limit: "max_calls",
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn torn_tail_is_discarded_on_open() {
+        let subject = subject();
+        let chord = chord(&subject);
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("chords.jsonl");
+        ChordStore::open(&path)
+            .unwrap()
+            .record_issued(chord, 1_000)
+            .unwrap();
+        let mut f = OpenOptions::new().append(true).open(&path).unwrap();
+        f.write_all(br#"{"event":"used","id":"c1","ca"#).unwrap();
+        drop(f);
+
+        let store = ChordStore::open(&path).unwrap();
+        store.consume(chord, 1, 10, None, None).unwrap();
+        let store = ChordStore::open(&path).unwrap();
+        assert_eq!(store.usage("c1").unwrap().tokens, 10);
+    }
+
+    #[test]
+    fn revocation_reaches_other_handles_immediately() {
+        let subject = subject();
+        let chord = chord(&subject);
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("chords.jsonl");
+        let gate = ChordStore::open(&path).unwrap();
+        let admin = ChordStore::open(&path).unwrap();
+        gate.consume(chord, 1, 0, None, None).unwrap();
+
+        admin
+            .revoke(RevokeTarget::Kind("SummarizeText".into()), "abuse")
+            .unwrap();
+        assert!(gate.is_revoked(chord).unwrap());
+        assert!(matches!(
+            gate.consume(chord, 1, 0, None, None),
+            Err(StoreError::Revoked(_))
+        ));
+
+        let other = ChordRef {
+            id: "c2",
+            kind: "ExplainPolicy",
+            ..chord
+        };
+        assert!(!gate.is_revoked(other).unwrap());
+        admin
+            .revoke(RevokeTarget::Subject(subject.clone()), "abuse")
+            .unwrap();
+        assert!(gate.is_revoked(other).unwrap());
+    }
+
+    #[test]
+    fn subject_revocation_matches_any_spelling_of_the_address() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("chords.jsonl");
+        let store = ChordStore::open(&path).unwrap();
+        let subject = subject();
+        let upper = SubjectId::parse(&BOSTROM.to_ascii_uppercase()).unwrap();
+        store.revoke(RevokeTarget::Subject(upper), "abuse").unwrap();
+        assert!(ChordStore::open(&path)
+            .unwrap()
+            .is_revoked(chord(&subject))
+            .unwrap());
+
+        let line = std::fs::read_to_string(&path).unwrap();
+        assert!(line.contains(BOSTROM), "{}", line);
+    }
+}
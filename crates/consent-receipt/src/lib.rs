@@ -0,0 +1,244 @@
+//! Signed consent receipts.
+//!
+//! A receipt is the subject's Ed25519-signed statement that one route may be
+//! used for one purpose (THERAPY, RESEARCH, ...) over named data categories,
+//! inside a validity window. Every receipt names a revocation handle; once
+//! the handle is revoked in the [`ConsentStore`], every receipt carrying it
+//! stops matching at the very next check.
+
+pub mod store;
+
+use std::fmt;
+use std::path::Path;
+
+use ed25519_dalek::{Signature, Signer, SigningKey, VerifyingKey};
+use serde::{Deserialize, Serialize};
+use subject_id::SubjectId;
+use thiserror::Error;
+
+pub use store::{ConsentStore, StoreError};
+
+/// Domain separation tag, so a receipt signature is never valid elsewhere.
+const RECEIPT_DOMAIN: &[u8] = b"rust-party/consent-receipt/v1\n";
+
+#[derive(Debug, Error)]
+pub enum ConsentError {
+    #[error("receipt carries no signature")]
+    Unsigned,
+    #[error("receipt signature is malformed: {0}")]
+    Malformed(String),
+    #[error("receipt signature does not verify against the subject's consent key")]
+    Invalid,
+    #[error("consent key: {0}")]
+    Key(String),
+    #[error("receipt claims do not serialize: {0}")]
+    Claims(#[from] serde_json::Error),
+}
+
+/// What the subject consents to the data being used for.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum Purpose {
+    Therapy,
+    Research,
+    /// Purposes named in `consent.json` without a dedicated variant.
+    #[serde(untagged)]
+    Other(String),
+}
+
+impl fmt::Display for Purpose {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Purpose::Therapy => f.write_str("THERAPY"),
+            Purpose::Research => f.write_str("RESEARCH"),
+            Purpose::Other(p) => f.write_str(p),
+        }
+    }
+}
+
+/// One signed consent receipt.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConsentReceipt {
+    pub receipt_id: String,
+    pub subject_id: SubjectId,
+    /// "XR", "NANOSWARM", "BCI", ...
+    pub route: String,
+    pub purpose: Purpose,
+    /// Data the receipt covers, e.g. "eeg_summary", "sleep_stage".
+    pub data_categories: Vec<String>,
+    /// Data-only unless set.
+    pub allow_actuation: bool,
+    pub not_before_unix: i64,
+    pub expires_at_unix: i64,
+    /// Revoking this handle withdraws the receipt (and any sharing it).
+    pub revocation_handle: String,
+    /// Hex Ed25519 signature over every other field.
+    #[serde(default)]
+    pub signature: String,
+}
+
+/// Signed view of a receipt: everything but the signature.
+#[derive(Serialize)]
+struct Claims<'a> {
+    receipt_id: &'a str,
+    subject_id: &'a str,
+    route: &'a str,
+    purpose: &'a Purpose,
+    data_categories: &'a [String],
+    allow_actuation: bool,
+    not_before_unix: i64,
+    expires_at_unix: i64,
+    revocation_handle: &'a str,
+}
+
+impl ConsentReceipt {
+    fn preimage(&self) -> Result<Vec<u8>, ConsentError> {
+        let mut bytes = RECEIPT_DOMAIN.to_vec();
+        serde_json::to_writer(
+            &mut bytes,
+            &Claims {
+                receipt_id: &self.receipt_id,
+                subject_id: self.subject_id.as_str(),
+                route: &self.route,
+                purpose: &self.purpose,
+                data_categories: &self.data_categories,
+                allow_actuation: self.allow_actuation,
+                not_before_unix: self.not_before_unix,
+                expires_at_unix: self.expires_at_unix,
+                revocation_handle: &self.revocation_handle,
+            },
+        )?;
+        Ok(bytes)
+    }
+
+    pub fn is_live(&self, now_unix: i64) -> bool {
+        self.not_before_unix <= now_unix && now_unix < self.expires_at_unix
+    }
+
+    /// Whether the receipt covers every one of `categories`.
+    pub fn covers(&self, categories: &[String]) -> bool {
+        categories.iter().all(|c| self.data_categories.contains(c))
+    }
+}
+
+/// The subject's consent key, held on the subject's own device.
+pub struct ConsentSigner {
+    key: SigningKey,
+}
+
+impl ConsentSigner {
+    pub fn from_secret(secret: [u8; 32]) -> Self {
+        Self {
+            key: SigningKey::from_bytes(&secret),
+        }
+    }
+
+    /// Load a 32-byte secret stored as hex.
+    pub fn from_key_file<P: AsRef<Path>>(path: P) -> Result<Self, ConsentError> {
+        let text =
+            std::fs::read_to_string(path).map_err(|e| ConsentError::Key(e.to_string()))?;
+        let raw = hex::decode(text.trim()).map_err(|e| ConsentError::Key(e.to_string()))?;
+        let secret: [u8; 32] = raw
+            .try_into()
+            .map_err(|_| ConsentError::Key("secret must be 32 bytes".into()))?;
+        Ok(Self::from_secret(secret))
+    }
+
+    pub fn verifier(&self) -> ConsentVerifier {
+        ConsentVerifier {
+            key: self.key.verifying_key(),
+        }
+    }
+
+    /// Fill in `receipt.signature`.
+    pub fn sign(&self, receipt: &mut ConsentReceipt) -> Result<(), ConsentError> {
+        receipt.signature = hex::encode(self.key.sign(&receipt.preimage()?).to_bytes());
+        Ok(())
+    }
+}
+
+/// Public half of the subject's consent key, held by the gate.
+#[derive(Debug, Clone)]
+pub struct ConsentVerifier {
+    key: VerifyingKey,
+}
+
+impl ConsentVerifier {
+    pub fn from_public_hex(hex_key: &str) -> Result<Self, ConsentError> {
+        let raw = hex::decode(hex_key.trim()).map_err(|e| ConsentError::Key(e.to_string()))?;
+        let bytes: [u8; 32] = raw
+            .try_into()
+            .map_err(|_| ConsentError::Key("public key must be 32 bytes".into()))?;
+        let key =
+            VerifyingKey::from_bytes(&bytes).map_err(|e| ConsentError::Key(e.to_string()))?;
+        Ok(Self { key })
+    }
+
+    pub fn public_hex(&self) -> String {
+        hex::encode(self.key.as_bytes())
+    }
+
+    pub fn verify(&self, receipt: &ConsentReceipt) -> Result<(), ConsentError> {
+        if receipt.signature.is_empty() {
+            return Err(ConsentError::Unsigned);
+        }
+        let raw =
+            hex::decode(&receipt.signature).map_err(|e| ConsentError::Malformed(e.to_string()))?;
+        let sig =
+            Signature::from_slice(&raw).map_err(|e| ConsentError::Malformed(e.to_string()))?;
+        self.key
+            .verify_strict(&receipt.preimage()?, &sig)
+            .map_err(|_| ConsentError::Invalid)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn receipt() -> ConsentReceipt {
+        ConsentReceipt {
+            receipt_id: "r1".into(),
+            subject_id: "bostrom18sd2ujv24ual9c9pshtxys6j8knh6xaead9ye7"
+                .parse()
+                .unwrap(),
+            route: "BCI".into(),
+            purpose: Purpose::Therapy,
+            data_categories: vec!["eeg_summary".into()],
+            allow_actuation: false,
+            not_before_unix: 100,
+            expires_at_unix: 200,
+            revocation_handle: "h1".into(),
+            signature: String::new(),
+        }
+    }
+
+    #[test]
+    fn signature_binds_purpose_and_window() {
+        let signer = ConsentSigner::from_secret([3; 32]);
+        let v = signer.verifier();
+        let mut r = receipt();
+        assert!(matches!(v.verify(&r), Err(ConsentError::Unsigned)));
+        signer.sign(&mut r).unwrap();
+        v.verify(&r).unwrap();
+
+        let mut repurposed = r.clone();
+        repurposed.purpose = Purpose::Research;
+        assert!(matches!(v.verify(&repurposed), Err(ConsentError::Invalid)));
+
+        let mut extended = r.clone();
+        extended.expires_at_unix = 10_000;
+        assert!(matches!(v.verify(&extended), Err(ConsentError::Invalid)));
+
+        assert!(r.is_live(150) && !r.is_live(200) && !r.is_live(99));
+    }
+
+    #[test]
+    fn purpose_names_round_trip() {
+        let p: Purpose = serde_json::from_str("\"RESEARCH\"").unwrap();
+        assert_eq!(p, Purpose::Research);
+        let p: Purpose = serde_json::from_str("\"EDUCATION\"").unwrap();
+        assert_eq!(p, Purpose::Other("EDUCATION".into()));
+        assert_eq!(serde_json::to_string(&Purpose::Therapy).unwrap(), "\"THERAPY\"");
+    }
+}
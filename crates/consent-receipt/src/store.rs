@@ -0,0 +1,151 @@
+//! Persistent consent revocations.
+//!
+//! A [`Journal`] shared by every gate on the node. Each check first reads
+//! whatever was appended since, so a revocation takes effect at the next
+//! request, not at receipt expiry.
+//!
+//! Revocations are keyed by the subject that made them: a handle withdraws
+//! only the receipts its own subject signed, so nobody can revoke another
+//! subject's consent by guessing or copying a handle.
+
+use std::collections::HashSet;
+use std::path::Path;
+
+use jsonl_journal::{Journal, JournalError, Replay};
+use serde::{Deserialize, Serialize};
+use subject_id::SubjectId;
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum StoreError {
+    #[error("consent store I/O: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("consent store line {line} is corrupt: {message}")]
+    Corrupt { line: usize, message: String },
+}
+
+impl From<JournalError> for StoreError {
+    fn from(e: JournalError) -> Self {
+        match e {
+            JournalError::Io(e) => StoreError::Io(e),
+            JournalError::Corrupt { line, message } => StoreError::Corrupt { line, message },
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "event", rename_all = "snake_case")]
+enum StoreEvent {
+    Revoked {
+        handle: String,
+        subject_id: SubjectId,
+        reason: String,
+    },
+}
+
+#[derive(Default)]
+struct State {
+    revoked: HashSet<(SubjectId, String)>,
+}
+
+impl Replay for State {
+    type Event = StoreEvent;
+
+    fn apply(&mut self, event: StoreEvent) {
+        match event {
+            StoreEvent::Revoked {
+                handle, subject_id, ..
+            } => {
+                self.revoked.insert((subject_id, handle));
+            }
+        }
+    }
+}
+
+pub struct ConsentStore {
+    journal: Journal<State>,
+}
+
+impl std::fmt::Debug for ConsentStore {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ConsentStore")
+            .field("path", &self.path())
+            .finish()
+    }
+}
+
+impl ConsentStore {
+    pub fn open<P: AsRef<Path>>(path: P) -> Result<Self, StoreError> {
+        Ok(Self {
+            journal: Journal::open(path)?,
+        })
+    }
+
+    pub fn path(&self) -> &Path {
+        self.journal.path()
+    }
+
+    /// Withdraw every receipt `subject_id` signed with `handle`. Durable
+    /// before it returns.
+    pub fn revoke(
+        &self,
+        handle: &str,
+        subject_id: &SubjectId,
+        reason: &str,
+    ) -> Result<(), StoreError> {
+        self.journal.with(|txn| {
+            txn.append(&StoreEvent::Revoked {
+                handle: handle.into(),
+                subject_id: subject_id.clone(),
+                reason: reason.into(),
+            })
+            .map_err(StoreError::from)
+        })
+    }
+
+    /// Whether `subject_id` has revoked `handle`.
+    pub fn is_revoked(&self, handle: &str, subject_id: &SubjectId) -> Result<bool, StoreError> {
+        Ok(self.journal.read(|state| {
+            state
+                .revoked
+                .contains(&(subject_id.clone(), handle.to_string()))
+        })?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn id(s: &str) -> SubjectId {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn revocation_is_seen_by_other_handles() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("consent.jsonl");
+        let subject = id("bostrom18sd2ujv24ual9c9pshtxys6j8knh6xaead9ye7");
+        let a = ConsentStore::open(&path).unwrap();
+        let b = ConsentStore::open(&path).unwrap();
+        assert!(!b.is_revoked("h1", &subject).unwrap());
+        a.revoke("h1", &subject, "subject withdrew").unwrap();
+        assert!(b.is_revoked("h1", &subject).unwrap());
+        assert!(!b.is_revoked("h2", &subject).unwrap());
+        assert!(ConsentStore::open(&path)
+            .unwrap()
+            .is_revoked("h1", &subject)
+            .unwrap());
+    }
+
+    #[test]
+    fn a_handle_is_only_revoked_for_its_own_subject() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = ConsentStore::open(dir.path().join("consent.jsonl")).unwrap();
+        let subject = id("bostrom18sd2ujv24ual9c9pshtxys6j8knh6xaead9ye7");
+        let other = id("bostrom1d2f5k3g5fcm43yg7lg57668m94pqlfaaemwtr4");
+        store.revoke("h1", &other, "not mine").unwrap();
+        assert!(store.is_revoked("h1", &other).unwrap());
+        assert!(!store.is_revoked("h1", &subject).unwrap());
+    }
+}
@@ -1,8 +1,38 @@
+use std::collections::BTreeSet;
+
+use bostrom_lcd::LcdClient;
+use subject_id::SubjectId;
+
 use crate::actions::SovereignAction;
 use crate::policy_engine::{BostromIdentity, PolicyEngine, PolicyError};
 
+/// On-chain holdings a signer needs before structural changes execute.
+#[derive(Debug, Clone)]
+pub struct EvolveStakeRequirements {
+    /// Bostrom base denom, e.g. "boot".
+    pub denom: String,
+    pub min_balance: u128,
+    pub min_delegated: u128,
+}
+
 pub struct EvolveTokenEngine {
-    // references to on-chain EVOLVE contract client and local stake cache
+    client: LcdClient,
+    requirements: EvolveStakeRequirements,
+    approved_signers: BTreeSet<SubjectId>,
+}
+
+impl EvolveTokenEngine {
+    pub fn new(
+        client: LcdClient,
+        requirements: EvolveStakeRequirements,
+        approved_signers: impl IntoIterator<Item = SubjectId>,
+    ) -> Self {
+        Self {
+            client,
+            requirements,
+            approved_signers: approved_signers.into_iter().collect(),
+        }
+    }
 }
 
 impl PolicyEngine for EvolveTokenEngine {
@@ -14,11 +44,31 @@ impl PolicyEngine for EvolveTokenEngine {
         match action {
             SovereignAction::ProposeEvolveKernel { .. }
             | SovereignAction::ApplyOtaUpdate { .. } => {
-                // 1. Query EVOLVE balance and stake.
-                // 2. Verify identity is in approved signer set.
-                // 3. Check RoH ceilings and neurorights posture.
-                // If any check fails:
-                // return Err(PolicyError::InsufficientStake);
+                // 1. Identity must be in the approved signer set.
+                if !self.approved_signers.contains(&identity.subject_id) {
+                    return Err(PolicyError::NotAuthorized);
+                }
+
+                // 2. Query EVOLVE balance and stake; an unreachable chain denies.
+                let denom = &self.requirements.denom;
+                let unavailable =
+                    |e: bostrom_lcd::LcdError| PolicyError::ChainUnavailable(e.to_string());
+                let balance = self
+                    .client
+                    .balance(identity.subject_id.as_str(), denom)
+                    .map_err(unavailable)?;
+                let delegated = self
+                    .client
+                    .delegated_stake(identity.subject_id.as_str(), denom)
+                    .map_err(unavailable)?;
+                if balance < self.requirements.min_balance
+                    || delegated < self.requirements.min_delegated
+                {
+                    return Err(PolicyError::InsufficientStake);
+                }
+
+                // 3. RoH ceilings and neurorights posture are enforced by the
+                //    Tsafe PolicyEngine before actions reach this engine.
                 Ok(())
             }
             _ => Ok(()),
@@ -1,13 +1,18 @@
 //! “This kernel is allowed to refuse my wishes when they threaten other beings’ neurorights, lifeforce envelopes, or RoH ceilings. My freedom is constrained so that my intelligence cannot become predatory.”
 
 use std::path::Path;
+use policy_bundle::{StakeAnchor, VerifiedBundle};
 use serde::{Deserialize, Serialize};
+use subject_id::SubjectId;
 
 use crate::quantum_envelope_guard::{
+    CounterOffer,
     QuantumRuntimeSnapshot,
     QuantumWorkloadRequest,
     QuantumSovereigntyEnvelope,
 };
+use crate::tsafe::{TsafeKernel, ViabilityKernel};
+use viability_kernel::Polytope;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct NeurorightsPolicy {
@@ -19,27 +24,6 @@ pub struct NeurorightsPolicy {
     pub storage_scope: String,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct TsafeAxis {
-    pub name: String,
-    pub min: f32,
-    pub max: f32,
-}
-
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct TsafeKernel {
-    pub axes: Vec<TsafeAxis>,
-}
-
-impl TsafeKernel {
-    pub fn get_axis_bounds(&self, name: &str) -> Option<(f32, f32)> {
-        self.axes
-            .iter()
-            .find(|a| a.name == name)
-            .map(|a| (a.min, a.max))
-    }
-}
-
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum SovereignActionKind {
     ReadNeuralShard,
@@ -53,7 +37,7 @@ pub enum SovereignActionKind {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SovereignAction {
     pub kind: SovereignActionKind,
-    pub subject_id: String,        // e.g. Bostrom address
+    pub subject_id: SubjectId,     // e.g. Bostrom address
     pub route: String,             // e.g. "BCI", "OTA", "GOV", "CHAT"
     pub context_labels: Vec<String>,
     pub requested_fields: Vec<String>,
@@ -63,7 +47,13 @@ pub struct SovereignAction {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum Decision {
     Allow { reason: String },
-    Deny { reason: String },
+    Deny {
+        reason: String,
+        /// Scaled-down workload that would be allowed instead, so
+        /// orchestrators can downgrade and resubmit. The denial stands.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        counter_offer: Option<CounterOffer>,
+    },
     AllowWithConstraints { reason: String, redactions: Vec<String> },
 }
 
@@ -71,17 +61,24 @@ pub enum Decision {
 pub struct PolicyEngine {
     neurorights: NeurorightsPolicy,
     tsafe: TsafeKernel,
-    // rohmodel, vkernel, etc. can be added here
+    vkernel: ViabilityKernel,
+    /// `vkernel` constraints and Tsafe axis bounds, compiled once at load.
+    region: Polytope,
+    // rohmodel, etc. can be added here
 }
 
 impl PolicyEngine {
-    pub fn load_from_dir<P: AsRef<Path>>(dir: P) -> anyhow::Result<Self> {
-        let dir = dir.as_ref();
-        let neurorights: NeurorightsPolicy =
-            serde_json::from_str(&std::fs::read_to_string(dir.join("neurorights.json"))?)?;
-        let tsafe: TsafeKernel =
-            serde_json::from_str(&std::fs::read_to_string(dir.join("tsafe.aln"))?)?;
-        Ok(Self { neurorights, tsafe })
+    /// Verify the signed bundle in `dir` against `anchor`, then load from it.
+    pub fn load_from_dir<P: AsRef<Path>>(dir: P, anchor: &StakeAnchor) -> anyhow::Result<Self> {
+        Self::load_from_bundle(&VerifiedBundle::open(dir, anchor)?)
+    }
+
+    pub fn load_from_bundle(bundle: &VerifiedBundle) -> anyhow::Result<Self> {
+        let neurorights: NeurorightsPolicy = serde_json::from_str(bundle.text("neurorights.json")?)?;
+        let tsafe: TsafeKernel = aln_format::from_str(bundle.text("tsafe.aln")?)?;
+        let vkernel: ViabilityKernel = aln_format::from_str(bundle.text("vkernel.aln")?)?;
+        let region = vkernel.compile(&tsafe)?;
+        Ok(Self { neurorights, tsafe, vkernel, region })
     }
 
     /// High-level evaluation for sovereign actions (non-QPU-specific).
@@ -92,6 +89,7 @@ impl PolicyEngine {
         {
             return Decision::Deny {
                 reason: "Mental privacy: direct neural shard export is forbidden".into(),
+                counter_offer: None,
             };
         }
 
@@ -99,6 +97,7 @@ impl PolicyEngine {
         if matches!(action.kind, SovereignActionKind::ReadKeys) {
             return Decision::Deny {
                 reason: "Keys may not be read via AI-mediated routes".into(),
+                counter_offer: None,
             };
         }
 
@@ -109,6 +108,7 @@ impl PolicyEngine {
                     "Lifeforce envelope exceeded for route {}",
                     action.route
                 ),
+                counter_offer: None,
             };
         }
 
@@ -147,7 +147,7 @@ impl PolicyEngine {
         snapshot: &QuantumRuntimeSnapshot,
         req: &QuantumWorkloadRequest,
     ) -> Decision {
-        let qenv = QuantumSovereigntyEnvelope::new(&self.tsafe);
+        let qenv = QuantumSovereigntyEnvelope::new(&self.tsafe, &self.region);
         qenv.evaluate(snapshot, req)
     }
 }
@@ -1,9 +1,10 @@
 use crate::actions::SovereignAction;
 use serde::{Deserialize, Serialize};
+use subject_id::SubjectId;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct BostromIdentity {
-    pub subject_id: String,
+    pub subject_id: SubjectId,
 }
 
 #[derive(Debug, thiserror::Error)]
@@ -14,6 +15,8 @@ pub enum PolicyError {
     NotAuthorized,
     #[error("violates neurorights or RoH limits")]
     NeurorightsViolation,
+    #[error("Bostrom chain unavailable: {0}")]
+    ChainUnavailable(String),
 }
 
 pub trait PolicyEngine {
@@ -1,4 +1,5 @@
 use serde::{Deserialize, Serialize};
+use viability_kernel::{AxisSnapshot, Polytope};
 
 use crate::policy::{Decision};
 use crate::tsafe::TsafeKernel;
@@ -29,14 +30,104 @@ pub struct QuantumWorkloadRequest {
     pub delta_lifeforce: f32,
 }
 
+/// The largest fraction of a denied workload that fits every envelope,
+/// returned alongside the denial.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CounterOffer {
+    /// Factor applied to every requested delta, in `(0, 1)`.
+    pub scale: f32,
+    /// The original request with its deltas multiplied by `scale`.
+    pub workload: QuantumWorkloadRequest,
+}
+
+impl QuantumWorkloadRequest {
+    /// Same workload with every delta multiplied by `scale`.
+    pub fn scaled(&self, scale: f32) -> Self {
+        Self {
+            route: self.route.clone(),
+            label: self.label.clone(),
+            delta_qpu_roh: self.delta_qpu_roh * scale,
+            delta_coherence: self.delta_coherence * scale,
+            delta_eco_impact: self.delta_eco_impact * scale,
+            delta_lifeforce: self.delta_lifeforce * scale,
+        }
+    }
+}
+
 /// Named guard: Quantum Sovereignty Envelope.
 pub struct QuantumSovereigntyEnvelope<'a> {
     tsafe: &'a TsafeKernel,
+    region: &'a Polytope,
 }
 
 impl<'a> QuantumSovereigntyEnvelope<'a> {
-    pub fn new(tsafe: &'a TsafeKernel) -> Self {
-        Self { tsafe }
+    /// `region` is the compiled `.vkernel.aln` polytope (see `ViabilityKernel::compile`).
+    pub fn new(tsafe: &'a TsafeKernel, region: &'a Polytope) -> Self {
+        Self { tsafe, region }
+    }
+
+    /// Runtime axes after `req`. Axes the QPU runtime does not report (e.g.
+    /// biospec_fatigue) sit at zero.
+    fn projected(
+        &self,
+        snapshot: &QuantumRuntimeSnapshot,
+        req: &QuantumWorkloadRequest,
+    ) -> AxisSnapshot {
+        let mut point = self.region.zero_snapshot();
+        for ((axis, v), (_, d)) in Self::axes(snapshot).into_iter().zip(Self::deltas(req)) {
+            point.insert(axis.to_string(), (v + d) as f64);
+        }
+        point
+    }
+
+    fn axes(snapshot: &QuantumRuntimeSnapshot) -> [(&'static str, f32); 5] {
+        [
+            ("qpu_roh", snapshot.qpu_roh),
+            ("qpu_coherence", snapshot.qpu_coherence),
+            ("qpu_eco_impact", snapshot.qpu_eco_impact),
+            ("lifeforce_load", snapshot.lifeforce_load),
+            ("roh_global", snapshot.roh_global),
+        ]
+    }
+
+    /// QPU RoH counts toward the global RoH as well.
+    fn deltas(req: &QuantumWorkloadRequest) -> [(&'static str, f32); 5] {
+        [
+            ("qpu_roh", req.delta_qpu_roh),
+            ("qpu_coherence", req.delta_coherence),
+            ("qpu_eco_impact", req.delta_eco_impact),
+            ("lifeforce_load", req.delta_lifeforce),
+            ("roh_global", req.delta_qpu_roh),
+        ]
+    }
+
+    /// Maximal uniform scale-down of `req`'s deltas that keeps the projected
+    /// state inside the region. `None` when nothing short of doing nothing
+    /// fits, including when the current snapshot is already outside.
+    pub fn project(
+        &self,
+        snapshot: &QuantumRuntimeSnapshot,
+        req: &QuantumWorkloadRequest,
+    ) -> Option<CounterOffer> {
+        let mut base = self.region.zero_snapshot();
+        for (axis, v) in Self::axes(snapshot) {
+            base.insert(axis.to_string(), v as f64);
+        }
+        let mut delta = AxisSnapshot::new();
+        for (axis, d) in Self::deltas(req) {
+            *delta.entry(axis.to_string()).or_insert(0.0) += d as f64;
+        }
+        let t = self.region.max_scale(&base, &delta).ok()??;
+        // Round down so the offer stays inside after f32 rounding.
+        let scale = ((t * 1e4).floor() / 1e4) as f32;
+        if scale <= 0.0 {
+            return None;
+        }
+        let workload = req.scaled(scale);
+        self.region
+            .check(&self.projected(snapshot, &workload))
+            .ok()
+            .map(|_| CounterOffer { scale, workload })
     }
 
     /// Evaluate whether a proposed quantum/neuromorph workload is allowed
@@ -46,57 +137,27 @@ impl<'a> QuantumSovereigntyEnvelope<'a> {
         snapshot: &QuantumRuntimeSnapshot,
         req: &QuantumWorkloadRequest,
     ) -> Decision {
-        // Pull configured bounds from Tsafe.
-        let (qpu_roh_min, qpu_roh_max) =
-            self.tsafe.get_axis_bounds("qpu_roh").unwrap_or((0.0, 0.20));
-        let (qpu_coh_min, qpu_coh_max) =
-            self.tsafe.get_axis_bounds("qpu_coherence").unwrap_or((0.0, 0.60));
-        let (_eco_min, eco_max) =
-            self.tsafe.get_axis_bounds("qpu_eco_impact").unwrap_or((0.0, 0.50));
-        let (_lf_min, lf_max) =
-            self.tsafe.get_axis_bounds("lifeforce_load").unwrap_or((0.0, 0.80));
-        let (_roh_min, roh_max) =
-            self.tsafe.get_axis_bounds("roh_global").unwrap_or((0.0, 0.30));
-
-        // Compute projected state after this workload.
         let proj_qpu_roh = snapshot.qpu_roh + req.delta_qpu_roh;
-        let proj_coh = snapshot.qpu_coherence + req.delta_coherence;
-        let proj_eco = snapshot.qpu_eco_impact + req.delta_eco_impact;
-        let proj_lf = snapshot.lifeforce_load + req.delta_lifeforce;
-        let proj_roh = snapshot.roh_global + req.delta_qpu_roh;
 
-        // Hard caps: global RoH and quantum-specific RoH must not be exceeded.
-        if proj_qpu_roh > qpu_roh_max || proj_roh > roh_max {
-            return Decision::Deny {
-                reason: format!(
-                    "Quantum Sovereignty Envelope: RoH ceiling exceeded (proj_qpu_roh={:.3}, proj_roh={:.3})",
-                    proj_qpu_roh, proj_roh
+        // Every coupling comes from the shard; an empty region denies.
+        if let Err(e) = self.region.check(&self.projected(snapshot, req)) {
+            let counter_offer = self.project(snapshot, req);
+            let reason = match &counter_offer {
+                Some(offer) => format!(
+                    "Quantum Sovereignty Envelope: {}; would fit scaled to {:.4}",
+                    e, offer.scale
                 ),
+                None => format!("Quantum Sovereignty Envelope: {}", e),
             };
-        }
-
-        // Coherence and lifeforce coupling (matches vkernel constraint).
-        if proj_lf + 0.5 * proj_coh > 1.0 || proj_lf > lf_max || proj_coh > qpu_coh_max {
-            return Decision::Deny {
-                reason: format!(
-                    "Quantum Sovereignty Envelope: lifeforce/coherence envelope exceeded \
-                     (proj_lf={:.3}, proj_coh={:.3})",
-                    proj_lf, proj_coh
-                ),
-            };
-        }
-
-        // Eco impact bound.
-        if proj_eco > eco_max {
             return Decision::Deny {
-                reason: format!(
-                    "Quantum Sovereignty Envelope: eco-impact envelope exceeded (proj_eco={:.3})",
-                    proj_eco
-                ),
+                reason,
+                counter_offer,
             };
         }
 
         // Optional: apply tighter limits on certain routes.
+        let (qpu_roh_min, qpu_roh_max) =
+            self.tsafe.get_axis_bounds("qpu_roh").unwrap_or((0.0, 0.20));
         if req.route == "BCI" && proj_qpu_roh > qpu_roh_min + 0.5 * (qpu_roh_max - qpu_roh_min) {
             return Decision::AllowWithConstraints {
                 reason: format!(
@@ -113,3 +174,91 @@ impl<'a> QuantumSovereigntyEnvelope<'a> {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tsafe::ViabilityKernel;
+
+    fn shards() -> (TsafeKernel, Polytope) {
+        let tsafe: TsafeKernel = aln_format::from_str(
+            r#"TsafeKernel { axes: [
+              { name: "lifeforce_load", min: 0.0, max: 0.8 },
+              { name: "roh_global", min: 0.0, max: 0.30 },
+              { name: "qpu_roh", min: 0.0, max: 0.20 },
+              { name: "qpu_coherence", min: 0.0, max: 1.0 },
+              { name: "qpu_eco_impact", min: 0.0, max: 0.50 } ] }"#,
+        )
+        .unwrap();
+        let vkernel: ViabilityKernel = aln_format::from_str(
+            r#"ViabilityKernel { mode: "Normal", constraints: [
+              { expr: "qpu_roh + roh_global <= 0.30" },
+              { expr: "lifeforce_load + 0.5 * qpu_coherence <= 1.0" } ] }"#,
+        )
+        .unwrap();
+        let region = vkernel.compile(&tsafe).unwrap();
+        (tsafe, region)
+    }
+
+    fn snapshot() -> QuantumRuntimeSnapshot {
+        QuantumRuntimeSnapshot {
+            qpu_roh: 0.1,
+            qpu_coherence: 0.2,
+            qpu_eco_impact: 0.1,
+            lifeforce_load: 0.5,
+            roh_global: 0.1,
+        }
+    }
+
+    fn workload(delta_qpu_roh: f32) -> QuantumWorkloadRequest {
+        QuantumWorkloadRequest {
+            route: "XR".into(),
+            label: "anneal".into(),
+            delta_qpu_roh,
+            delta_coherence: 0.0,
+            delta_eco_impact: 0.0,
+            delta_lifeforce: 0.0,
+        }
+    }
+
+    #[test]
+    fn project_scales_to_the_binding_vkernel_row() {
+        let (tsafe, region) = shards();
+        let guard = QuantumSovereigntyEnvelope::new(&tsafe, &region);
+        // qpu_roh + roh_global has 0.1 of slack; the workload moves it by 0.4.
+        let offer = guard.project(&snapshot(), &workload(0.2)).unwrap();
+        assert_eq!(offer.scale, 0.25);
+        assert!((offer.workload.delta_qpu_roh - 0.05).abs() < 1e-6);
+
+        let mut outside = snapshot();
+        outside.qpu_eco_impact = 0.9;
+        assert!(guard.project(&outside, &workload(0.2)).is_none());
+    }
+
+    #[test]
+    fn breach_is_denied_with_the_counter_offer_attached() {
+        let (tsafe, region) = shards();
+        let guard = QuantumSovereigntyEnvelope::new(&tsafe, &region);
+        let offer = match guard.evaluate(&snapshot(), &workload(0.2)) {
+            Decision::Deny {
+                counter_offer: Some(offer),
+                ..
+            } => offer,
+            other => panic!("expected a denial with a counter-offer, got {:?}", other),
+        };
+        assert!(matches!(
+            guard.evaluate(&snapshot(), &offer.workload),
+            Decision::Allow { .. }
+        ));
+
+        let mut outside = snapshot();
+        outside.qpu_eco_impact = 0.9;
+        assert!(matches!(
+            guard.evaluate(&outside, &workload(0.2)),
+            Decision::Deny {
+                counter_offer: None,
+                ..
+            }
+        ));
+    }
+}
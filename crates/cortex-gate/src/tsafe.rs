@@ -1,4 +1,6 @@
 use serde::{Deserialize, Serialize};
+use std::path::Path;
+use viability_kernel::{CompileError, Polytope};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TsafeAxis {
@@ -7,13 +9,20 @@ pub struct TsafeAxis {
     pub max: f32,
 }
 
+/// `.tsafe.aln` shard: named axes with hard bounds.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TsafeKernel {
     pub axes: Vec<TsafeAxis>,
-    // existing fields: constraints, tags, etc.
+    /// Governance / neurorights bindings, e.g. "QuantumSovereigntyEnvelope".
+    #[serde(default)]
+    pub tags: Vec<String>,
 }
 
 impl TsafeKernel {
+    pub fn load<P: AsRef<Path>>(path: P) -> anyhow::Result<Self> {
+        aln_format::from_path(path)
+    }
+
     pub fn get_axis_bounds(&self, name: &str) -> Option<(f32, f32)> {
         self.axes
             .iter()
@@ -21,3 +30,50 @@ impl TsafeKernel {
             .map(|a| (a.min, a.max))
     }
 }
+
+/// Single linear constraint from `.vkernel.aln`, e.g. `"qpu_roh + roh_global <= 0.30"`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ViabilityConstraint {
+    pub expr: String,
+}
+
+/// `.vkernel.aln` shard: the viability region the node must stay inside.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ViabilityKernel {
+    pub mode: String,
+    pub constraints: Vec<ViabilityConstraint>,
+    #[serde(default)]
+    pub labels: Vec<String>,
+}
+
+impl ViabilityKernel {
+    pub fn load<P: AsRef<Path>>(path: P) -> anyhow::Result<Self> {
+        aln_format::from_path(path)
+    }
+
+    /// Compile the constraint strings, plus the Tsafe axis box, into one
+    /// `Ax <= b` region that snapshots are checked against.
+    pub fn compile(&self, tsafe: &TsafeKernel) -> Result<Polytope, CompileError> {
+        let mut region = Polytope::compile(self.constraints.iter().map(|c| c.expr.as_str()))?;
+        for axis in &tsafe.axes {
+            region.push_axis_bounds(&axis.name, axis.min as f64, axis.max as f64);
+        }
+        Ok(region)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn shipped_shards_compile_into_one_region() {
+        let tsafe: TsafeKernel =
+            aln_format::from_str(include_str!("../../../shards/root/tsafe.aln")).unwrap();
+        let vkernel: ViabilityKernel =
+            aln_format::from_str(include_str!("../../../shards/root/vkernel.aln")).unwrap();
+        assert!(vkernel.labels.contains(&"QuantumSovereigntyEnvelope".to_string()));
+        let region = vkernel.compile(&tsafe).unwrap();
+        assert_eq!(region.rows().len(), 5 + 2 * tsafe.axes.len());
+    }
+}
@@ -0,0 +1,109 @@
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+/// Hexstamp preimage version the writer stamps new entries with.
+///
+/// - `0`: `serde_json` of the preimage fields in declaration order. Lines
+///   written before versioning carry no `version` and verify this way.
+/// - `1`: canonical JSON (sorted keys, normalized floats) including `version`.
+pub const ENTRY_VERSION: u32 = 1;
+
+/// `prev_hexstamp` of the first entry in a ledger.
+pub const GENESIS_HEXSTAMP: &str =
+    "0000000000000000000000000000000000000000000000000000000000000000";
+
+/// Outcome recorded for a guarded request.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Verdict {
+    Allow,
+    Reject,
+    Blocked,
+    /// A standing grant, such as a consent receipt, was withdrawn.
+    Revoke,
+}
+
+/// Caller-supplied part of an entry; the writer fills in sequence, chain
+/// link, policy-bundle hash and hexstamp.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct EntryDraft {
+    pub subject_id: String,
+    pub route: String,
+    pub verdict: Verdict,
+    /// Guard code, e.g. "ROH_CEILING", "FIREWALL_BLOCK", "ALLOW".
+    pub guard_code: String,
+    pub reason: String,
+    /// SHA-256 hex of the serialized request (see [`digest_hex`]).
+    pub request_digest: String,
+}
+
+/// One `.donutloop.aln` line.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DonutloopEntry {
+    /// Preimage version, see [`ENTRY_VERSION`]; absent on legacy lines.
+    #[serde(default)]
+    pub version: u32,
+    pub seq: u64,
+    pub timestamp_unix: i64,
+    pub prev_hexstamp: String,
+    pub subject_id: String,
+    pub route: String,
+    pub verdict: Verdict,
+    pub guard_code: String,
+    pub reason: String,
+    pub request_digest: String,
+    pub policy_bundle_hash: String,
+    /// SHA-256 over every other field, see [`DonutloopEntry::compute_hexstamp`].
+    pub hexstamp: String,
+}
+
+/// Hashed view of an entry: all fields except `hexstamp`. Version 0 leaves
+/// `version` out so legacy lines hash to exactly their original bytes.
+#[derive(Serialize)]
+struct Preimage<'a> {
+    #[serde(skip_serializing_if = "is_legacy")]
+    version: u32,
+    seq: u64,
+    timestamp_unix: i64,
+    prev_hexstamp: &'a str,
+    subject_id: &'a str,
+    route: &'a str,
+    verdict: Verdict,
+    guard_code: &'a str,
+    reason: &'a str,
+    request_digest: &'a str,
+    policy_bundle_hash: &'a str,
+}
+
+fn is_legacy(version: &u32) -> bool {
+    *version == 0
+}
+
+impl DonutloopEntry {
+    /// Recompute this entry's hexstamp from its other fields, using the
+    /// preimage encoding of its `version`.
+    pub fn compute_hexstamp(&self) -> String {
+        let pre = Preimage {
+            version: self.version,
+            seq: self.seq,
+            timestamp_unix: self.timestamp_unix,
+            prev_hexstamp: &self.prev_hexstamp,
+            subject_id: &self.subject_id,
+            route: &self.route,
+            verdict: self.verdict,
+            guard_code: &self.guard_code,
+            reason: &self.reason,
+            request_digest: &self.request_digest,
+            policy_bundle_hash: &self.policy_bundle_hash,
+        };
+        if is_legacy(&self.version) {
+            return digest_hex(&serde_json::to_vec(&pre).expect("preimage serializes"));
+        }
+        let canonical = aln_format::to_canonical_json(&pre).expect("preimage serializes");
+        digest_hex(canonical.as_bytes())
+    }
+}
+
+/// Lowercase SHA-256 hex of `bytes`.
+pub fn digest_hex(bytes: &[u8]) -> String {
+    hex::encode(Sha256::digest(bytes))
+}
@@ -0,0 +1,338 @@
+//! Donutloop: the hash-linked, append-only audit ledger (`.donutloop.aln`).
+//!
+//! Every guarded request leaves one entry carrying a monotonic sequence
+//! number, the previous entry's SHA-256 hexstamp, the request digest, the
+//! guard code that decided it and the hash of the policy bundle in force.
+
+mod entry;
+mod verify;
+mod writer;
+
+use std::path::Path;
+
+pub use entry::{
+    digest_hex, DonutloopEntry, EntryDraft, Verdict, ENTRY_VERSION, GENESIS_HEXSTAMP,
+};
+pub use verify::{
+    anchor_path, verify_anchored, verify_bytes, verify_entries, verify_path, Anchor, TamperKind,
+    TamperReport, VerifiedLedger, VerifyError,
+};
+pub use writer::DonutloopWriter;
+
+#[derive(Debug, thiserror::Error)]
+pub enum LedgerError {
+    #[error("donutloop io error: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("donutloop serialization error: {0}")]
+    Serialize(#[from] serde_json::Error),
+    #[error("donutloop corrupt at line {line}: {message}")]
+    Corrupt { line: u64, message: String },
+}
+
+/// Hash of a policy bundle: SHA-256 over each file's name and contents, in
+/// the order given.
+pub fn policy_bundle_hash<P: AsRef<Path>>(paths: &[P]) -> std::io::Result<String> {
+    let mut files = Vec::with_capacity(paths.len());
+    for p in paths {
+        let p = p.as_ref();
+        let name = p
+            .file_name()
+            .map(|n| n.to_string_lossy().into_owned())
+            .unwrap_or_default();
+        files.push((name, std::fs::read(p)?));
+    }
+    Ok(policy_bundle_hash_bytes(&files))
+}
+
+/// [`policy_bundle_hash`] over `(file name, contents)` pairs already in
+/// memory, so a caller can hash exactly the bytes it parsed.
+pub fn policy_bundle_hash_bytes<N: AsRef<str>, B: AsRef<[u8]>>(files: &[(N, B)]) -> String {
+    let mut pre = Vec::new();
+    for (name, bytes) in files {
+        pre.extend_from_slice(name.as_ref().as_bytes());
+        pre.push(0);
+        pre.extend_from_slice(digest_hex(bytes.as_ref()).as_bytes());
+        pre.push(b'\n');
+    }
+    digest_hex(&pre)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+
+    fn draft(code: &str) -> EntryDraft {
+        EntryDraft {
+            subject_id: "bostrom18sd2u".into(),
+            route: "CHAT".into(),
+            verdict: Verdict::Reject,
+            guard_code: code.into(),
+            reason: "test".into(),
+            request_digest: digest_hex(code.as_bytes()),
+        }
+    }
+
+    #[test]
+    fn entries_are_chained_and_sequenced() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("ledger/.donutloop.aln");
+        let w = DonutloopWriter::open(&path, "bundle").unwrap();
+        let a = w.append(draft("ROH_CEILING")).unwrap();
+        let b = w.append(draft("FIREWALL_BLOCK")).unwrap();
+        assert_eq!((a.seq, b.seq), (0, 1));
+        assert_eq!(a.prev_hexstamp, GENESIS_HEXSTAMP);
+        assert_eq!(b.prev_hexstamp, a.hexstamp);
+        assert_eq!(b.compute_hexstamp(), b.hexstamp);
+        assert_eq!(b.policy_bundle_hash, "bundle");
+
+        // Lines are plain ALN records.
+        let text = std::fs::read_to_string(&path).unwrap();
+        assert_eq!(text.lines().count(), 2);
+
+        // Reopening resumes the chain.
+        drop(w);
+        let w = DonutloopWriter::open(&path, "bundle").unwrap();
+        assert_eq!(w.head(), (2, b.hexstamp.clone()));
+        let c = w.append(draft("ALLOW")).unwrap();
+        assert_eq!(c.prev_hexstamp, b.hexstamp);
+    }
+
+    #[test]
+    fn hexstamp_preimage_is_canonical_json() {
+        let entry = DonutloopEntry {
+            version: ENTRY_VERSION,
+            seq: 7,
+            timestamp_unix: 1_700_000_000,
+            prev_hexstamp: GENESIS_HEXSTAMP.into(),
+            subject_id: "bostrom18sd2u".into(),
+            route: "CHAT".into(),
+            verdict: Verdict::Allow,
+            guard_code: "ALLOW".into(),
+            reason: "ok \"quoted\"".into(),
+            request_digest: "ab".into(),
+            policy_bundle_hash: "cd".into(),
+            hexstamp: String::new(),
+        };
+        let preimage = format!(
+            r#"{{"guard_code":"ALLOW","policy_bundle_hash":"cd","prev_hexstamp":"{}","reason":"ok \"quoted\"","request_digest":"ab","route":"CHAT","seq":7,"subject_id":"bostrom18sd2u","timestamp_unix":1700000000,"verdict":"Allow","version":1}}"#,
+            GENESIS_HEXSTAMP
+        );
+        assert_eq!(entry.compute_hexstamp(), digest_hex(preimage.as_bytes()));
+    }
+
+    #[test]
+    fn legacy_entries_verify_and_cannot_follow_versioned_ones() {
+        let (_dir, path, e) = ledger(2);
+        let mut legacy = e[0].clone();
+        legacy.version = 0;
+        legacy.hexstamp = legacy.compute_hexstamp();
+        let line = serde_json::to_string(&legacy).unwrap();
+        let line = line.replace("\"version\":0,", "");
+        let mut next = e[1].clone();
+        next.prev_hexstamp = legacy.hexstamp.clone();
+        next.hexstamp = next.compute_hexstamp();
+        std::fs::write(
+            &path,
+            format!("{}\n{}\n", line, serde_json::to_string(&next).unwrap()),
+        )
+        .unwrap();
+        assert_eq!(verify_path(&path, None).unwrap().head, next.hexstamp);
+
+        let mut downgraded = e[1].clone();
+        downgraded.version = 0;
+        downgraded.hexstamp = downgraded.compute_hexstamp();
+        write_lines(&path, &[e[0].clone(), downgraded]);
+        assert!(matches!(
+            tamper_kind(&path, None),
+            (
+                2,
+                TamperKind::VersionDowngrade {
+                    previous: 1,
+                    found: 0
+                }
+            )
+        ));
+    }
+
+    #[test]
+    fn torn_tail_is_discarded_on_open() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join(".donutloop.aln");
+        let first = {
+            let w = DonutloopWriter::open(&path, "bundle").unwrap();
+            w.append(draft("ROH_CEILING")).unwrap()
+        };
+        let committed = std::fs::metadata(&path).unwrap().len();
+
+        let mut f = std::fs::OpenOptions::new()
+            .append(true)
+            .open(&path)
+            .unwrap();
+        f.write_all(br#"{"seq":1,"timestamp_unix":17"#).unwrap();
+        drop(f);
+
+        let w = DonutloopWriter::open(&path, "bundle").unwrap();
+        assert_eq!(std::fs::metadata(&path).unwrap().len(), committed);
+        assert_eq!(w.head(), (1, first.hexstamp));
+    }
+
+    fn ledger(n: usize) -> (tempfile::TempDir, std::path::PathBuf, Vec<DonutloopEntry>) {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join(".donutloop.aln");
+        let w = DonutloopWriter::open(&path, "bundle").unwrap();
+        let entries = (0..n)
+            .map(|i| w.append(draft(&format!("CODE_{}", i))).unwrap())
+            .collect();
+        (dir, path, entries)
+    }
+
+    fn write_lines(path: &Path, entries: &[DonutloopEntry]) {
+        let text: String = entries
+            .iter()
+            .map(|e| serde_json::to_string(e).unwrap() + "\n")
+            .collect();
+        std::fs::write(path, text).unwrap();
+    }
+
+    fn tamper_kind(path: &Path, anchor: Option<&Anchor>) -> (u64, TamperKind) {
+        match verify_path(path, anchor) {
+            Err(VerifyError::Tampered(r)) => (r.line, r.kind),
+            other => panic!("expected tamper report, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn verifier_accepts_clean_ledger_and_anchor() {
+        let (_dir, path, e) = ledger(3);
+        let anchor = Anchor {
+            seq: 2,
+            hexstamp: e[2].hexstamp.clone(),
+        };
+        let v = verify_path(&path, Some(&anchor)).unwrap();
+        assert_eq!(
+            (v.entries, v.head.as_str(), v.torn_tail),
+            (3, e[2].hexstamp.as_str(), false)
+        );
+    }
+
+    #[test]
+    fn verifier_detects_gap_reorder_fork_and_edit() {
+        let (_dir, path, e) = ledger(4);
+
+        write_lines(&path, &[e[0].clone(), e[1].clone(), e[3].clone()]);
+        assert!(matches!(
+            tamper_kind(&path, None),
+            (
+                3,
+                TamperKind::Gap {
+                    expected: 2,
+                    found: 3
+                }
+            )
+        ));
+
+        write_lines(
+            &path,
+            &[e[0].clone(), e[2].clone(), e[1].clone(), e[3].clone()],
+        );
+        assert!(matches!(
+            tamper_kind(&path, None),
+            (2, TamperKind::Reordered { .. })
+        ));
+
+        let mut fork = e[1].clone();
+        fork.reason = "rewritten".into();
+        fork.hexstamp = fork.compute_hexstamp();
+        write_lines(&path, &[e[0].clone(), e[1].clone(), fork]);
+        assert!(matches!(
+            tamper_kind(&path, None),
+            (
+                3,
+                TamperKind::Fork {
+                    seq: 1,
+                    first_line: 2
+                }
+            )
+        ));
+
+        let mut edited = e[1].clone();
+        edited.guard_code = "ALLOW".into();
+        write_lines(&path, &[e[0].clone(), edited.clone()]);
+        assert!(matches!(
+            tamper_kind(&path, None),
+            (2, TamperKind::HexstampMismatch { .. })
+        ));
+
+        edited.hexstamp = edited.compute_hexstamp();
+        write_lines(&path, &[e[0].clone(), edited, e[2].clone()]);
+        assert!(matches!(
+            tamper_kind(&path, None),
+            (3, TamperKind::BrokenLink { .. })
+        ));
+    }
+
+    #[test]
+    fn verifier_detects_truncation_against_anchor() {
+        let (_dir, path, e) = ledger(3);
+        let anchor = Anchor {
+            seq: 2,
+            hexstamp: e[2].hexstamp.clone(),
+        };
+        write_lines(&path, &e[..2]);
+        assert!(verify_path(&path, None).is_ok());
+        assert!(matches!(
+            tamper_kind(&path, Some(&anchor)),
+            (
+                0,
+                TamperKind::Truncated {
+                    anchor_seq: 2,
+                    last_seq: Some(1)
+                }
+            )
+        ));
+    }
+
+    #[test]
+    fn persisted_anchor_catches_truncation_and_deletion() {
+        let (_dir, path, e) = ledger(3);
+        let anchor = Anchor::load(anchor_path(&path)).unwrap().unwrap();
+        assert_eq!((anchor.seq, anchor.hexstamp.as_str()), (2, e[2].hexstamp.as_str()));
+        assert_eq!(verify_anchored(&path).unwrap().entries, 3);
+
+        write_lines(&path, &e[..2]);
+        assert!(matches!(
+            verify_anchored(&path),
+            Err(VerifyError::Tampered(TamperReport {
+                kind: TamperKind::Truncated { anchor_seq: 2, .. },
+                ..
+            }))
+        ));
+        assert!(DonutloopWriter::open(&path, "bundle").is_err());
+
+        std::fs::remove_file(&path).unwrap();
+        assert!(matches!(
+            verify_anchored(&path),
+            Err(VerifyError::Tampered(TamperReport {
+                kind: TamperKind::Truncated { last_seq: None, .. },
+                ..
+            }))
+        ));
+    }
+
+    #[test]
+    fn refuses_to_extend_a_tampered_head() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join(".donutloop.aln");
+        DonutloopWriter::open(&path, "bundle")
+            .unwrap()
+            .append(draft("ROH_CEILING"))
+            .unwrap();
+        let text = std::fs::read_to_string(&path).unwrap();
+        std::fs::write(&path, text.replace("ROH_CEILING", "ALLOW")).unwrap();
+        assert!(matches!(
+            DonutloopWriter::open(&path, "bundle"),
+            Err(LedgerError::Corrupt { line: 1, .. })
+        ));
+    }
+}
@@ -0,0 +1,357 @@
+use std::collections::HashMap;
+use std::fmt;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use crate::entry::{DonutloopEntry, ENTRY_VERSION, GENESIS_HEXSTAMP};
+use crate::LedgerError;
+
+/// Externally remembered ledger head (e.g. from the last clean shutdown or a
+/// published proof). Without one, a ledger cut back to an earlier entry is
+/// indistinguishable from a shorter honest one.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Anchor {
+    /// Sequence number of the anchored entry.
+    pub seq: u64,
+    pub hexstamp: String,
+}
+
+impl Anchor {
+    /// The anchor persisted at `path`, or `None` if none was ever written.
+    pub fn load<P: AsRef<Path>>(path: P) -> std::io::Result<Option<Self>> {
+        match std::fs::read(path) {
+            Ok(bytes) => serde_json::from_slice(&bytes)
+                .map(Some)
+                .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e)),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Replace the anchor at `path` atomically, durable before it returns.
+    pub fn store<P: AsRef<Path>>(&self, path: P) -> std::io::Result<()> {
+        let path = path.as_ref();
+        let mut tmp = path.as_os_str().to_owned();
+        tmp.push(".tmp");
+        let tmp = PathBuf::from(tmp);
+        let mut file = std::fs::File::create(&tmp)?;
+        file.write_all(&serde_json::to_vec(self)?)?;
+        file.sync_all()?;
+        std::fs::rename(&tmp, path)
+    }
+}
+
+/// Where the writer keeps the ledger's head anchor: `<ledger>.head`.
+pub fn anchor_path<P: AsRef<Path>>(ledger: P) -> PathBuf {
+    let mut p = ledger.as_ref().as_os_str().to_owned();
+    p.push(".head");
+    PathBuf::from(p)
+}
+
+/// What is wrong with the first broken entry.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum TamperKind {
+    /// Line is not a parseable entry.
+    Malformed { message: String },
+    /// Stored hexstamp differs from the one recomputed over the entry.
+    HexstampMismatch { stored: String, computed: String },
+    /// Preimage version newer than this verifier knows.
+    UnknownVersion { version: u32 },
+    /// Entry uses an older preimage version than the entry before it.
+    VersionDowngrade { previous: u32, found: u32 },
+    /// `prev_hexstamp` does not point at the preceding entry.
+    BrokenLink { expected: String, found: String },
+    /// Sequence jumps forward and the missing entries appear nowhere.
+    Gap { expected: u64, found: u64 },
+    /// Entries are present but out of order.
+    Reordered { expected: u64, found: u64 },
+    /// A sequence number already used is reused for a different entry.
+    Fork { seq: u64, first_line: u64 },
+    /// A sequence number is repeated with identical content.
+    Duplicate { seq: u64, first_line: u64 },
+    /// The ledger ends before the anchored entry.
+    Truncated {
+        anchor_seq: u64,
+        last_seq: Option<u64>,
+    },
+    /// The entry at the anchored sequence is not the anchored entry.
+    AnchorMismatch {
+        seq: u64,
+        expected: String,
+        found: String,
+    },
+}
+
+/// First broken entry of a ledger. `line` is 1-based; `0` means the problem
+/// is the ledger as a whole (truncation past its last line).
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, thiserror::Error)]
+pub struct TamperReport {
+    pub line: u64,
+    pub seq: Option<u64>,
+    pub kind: TamperKind,
+}
+
+impl fmt::Display for TamperReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "donutloop tampered at line {}", self.line)?;
+        if let Some(seq) = self.seq {
+            write!(f, " (seq {})", seq)?;
+        }
+        match &self.kind {
+            TamperKind::Malformed { message } => write!(f, ": malformed entry: {}", message),
+            TamperKind::HexstampMismatch { stored, computed } => write!(
+                f,
+                ": hexstamp mismatch (stored {}, computed {})",
+                stored, computed
+            ),
+            TamperKind::UnknownVersion { version } => {
+                write!(f, ": unknown preimage version {}", version)
+            }
+            TamperKind::VersionDowngrade { previous, found } => write!(
+                f,
+                ": preimage version {} follows version {}",
+                found, previous
+            ),
+            TamperKind::BrokenLink { expected, found } => write!(
+                f,
+                ": prev_hexstamp {} does not match previous entry {}",
+                found, expected
+            ),
+            TamperKind::Gap { expected, found } => {
+                write!(
+                    f,
+                    ": expected seq {}, found {}; entries missing",
+                    expected, found
+                )
+            }
+            TamperKind::Reordered { expected, found } => {
+                write!(
+                    f,
+                    ": expected seq {}, found {}; entries reordered",
+                    expected, found
+                )
+            }
+            TamperKind::Fork { seq, first_line } => write!(
+                f,
+                ": seq {} already used by a different entry at line {}",
+                seq, first_line
+            ),
+            TamperKind::Duplicate { seq, first_line } => {
+                write!(f, ": seq {} replays the entry at line {}", seq, first_line)
+            }
+            TamperKind::Truncated {
+                anchor_seq,
+                last_seq,
+            } => match last_seq {
+                Some(last) => write!(
+                    f,
+                    ": ledger ends at seq {} but anchor is seq {}",
+                    last, anchor_seq
+                ),
+                None => write!(f, ": ledger is empty but anchor is seq {}", anchor_seq),
+            },
+            TamperKind::AnchorMismatch {
+                seq,
+                expected,
+                found,
+            } => write!(
+                f,
+                ": anchored seq {} should be {}, found {}",
+                seq, expected, found
+            ),
+        }
+    }
+}
+
+/// Result of a clean walk.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VerifiedLedger {
+    pub entries: u64,
+    /// Hexstamp of the last entry, or [`GENESIS_HEXSTAMP`] when empty.
+    pub head: String,
+    /// A crash left an unterminated final line. It is not an entry; the
+    /// writer discards it on its next open.
+    pub torn_tail: bool,
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum VerifyError {
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+    #[error(transparent)]
+    Tampered(#[from] TamperReport),
+}
+
+impl From<VerifyError> for LedgerError {
+    fn from(e: VerifyError) -> Self {
+        match e {
+            VerifyError::Io(e) => LedgerError::Io(e),
+            VerifyError::Tampered(r) => LedgerError::Corrupt {
+                line: r.line,
+                message: r.to_string(),
+            },
+        }
+    }
+}
+
+/// Walk the ledger at `path`, recomputing every hexstamp. A missing file is
+/// an empty ledger (and fails any anchor).
+pub fn verify_path<P: AsRef<Path>>(
+    path: P,
+    anchor: Option<&Anchor>,
+) -> Result<VerifiedLedger, VerifyError> {
+    let bytes = match std::fs::read(path.as_ref()) {
+        Ok(b) => b,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Vec::new(),
+        Err(e) => return Err(e.into()),
+    };
+    Ok(verify_bytes(&bytes, anchor)?)
+}
+
+/// [`verify_path`] against the anchor the writer persisted at
+/// [`anchor_path`]. Once an anchor exists, a deleted or truncated ledger
+/// fails as [`TamperKind::Truncated`] instead of passing as empty.
+pub fn verify_anchored<P: AsRef<Path>>(path: P) -> Result<VerifiedLedger, VerifyError> {
+    let anchor = Anchor::load(anchor_path(path.as_ref()))?;
+    verify_path(path, anchor.as_ref())
+}
+
+/// Verify ledger contents already in memory.
+pub fn verify_bytes(bytes: &[u8], anchor: Option<&Anchor>) -> Result<VerifiedLedger, TamperReport> {
+    let committed = bytes.iter().rposition(|b| *b == b'\n').map_or(0, |i| i + 1);
+    let torn_tail = committed < bytes.len();
+
+    let lines: Vec<&[u8]> = match bytes[..committed].strip_suffix(b"\n") {
+        Some(body) => body.split(|b| *b == b'\n').collect(),
+        None => Vec::new(),
+    };
+    let mut entries = Vec::with_capacity(lines.len());
+    for (i, line) in lines.into_iter().enumerate() {
+        let entry: DonutloopEntry = serde_json::from_slice(line).map_err(|e| TamperReport {
+            line: i as u64 + 1,
+            seq: None,
+            kind: TamperKind::Malformed {
+                message: e.to_string(),
+            },
+        })?;
+        entries.push(entry);
+    }
+
+    let mut v = verify_entries(&entries, anchor)?;
+    v.torn_tail = torn_tail;
+    Ok(v)
+}
+
+/// Verify a parsed sequence of entries; `line` in reports is the 1-based index.
+pub fn verify_entries(
+    entries: &[DonutloopEntry],
+    anchor: Option<&Anchor>,
+) -> Result<VerifiedLedger, TamperReport> {
+    let mut seen: HashMap<u64, (u64, &str)> = HashMap::new();
+    let mut head = GENESIS_HEXSTAMP;
+    let mut version = 0;
+
+    for (i, e) in entries.iter().enumerate() {
+        let line = i as u64 + 1;
+        let expected = i as u64;
+        let report = |kind| TamperReport {
+            line,
+            seq: Some(e.seq),
+            kind,
+        };
+
+        // A legacy ledger keeps verifying, but once an entry is stamped
+        // with a newer preimage no later entry may fall back to an older one.
+        if e.version > ENTRY_VERSION {
+            return Err(report(TamperKind::UnknownVersion { version: e.version }));
+        }
+        if e.version < version {
+            return Err(report(TamperKind::VersionDowngrade {
+                previous: version,
+                found: e.version,
+            }));
+        }
+        version = e.version;
+
+        let computed = e.compute_hexstamp();
+        if computed != e.hexstamp {
+            return Err(report(TamperKind::HexstampMismatch {
+                stored: e.hexstamp.clone(),
+                computed,
+            }));
+        }
+
+        if e.seq != expected {
+            if let Some(&(first_line, stamp)) = seen.get(&e.seq) {
+                return Err(report(if stamp == e.hexstamp {
+                    TamperKind::Duplicate {
+                        seq: e.seq,
+                        first_line,
+                    }
+                } else {
+                    TamperKind::Fork {
+                        seq: e.seq,
+                        first_line,
+                    }
+                }));
+            }
+            let later = entries[i + 1..].iter().any(|x| x.seq == expected);
+            return Err(report(if later {
+                TamperKind::Reordered {
+                    expected,
+                    found: e.seq,
+                }
+            } else {
+                TamperKind::Gap {
+                    expected,
+                    found: e.seq,
+                }
+            }));
+        }
+
+        if e.prev_hexstamp != head {
+            return Err(report(TamperKind::BrokenLink {
+                expected: head.to_string(),
+                found: e.prev_hexstamp.clone(),
+            }));
+        }
+
+        seen.insert(e.seq, (line, &e.hexstamp));
+        head = &e.hexstamp;
+    }
+
+    if let Some(a) = anchor {
+        match entries.get(a.seq as usize) {
+            None => {
+                return Err(TamperReport {
+                    line: 0,
+                    seq: None,
+                    kind: TamperKind::Truncated {
+                        anchor_seq: a.seq,
+                        last_seq: entries.last().map(|e| e.seq),
+                    },
+                })
+            }
+            Some(e) if e.hexstamp != a.hexstamp => {
+                return Err(TamperReport {
+                    line: a.seq + 1,
+                    seq: Some(a.seq),
+                    kind: TamperKind::AnchorMismatch {
+                        seq: a.seq,
+                        expected: a.hexstamp.clone(),
+                        found: e.hexstamp.clone(),
+                    },
+                })
+            }
+            Some(_) => {}
+        }
+    }
+
+    Ok(VerifiedLedger {
+        entries: entries.len() as u64,
+        head: head.to_string(),
+        torn_tail: false,
+    })
+}
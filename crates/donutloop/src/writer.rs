@@ -0,0 +1,149 @@
+use std::fs::{File, OpenOptions};
+use std::io::{Read, Write};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::entry::{DonutloopEntry, EntryDraft, ENTRY_VERSION};
+use crate::verify::{anchor_path, verify_bytes, Anchor, VerifyError};
+use crate::LedgerError;
+
+struct Tail {
+    file: File,
+    /// Byte length of the committed (newline-terminated) ledger.
+    len: u64,
+    next_seq: u64,
+    head: String,
+}
+
+/// Append-only, hash-linked writer for `.donutloop.aln`.
+///
+/// Each entry is one newline-terminated JSON line (valid ALN), written with a
+/// single `write_all` and `fsync`ed before `append` returns. A line without
+/// its trailing newline can only come from a crash mid-write; it is cut off
+/// on the next `open`, so readers never see a half-written entry.
+///
+/// After each append the new head is persisted to [`anchor_path`], so a
+/// later open or boot-time verification catches a cut-back or deleted ledger.
+pub struct DonutloopWriter {
+    path: PathBuf,
+    anchor: PathBuf,
+    policy_bundle_hash: String,
+    tail: Mutex<Tail>,
+}
+
+impl DonutloopWriter {
+    /// Open (or create) the ledger at `path`. Every entry appended through
+    /// this writer is stamped with `policy_bundle_hash`.
+    pub fn open<P: AsRef<Path>>(path: P, policy_bundle_hash: &str) -> Result<Self, LedgerError> {
+        let path = path.as_ref().to_path_buf();
+        if let Some(dir) = path.parent().filter(|d| !d.as_os_str().is_empty()) {
+            std::fs::create_dir_all(dir)?;
+        }
+        let mut file = OpenOptions::new()
+            .read(true)
+            .append(true)
+            .create(true)
+            .open(&path)?;
+
+        let mut buf = Vec::new();
+        file.read_to_end(&mut buf)?;
+
+        // Drop a torn final line left by a crash between write and fsync.
+        let committed = buf.iter().rposition(|b| *b == b'\n').map_or(0, |i| i + 1);
+        if committed < buf.len() {
+            file.set_len(committed as u64)?;
+            file.sync_all()?;
+            buf.truncate(committed);
+        }
+
+        // Never extend a ledger whose history does not verify, or that
+        // ends before the head this writer last committed.
+        let anchor_file = anchor_path(&path);
+        let anchor = Anchor::load(&anchor_file)?;
+        let verified = verify_bytes(&buf, anchor.as_ref()).map_err(VerifyError::from)?;
+
+        Ok(Self {
+            path,
+            anchor: anchor_file,
+            policy_bundle_hash: policy_bundle_hash.to_string(),
+            tail: Mutex::new(Tail {
+                file,
+                len: committed as u64,
+                next_seq: verified.entries,
+                head: verified.head,
+            }),
+        })
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// `(next sequence number, hexstamp of the last entry)`.
+    pub fn head(&self) -> (u64, String) {
+        let tail = self.tail.lock().expect("donutloop lock poisoned");
+        (tail.next_seq, tail.head.clone())
+    }
+
+    /// Chain, stamp, write and fsync one entry.
+    pub fn append(&self, draft: EntryDraft) -> Result<DonutloopEntry, LedgerError> {
+        self.append_under(draft, &self.policy_bundle_hash)
+    }
+
+    /// As [`append`](Self::append), stamped with the bundle that actually
+    /// made the decision, for callers whose policy is swapped at runtime.
+    pub fn append_under(
+        &self,
+        draft: EntryDraft,
+        policy_bundle_hash: &str,
+    ) -> Result<DonutloopEntry, LedgerError> {
+        let mut tail = self.tail.lock().expect("donutloop lock poisoned");
+
+        let mut entry = DonutloopEntry {
+            version: ENTRY_VERSION,
+            seq: tail.next_seq,
+            timestamp_unix: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map(|d| d.as_secs() as i64)
+                .unwrap_or(0),
+            prev_hexstamp: tail.head.clone(),
+            subject_id: draft.subject_id,
+            route: draft.route,
+            verdict: draft.verdict,
+            guard_code: draft.guard_code,
+            reason: draft.reason,
+            request_digest: draft.request_digest,
+            policy_bundle_hash: policy_bundle_hash.to_string(),
+            hexstamp: String::new(),
+        };
+        entry.hexstamp = entry.compute_hexstamp();
+
+        let mut line = serde_json::to_vec(&entry)?;
+        line.push(b'\n');
+
+        let written = tail
+            .file
+            .write_all(&line)
+            .and_then(|_| tail.file.sync_data());
+        if let Err(e) = written {
+            // Roll back a partial line so the in-memory head stays truthful.
+            let len = tail.len;
+            let _ = tail.file.set_len(len);
+            return Err(e.into());
+        }
+
+        tail.len += line.len() as u64;
+        tail.next_seq += 1;
+        tail.head = entry.hexstamp.clone();
+
+        // The entry is committed; a stale anchor only weakens the truncation
+        // check, but the caller still learns the head was not persisted.
+        Anchor {
+            seq: entry.seq,
+            hexstamp: entry.hexstamp.clone(),
+        }
+        .store(&self.anchor)?;
+        Ok(entry)
+    }
+}
@@ -0,0 +1,34 @@
+//! Production verification: stake multisig offline, then the Bostrom chain.
+
+use bostrom_lcd::LcdEvolveVerifier;
+
+use crate::{BostromClient, EvolveToken, OfflineEvolveVerifier};
+
+/// Signatures against `.stake.aln` first, so forged tokens never cost an LCD
+/// round-trip; then on-chain inclusion and finality. Inclusion alone proves
+/// nothing (anyone can post the hexstamp as a memo), so the LCD verifier is
+/// only ever a [`BostromClient`] through this type.
+pub struct ChainEvolveVerifier {
+    offline: OfflineEvolveVerifier,
+    chain: LcdEvolveVerifier,
+}
+
+impl ChainEvolveVerifier {
+    pub fn new(offline: OfflineEvolveVerifier, chain: LcdEvolveVerifier) -> Self {
+        Self { offline, chain }
+    }
+
+    pub fn verify(&self, token: &EvolveToken) -> anyhow::Result<()> {
+        self.offline.verify(token)?;
+        let tx = token.bostrom_tx.as_deref().unwrap_or_default();
+        self.chain.verify_inclusion(&token.hexstamp, tx)?;
+        Ok(())
+    }
+}
+
+impl BostromClient for ChainEvolveVerifier {
+    fn verify_evolve_token(&self, token: &EvolveToken) -> anyhow::Result<bool> {
+        self.verify(token)?;
+        Ok(true)
+    }
+}
@@ -1,14 +1,35 @@
 use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::sync::Mutex;
+
+pub mod chain;
+pub mod offline;
+pub mod stake;
+
+pub use chain::ChainEvolveVerifier;
+pub use offline::{EvolveError, OfflineEvolveVerifier, TOKEN_VERSION};
+pub use stake::StakeShard;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct EvolveToken {
+    /// Signed-bytes version, see [`offline::TOKEN_VERSION`]. Absent on
+    /// tokens signed before the canonical preimage.
+    #[serde(default)]
+    pub version: u32,
     pub token_id: String,
     pub subject_id: String,
     pub scopes: Vec<String>,
     pub roh_before: f32,
     pub roh_after: f32,
+    /// Signed; a token without one is already expired.
+    #[serde(default)]
+    pub expires_at_unix: i64,
     pub signatures: Vec<String>,
     pub hexstamp: String,
+    /// Hash of the Bostrom tx whose memo commits to `hexstamp`. Not part of
+    /// the signed bytes: the tx is broadcast after co-signing.
+    #[serde(default)]
+    pub bostrom_tx: Option<String>,
 }
 
 #[derive(Debug, Clone)]
@@ -18,33 +39,247 @@ pub struct GuardError {
 }
 
 pub trait BostromClient {
-    fn verify_evolve_token(&self, token_id: &str, subject_id: &str) -> anyhow::Result<bool>;
+    fn verify_evolve_token(&self, token: &EvolveToken) -> anyhow::Result<bool>;
+}
+
+impl<T: BostromClient + ?Sized> BostromClient for Box<T> {
+    fn verify_evolve_token(&self, token: &EvolveToken) -> anyhow::Result<bool> {
+        (**self).verify_evolve_token(token)
+    }
+}
+
+/// Client for a node without a verified stake shard: refuses every token.
+pub struct FailClosed;
+
+impl BostromClient for FailClosed {
+    fn verify_evolve_token(&self, _token: &EvolveToken) -> anyhow::Result<bool> {
+        Ok(false)
+    }
+}
+
+/// Implemented by action types that carry their own EVOLVE token.
+pub trait EvolveGated {
+    /// Structural / OTA actions that must present a token.
+    fn requires_evolve_token(&self) -> bool;
+    fn evolve_token(&self) -> Option<&EvolveToken>;
+    /// Scope a token must list for this action, e.g. "ApplyOta".
+    fn evolve_scope(&self) -> String;
+    /// `(roh_before, roh_after_estimate)` the action claims.
+    fn roh_span(&self) -> (f32, f32);
+}
+
+/// Tie a (signature-verified) token to one action: its scopes must name the
+/// action, it must be unexpired, and the action may not start from another
+/// RoH or end above the RoH the stake approved.
+pub fn check_binding<A: EvolveGated>(
+    token: &EvolveToken,
+    action: &A,
+    now_unix: i64,
+) -> Result<(), GuardError> {
+    let scope = action.evolve_scope();
+    if !token.scopes.iter().any(|s| *s == scope) {
+        return Err(GuardError {
+            code: "EVOLVE_SCOPE_MISMATCH".into(),
+            message: format!("EVOLVE token scopes {:?} do not cover {}", token.scopes, scope),
+        });
+    }
+    if now_unix >= token.expires_at_unix {
+        return Err(GuardError {
+            code: "EVOLVE_TOKEN_EXPIRED".into(),
+            message: format!("EVOLVE token {} expired", token.token_id),
+        });
+    }
+    let (before, after) = action.roh_span();
+    // Written so a NaN estimate never matches.
+    let within = after <= token.roh_after + 1e-6;
+    if (before - token.roh_before).abs() > 1e-6 || !within {
+        return Err(GuardError {
+            code: "EVOLVE_ROH_MISMATCH".into(),
+            message: format!(
+                "Action moves RoH {:.3} -> {:.3}; token approves {:.3} -> {:.3}",
+                before, after, token.roh_before, token.roh_after
+            ),
+        });
+    }
+    Ok(())
 }
 
+/// Verifies and spends EVOLVE tokens. Each token authorizes one action; the
+/// spent set is in memory, and the signed expiry bounds replay across restarts.
+/// [`check`](Self::check) only verifies; the caller [`spend`](Self::spend)s
+/// once every other guard has passed, and can [`release`](Self::release) a
+/// token whose action was not authorized after all.
 pub struct EvolveGuard<C: BostromClient> {
     client: C,
+    spent: Mutex<HashSet<String>>,
 }
 
 impl<C: BostromClient> EvolveGuard<C> {
     pub fn new(client: C) -> Self {
-        Self { client }
-    }
-
-    pub fn check(&self, action: &crate::XRAction, subject_id: &str) -> Result<(), GuardError> {
-        if matches!(action.kind, crate::XRActionKind::ProposeEvolve | crate::XRActionKind::ApplyOta) {
-            // In a full impl, the action carries token_id; simplified here.
-            let token_id = "from_action_context";
-            let ok = self
-                .client
-                .verify_evolve_token(token_id, subject_id)
-                .unwrap_or(false);
-            if !ok {
+        Self {
+            client,
+            spent: Mutex::new(HashSet::new()),
+        }
+    }
+
+    /// Verify the action's token without spending it.
+    pub fn check<A: EvolveGated>(
+        &self,
+        action: &A,
+        subject_id: &str,
+        now_unix: i64,
+    ) -> Result<(), GuardError> {
+        if !action.requires_evolve_token() {
+            return Ok(());
+        }
+        let token = action.evolve_token().ok_or_else(|| GuardError {
+            code: "EVOLVE_TOKEN_MISSING".into(),
+            message: "Structural change carries no EVOLVE token".into(),
+        })?;
+        if token.subject_id != subject_id {
+            return Err(GuardError {
+                code: "EVOLVE_SUBJECT_MISMATCH".into(),
+                message: format!(
+                    "EVOLVE token is for {}, not {}",
+                    token.subject_id, subject_id
+                ),
+            });
+        }
+        check_binding(token, action, now_unix)?;
+        match self.client.verify_evolve_token(token) {
+            Ok(true) => {}
+            Ok(false) => {
                 return Err(GuardError {
                     code: "EVOLVE_TOKEN_INVALID".into(),
                     message: "Missing or invalid EVOLVE token for structural change".into(),
-                });
+                })
             }
+            Err(e) => {
+                return Err(GuardError {
+                    code: "EVOLVE_TOKEN_INVALID".into(),
+                    message: e.to_string(),
+                })
+            }
+        }
+        let spent = self.spent.lock().expect("evolve guard lock poisoned");
+        if spent.contains(&token.hexstamp) {
+            return Err(spent_error(token));
+        }
+        Ok(())
+    }
+
+    /// Mark the action's token used; fails if another action spent it since
+    /// [`check`](Self::check). A no-op for actions that need no token.
+    pub fn spend<A: EvolveGated>(&self, action: &A) -> Result<(), GuardError> {
+        let Some(token) = action.evolve_token().filter(|_| action.requires_evolve_token()) else {
+            return Ok(());
+        };
+        let mut spent = self.spent.lock().expect("evolve guard lock poisoned");
+        if !spent.insert(token.hexstamp.clone()) {
+            return Err(spent_error(token));
         }
         Ok(())
     }
+
+    /// Undo [`spend`](Self::spend) for an action that was not authorized.
+    pub fn release<A: EvolveGated>(&self, action: &A) {
+        if let Some(token) = action.evolve_token().filter(|_| action.requires_evolve_token()) {
+            let mut spent = self.spent.lock().expect("evolve guard lock poisoned");
+            spent.remove(&token.hexstamp);
+        }
+    }
+}
+
+fn spent_error(token: &EvolveToken) -> GuardError {
+    GuardError {
+        code: "EVOLVE_TOKEN_SPENT".into(),
+        message: format!("EVOLVE token {} was already used", token.token_id),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Action {
+        kind: &'static str,
+        roh: (f32, f32),
+        token: EvolveToken,
+    }
+
+    impl EvolveGated for Action {
+        fn requires_evolve_token(&self) -> bool {
+            true
+        }
+        fn evolve_token(&self) -> Option<&EvolveToken> {
+            Some(&self.token)
+        }
+        fn evolve_scope(&self) -> String {
+            self.kind.into()
+        }
+        fn roh_span(&self) -> (f32, f32) {
+            self.roh
+        }
+    }
+
+    struct AcceptAll;
+
+    impl BostromClient for AcceptAll {
+        fn verify_evolve_token(&self, _token: &EvolveToken) -> anyhow::Result<bool> {
+            Ok(true)
+        }
+    }
+
+    fn action(kind: &'static str, roh: (f32, f32)) -> Action {
+        Action {
+            kind,
+            roh,
+            token: EvolveToken {
+                version: offline::TOKEN_VERSION,
+                token_id: "evolve-0001".into(),
+                subject_id: "bostrom18sd2u".into(),
+                scopes: vec!["ApplyOta".into()],
+                roh_before: 0.2,
+                roh_after: 0.15,
+                expires_at_unix: 1_000,
+                signatures: Vec::new(),
+                hexstamp: "ab".into(),
+                bostrom_tx: None,
+            },
+        }
+    }
+
+    fn code(r: Result<(), GuardError>) -> String {
+        r.unwrap_err().code
+    }
+
+    #[test]
+    fn token_is_bound_to_scope_span_expiry_and_one_use() {
+        let guard = EvolveGuard::new(AcceptAll);
+        let subject = "bostrom18sd2u";
+        assert_eq!(
+            code(guard.check(&action("ProposeEvolve", (0.2, 0.15)), subject, 10)),
+            "EVOLVE_SCOPE_MISMATCH"
+        );
+        assert_eq!(
+            code(guard.check(&action("ApplyOta", (0.2, 0.15)), subject, 1_000)),
+            "EVOLVE_TOKEN_EXPIRED"
+        );
+        assert_eq!(
+            code(guard.check(&action("ApplyOta", (0.1, 0.05)), subject, 10)),
+            "EVOLVE_ROH_MISMATCH"
+        );
+        assert_eq!(
+            code(guard.check(&action("ApplyOta", (0.2, 0.25)), subject, 10)),
+            "EVOLVE_ROH_MISMATCH"
+        );
+        let ok = action("ApplyOta", (0.2, 0.1));
+        guard.check(&ok, subject, 10).unwrap();
+        guard.check(&ok, subject, 10).unwrap();
+        guard.spend(&ok).unwrap();
+        assert_eq!(code(guard.check(&ok, subject, 10)), "EVOLVE_TOKEN_SPENT");
+        assert_eq!(code(guard.spend(&ok)), "EVOLVE_TOKEN_SPENT");
+        guard.release(&ok);
+        guard.check(&ok, subject, 10).unwrap();
+    }
 }
@@ -0,0 +1,266 @@
+//! Local, chain-free EVOLVE verification: recoverable secp256k1 signatures
+//! over the token's canonical bytes, checked against the `.stake.aln`
+//! multisig set.
+
+use k256::ecdsa::{SigningKey, VerifyingKey};
+use policy_bundle::{recoverable, VerifiedBundle};
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+use std::collections::BTreeSet;
+use thiserror::Error;
+
+use crate::stake::StakeShard;
+use crate::{BostromClient, EvolveToken};
+
+/// Global RoH ceiling an EVOLVE token may never start above.
+pub const ROH_CEILING: f32 = 0.3;
+
+/// Version of the bytes co-signers sign. Version 0 tokens were signed over
+/// `serde_json` field order without an expiry; they are never accepted and
+/// must be re-issued under this version.
+pub const TOKEN_VERSION: u32 = 1;
+
+#[derive(Debug, Error)]
+pub enum EvolveError {
+    #[error("token version {found} is not supported (expected {TOKEN_VERSION}); re-issue the token")]
+    UnsupportedVersion { found: u32 },
+    #[error("token subject {token} does not match stake subject {stake}")]
+    SubjectMismatch { token: String, stake: String },
+    #[error("hexstamp {stored} does not match canonical token bytes ({computed})")]
+    HexstampMismatch { stored: String, computed: String },
+    #[error("RoH must satisfy roh_after <= roh_before <= {ROH_CEILING} (got {before} -> {after})")]
+    RohNotMonotone { before: f32, after: f32 },
+    #[error("signature {index} is malformed: {message}")]
+    MalformedSignature { index: usize, message: String },
+    #[error("signature {index} is from {pubkey}, which is not in the stake multisig set")]
+    UnknownSigner { index: usize, pubkey: String },
+    #[error("{valid} distinct stake signatures, threshold is {threshold}")]
+    BelowThreshold { valid: usize, threshold: u32 },
+}
+
+/// Signed portion of the token.
+#[derive(Serialize)]
+struct CanonicalToken<'a> {
+    version: u32,
+    token_id: &'a str,
+    subject_id: &'a str,
+    scopes: &'a [String],
+    roh_before: f32,
+    roh_after: f32,
+    expires_at_unix: i64,
+}
+
+/// Bytes every co-signer signs: canonical JSON of all fields except
+/// `signatures`, `hexstamp` and `bostrom_tx`.
+pub fn canonical_bytes(token: &EvolveToken) -> Vec<u8> {
+    aln_format::to_canonical_json(&CanonicalToken {
+        version: token.version,
+        token_id: &token.token_id,
+        subject_id: &token.subject_id,
+        scopes: &token.scopes,
+        roh_before: token.roh_before,
+        roh_after: token.roh_after,
+        expires_at_unix: token.expires_at_unix,
+    })
+    .expect("canonical token serializes")
+    .into_bytes()
+}
+
+/// SHA-256 hexstamp of the canonical bytes.
+pub fn token_hexstamp(token: &EvolveToken) -> String {
+    hex::encode(Sha256::digest(canonical_bytes(token)))
+}
+
+/// Produce one co-signature: hex of `r || s || recovery_id` (65 bytes).
+pub fn sign_token(token: &EvolveToken, key: &SigningKey) -> String {
+    recoverable::sign(&canonical_bytes(token), key)
+}
+
+/// Verifies EVOLVE tokens against one subject's stake shard, without network.
+pub struct OfflineEvolveVerifier {
+    stake: StakeShard,
+    signers: Vec<VerifyingKey>,
+}
+
+impl OfflineEvolveVerifier {
+    pub fn new(stake: StakeShard) -> anyhow::Result<Self> {
+        let signers = stake
+            .multisig
+            .signers
+            .iter()
+            .map(|s| {
+                let bytes = hex::decode(&s.pubkey)?;
+                VerifyingKey::from_sec1_bytes(&bytes)
+                    .map_err(|e| anyhow::anyhow!("stake signer {}: {}", s.label, e))
+            })
+            .collect::<anyhow::Result<Vec<_>>>()?;
+        Ok(Self { stake, signers })
+    }
+
+    /// Stake shard taken from a signed policy bundle, never a loose file.
+    pub fn from_bundle(bundle: &VerifiedBundle) -> anyhow::Result<Self> {
+        Self::new(StakeShard::parse(bundle.text("stake.aln")?)?)
+    }
+
+    pub fn verify(&self, token: &EvolveToken) -> Result<(), EvolveError> {
+        if token.version != TOKEN_VERSION {
+            return Err(EvolveError::UnsupportedVersion {
+                found: token.version,
+            });
+        }
+        if token.subject_id != self.stake.subject_id {
+            return Err(EvolveError::SubjectMismatch {
+                token: token.subject_id.clone(),
+                stake: self.stake.subject_id.clone(),
+            });
+        }
+
+        let computed = token_hexstamp(token);
+        if computed != token.hexstamp {
+            return Err(EvolveError::HexstampMismatch {
+                stored: token.hexstamp.clone(),
+                computed,
+            });
+        }
+
+        if !(token.roh_after <= token.roh_before && token.roh_before <= ROH_CEILING) {
+            return Err(EvolveError::RohNotMonotone {
+                before: token.roh_before,
+                after: token.roh_after,
+            });
+        }
+
+        let msg = canonical_bytes(token);
+        let mut approved = BTreeSet::new();
+        for (index, sig) in token.signatures.iter().enumerate() {
+            let key = recoverable::recover(&msg, sig)
+                .map_err(|message| EvolveError::MalformedSignature { index, message })?;
+            match self.signers.iter().position(|k| *k == key) {
+                Some(i) => {
+                    approved.insert(i);
+                }
+                None => {
+                    return Err(EvolveError::UnknownSigner {
+                        index,
+                        pubkey: recoverable::pubkey_hex(&key),
+                    })
+                }
+            }
+        }
+
+        if approved.len() < self.stake.multisig.threshold as usize {
+            return Err(EvolveError::BelowThreshold {
+                valid: approved.len(),
+                threshold: self.stake.multisig.threshold,
+            });
+        }
+        Ok(())
+    }
+}
+
+impl BostromClient for OfflineEvolveVerifier {
+    fn verify_evolve_token(&self, token: &EvolveToken) -> anyhow::Result<bool> {
+        self.verify(token)?;
+        Ok(true)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::stake::{MultisigSet, StakeSigner};
+
+    fn key(byte: u8) -> SigningKey {
+        SigningKey::from_slice(&[byte; 32]).unwrap()
+    }
+
+    fn pubkey_hex(k: &SigningKey) -> String {
+        hex::encode(k.verifying_key().to_encoded_point(true).as_bytes())
+    }
+
+    fn verifier(threshold: u32) -> OfflineEvolveVerifier {
+        OfflineEvolveVerifier::new(StakeShard {
+            subject_id: "bostrom18sd2u".into(),
+            multisig: MultisigSet {
+                threshold,
+                signers: (1..=3)
+                    .map(|b| StakeSigner {
+                        label: format!("signer-{}", b),
+                        pubkey: pubkey_hex(&key(b)),
+                    })
+                    .collect(),
+            },
+        })
+        .unwrap()
+    }
+
+    fn token(signers: &[u8]) -> EvolveToken {
+        let mut t = EvolveToken {
+            version: TOKEN_VERSION,
+            token_id: "evolve-0001".into(),
+            subject_id: "bostrom18sd2u".into(),
+            scopes: vec!["ApplyOta".into()],
+            roh_before: 0.2,
+            roh_after: 0.15,
+            expires_at_unix: 1_900_000_000,
+            signatures: Vec::new(),
+            hexstamp: String::new(),
+            bostrom_tx: None,
+        };
+        t.hexstamp = token_hexstamp(&t);
+        t.signatures = signers.iter().map(|b| sign_token(&t, &key(*b))).collect();
+        t
+    }
+
+    #[test]
+    fn accepts_threshold_of_stake_signers() {
+        verifier(2).verify(&token(&[1, 3])).unwrap();
+    }
+
+    #[test]
+    fn rejects_duplicates_outsiders_and_tampering() {
+        let v = verifier(2);
+        assert!(matches!(
+            v.verify(&token(&[1, 1])),
+            Err(EvolveError::BelowThreshold { valid: 1, .. })
+        ));
+        assert!(matches!(
+            v.verify(&token(&[1, 9])),
+            Err(EvolveError::UnknownSigner { index: 1, .. })
+        ));
+
+        let mut t = token(&[1, 2]);
+        t.scopes.push("KernelChange".into());
+        assert!(matches!(
+            v.verify(&t),
+            Err(EvolveError::HexstampMismatch { .. })
+        ));
+        t.hexstamp = token_hexstamp(&t);
+        // Signatures were over the old scopes; they now recover to strangers.
+        assert!(matches!(
+            v.verify(&t),
+            Err(EvolveError::UnknownSigner { .. })
+        ));
+
+        let mut legacy = token(&[1, 2]);
+        legacy.version = 0;
+        assert!(matches!(
+            v.verify(&legacy),
+            Err(EvolveError::UnsupportedVersion { found: 0 })
+        ));
+    }
+
+    #[test]
+    fn enforces_roh_monotone_under_ceiling() {
+        let v = verifier(1);
+        let mut t = token(&[]);
+        t.roh_before = 0.35;
+        t.roh_after = 0.2;
+        t.hexstamp = token_hexstamp(&t);
+        t.signatures = vec![sign_token(&t, &key(1))];
+        assert!(matches!(
+            v.verify(&t),
+            Err(EvolveError::RohNotMonotone { .. })
+        ));
+    }
+}
@@ -0,0 +1,43 @@
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+/// One key allowed to co-sign EVOLVE tokens for the subject.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StakeSigner {
+    /// Human label or DID, e.g. "host", "did:key:z6Mk...".
+    pub label: String,
+    /// SEC1-compressed secp256k1 public key, hex (33 bytes).
+    pub pubkey: String,
+}
+
+/// k-of-n signer set that must approve structural evolution.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MultisigSet {
+    pub threshold: u32,
+    pub signers: Vec<StakeSigner>,
+}
+
+/// `.stake.aln` shard: who may authorize EVOLVE for a subject.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StakeShard {
+    pub subject_id: String,
+    pub multisig: MultisigSet,
+}
+
+impl StakeShard {
+    pub fn load<P: AsRef<Path>>(path: P) -> anyhow::Result<Self> {
+        Self::parse(&std::fs::read_to_string(path)?)
+    }
+
+    pub fn parse(text: &str) -> anyhow::Result<Self> {
+        let shard: StakeShard = aln_format::from_str(text)?;
+        anyhow::ensure!(
+            shard.multisig.threshold >= 1
+                && shard.multisig.threshold as usize <= shard.multisig.signers.len(),
+            "stake multisig threshold {} is not satisfiable by {} signers",
+            shard.multisig.threshold,
+            shard.multisig.signers.len()
+        );
+        Ok(shard)
+    }
+}
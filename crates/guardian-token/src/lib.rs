@@ -1,51 +1,70 @@
-use serde::{Deserialize, Serialize};
-use std::{fs, path::Path};
-
-use sovereign_kernel::types::{GuardError, GuardResult, SovereignAction, SovereignActionKind, TokenVerifier};
-
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct EvolveToken {
-    pub scope_paths: Vec<String>,
-    pub roh_before: f32,
-    pub roh_after: f32,
-    pub token_kind: String,      // "QPolicyUpdate" | "BioScaleUpgrade" | ...
-    pub signatures: Vec<String>, // Bostrom DIDs
-    pub hexstamp: String,
-}
+use evolve_token_verifier::{
+    check_binding, ChainEvolveVerifier, EvolveGated, EvolveToken, OfflineEvolveVerifier,
+};
+
+use sovereign_kernel::types::{GuardError, GuardResult, SovereignAction, TokenVerifier};
 
 pub trait BostromVerifier {
     fn verify_on_chain(&self, token: &EvolveToken) -> anyhow::Result<bool>;
 }
 
+/// Signature and multisig check against `.stake.aln`, no chain round-trip.
+impl BostromVerifier for OfflineEvolveVerifier {
+    fn verify_on_chain(&self, token: &EvolveToken) -> anyhow::Result<bool> {
+        self.verify(token)?;
+        Ok(true)
+    }
+}
+
+/// Multisig check, then inclusion and finality of `bostrom_tx` via the LCD.
+impl BostromVerifier for ChainEvolveVerifier {
+    fn verify_on_chain(&self, token: &EvolveToken) -> anyhow::Result<bool> {
+        self.verify(token)?;
+        Ok(true)
+    }
+}
+
 pub struct TokenVerifierImpl<V: BostromVerifier> {
-    evolve_token: EvolveToken,
     verifier: V,
 }
 
 impl<V: BostromVerifier> TokenVerifierImpl<V> {
-    pub fn from_file(path: &Path, verifier: V) -> anyhow::Result<Self> {
-        let text = fs::read_to_string(path)?;
-        let token: EvolveToken = serde_json::from_str(&text)?;
-        Ok(Self {
-            evolve_token: token,
-            verifier,
-        })
+    pub fn new(verifier: V) -> Self {
+        Self { verifier }
     }
 }
 
 impl<V: BostromVerifier> TokenVerifier for TokenVerifierImpl<V> {
     fn verify_evolve_token(&self, action: &SovereignAction) -> GuardResult {
-        if !matches!(
-            action.kind,
-            SovereignActionKind::ProposeEvolve | SovereignActionKind::ApplyOta
-        ) {
+        if !action.requires_evolve_token() {
             return Ok(());
         }
 
         // Structural / OTA changes must carry valid EVOLVE token
+        let token = action.evolve_token().ok_or_else(|| GuardError {
+            code: "EVOLVE_MISSING".into(),
+            message: "Structural / OTA action carries no EVOLVE token.".into(),
+        })?;
+        if action.subject_id != *token.subject_id {
+            return Err(GuardError {
+                code: "EVOLVE_SUBJECT_MISMATCH".into(),
+                message: format!(
+                    "EVOLVE token is for {}, not {}.",
+                    token.subject_id, action.subject_id
+                ),
+            });
+        }
+        let now = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|d| d.as_secs() as i64)
+            .unwrap_or(0);
+        check_binding(token, action, now).map_err(|e| GuardError {
+            code: e.code,
+            message: e.message,
+        })?;
         let ok = self
             .verifier
-            .verify_on_chain(&self.evolve_token)
+            .verify_on_chain(token)
             .map_err(|e| GuardError {
                 code: "EVOLVE_VERIFY_ERR".into(),
                 message: e.to_string(),
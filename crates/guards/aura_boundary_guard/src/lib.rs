@@ -1,5 +1,9 @@
-use std::{collections::HashMap, net::IpAddr, time::Duration};
+use std::{collections::HashMap, net::IpAddr, sync::Arc, time::Duration};
+use chord_seal::{
+    extend_tag, Caveat, ChordIssuer, ChordRef, ChordStore, ChordVerifier, Grant, SealError, StoreError,
+};
 use serde::{Deserialize, Serialize};
+use subject_id::{SubjectId, SubjectIdError};
 use thiserror::Error;
 use uuid::Uuid;
 
@@ -21,9 +25,81 @@ pub struct CapabilityChord {
     pub route: String,         // "BCI", "OTA", "GOV", "CHAT"
     pub max_calls: u32,
     pub expires_at_unix: i64,
+    /// Hex Ed25519 seal from the node's chord issuer; empty means unsigned.
+    #[serde(default)]
+    pub seal: String,
+    /// Restrictions appended when delegating, e.g. a domain subset.
+    #[serde(default)]
+    pub caveats: Vec<Caveat>,
+    #[serde(default)]
+    pub caveat_tag: String,
+}
+
+#[derive(Serialize)]
+struct ChordClaims<'a> {
+    id: &'a Uuid,
+    kind: &'a CapabilityKind,
+    subject_id: &'a str,
+    route: &'a str,
+    max_calls: u32,
+    expires_at_unix: i64,
 }
 
 impl CapabilityChord {
+    fn claims(&self) -> ChordClaims<'_> {
+        ChordClaims {
+            id: &self.id,
+            kind: &self.kind,
+            subject_id: &self.subject_id,
+            route: &self.route,
+            max_calls: self.max_calls,
+            expires_at_unix: self.expires_at_unix,
+        }
+    }
+
+    fn base_grant(&self) -> Grant {
+        Grant {
+            expires_at_unix: self.expires_at_unix,
+            route: self.route.clone(),
+            max_tokens: None,
+            max_calls: Some(self.max_calls),
+            domains: None,
+        }
+    }
+
+    /// Seal the chord with the node issuer key; call after the last field edit.
+    pub fn seal_with(&mut self, issuer: &ChordIssuer) -> Result<(), SealError> {
+        self.seal = issuer.seal(&self.claims())?;
+        self.caveat_tag = issuer.caveat_tag(&self.claims())?;
+        self.caveats.clear();
+        Ok(())
+    }
+
+    /// Narrower copy for a sub-agent; widening caveats are refused.
+    pub fn attenuate(&self, caveat: Caveat) -> Result<Self, SealError> {
+        let mut grant = self.base_grant();
+        for c in &self.caveats {
+            grant.restrict(c)?;
+        }
+        grant.restrict(&caveat)?;
+        let mut child = self.clone();
+        child.caveat_tag = extend_tag(&self.caveat_tag, &caveat)?;
+        child.caveats.push(caveat);
+        child.seal.clear();
+        Ok(child)
+    }
+
+    /// Verify the seal or caveat chain and return what the chord permits.
+    pub fn grant(&self, verifier: &ChordVerifier) -> Result<Grant, SealError> {
+        verifier.grant(
+            &self.claims(),
+            &self.seal,
+            self.base_grant(),
+            &self.caveats,
+            &self.caveat_tag,
+        )
+    }
+
     pub fn is_expired(&self) -> bool {
         chrono::Utc::now().timestamp() > self.expires_at_unix
     }
@@ -45,6 +121,14 @@ pub struct NetworkPolicy {
 
 #[derive(Debug, Error)]
 pub enum NetworkGuardError {
+    #[error("capability chord rejected: {0}")]
+    Unsealed(#[from] SealError),
+    #[error("capability revoked: {0}")]
+    Revoked(String),
+    #[error("capability store unavailable: {0}")]
+    Store(StoreError),
+    #[error("capability subject is not a valid address: {0}")]
+    Subject(#[from] SubjectIdError),
     #[error("capability expired or exhausted")]
     CapabilityExpired,
     #[error("network target not allowed for capability {0:?}")]
@@ -54,14 +138,17 @@ pub enum NetworkGuardError {
 #[derive(Debug)]
 pub struct AuraBoundaryGuard {
     policy: NetworkPolicy,
-    usage: HashMap<Uuid, u32>,
+    chords: ChordVerifier,
+    /// Persistent call accounting and revocations, shared with other guards.
+    store: Arc<ChordStore>,
 }
 
 impl AuraBoundaryGuard {
-    pub fn new(policy: NetworkPolicy) -> Self {
+    pub fn new(policy: NetworkPolicy, chords: ChordVerifier, store: Arc<ChordStore>) -> Self {
         Self {
             policy,
-            usage: HashMap::new(),
+            chords,
+            store,
         }
     }
 
@@ -72,13 +159,13 @@ impl AuraBoundaryGuard {
         port: u16,
         ip_hint: Option<IpAddr>,
     ) -> Result<bool, NetworkGuardError> {
-        if cap.is_expired() {
+        let grant = cap.grant(&self.chords)?;
+        if chrono::Utc::now().timestamp() > grant.expires_at_unix {
             return Err(NetworkGuardError::CapabilityExpired);
         }
 
-        let used = self.usage.entry(cap.id).or_insert(0);
-        if *used >= cap.max_calls {
-            return Err(NetworkGuardError::CapabilityExpired);
+        if !grant.allows_domain(domain) {
+            return Err(NetworkGuardError::TargetNotAllowed(cap.kind.clone()));
         }
 
         // IP allowlist takes precedence for strict local RPC.
@@ -102,8 +189,18 @@ impl AuraBoundaryGuard {
             return Err(NetworkGuardError::TargetNotAllowed(cap.kind.clone()));
         }
 
-        *used += 1;
-        Ok(true)
+        // Charged last, so refused targets do not burn calls. Delegated
+        // chords share their parent's id, and so its call budget.
+        // Revocations are keyed by the canonical subject, not its spelling.
+        let subject = SubjectId::parse(&cap.subject_id)?;
+        let (id, kind) = (cap.id.to_string(), format!("{:?}", cap.kind));
+        let chord = ChordRef { id: &id, subject_id: &subject, kind: &kind };
+        match self.store.consume(chord, 1, 0, grant.max_calls, None) {
+            Ok(_) => Ok(true),
+            Err(StoreError::Revoked(id)) => Err(NetworkGuardError::Revoked(id)),
+            Err(StoreError::Exhausted { .. }) => Err(NetworkGuardError::CapabilityExpired),
+            Err(e) => Err(NetworkGuardError::Store(e)),
+        }
     }
 }
 
@@ -1,7 +1,8 @@
-use std::{fs::OpenOptions, io::Write, path::{Path, PathBuf}};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
 use serde::{Deserialize, Serialize};
 use thiserror::Error;
-use chrono::{DateTime, Utc};
+use donutloop::{digest_hex, DonutloopWriter, EntryDraft, LedgerError, Verdict};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum FsOpKind {
@@ -19,41 +20,37 @@ pub struct FsOpContext {
     pub dest: Option<PathBuf>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct DonutloopEntry {
-    pub proposal_id: String,
-    pub decision: String,
-    pub roh_before: f32,
-    pub roh_after: f32,
-    pub effect_bounds: String,
-    pub token_kind: String,
-    pub timestamp: DateTime<Utc>,
-    pub reason: String,
-}
-
 #[derive(Debug, Error)]
 pub enum ShieldError {
     #[error("operation denied: {0}")]
     Denied(String),
     #[error("io error: {0}")]
     Io(#[from] std::io::Error),
+    #[error("ledger error: {0}")]
+    Ledger(#[from] LedgerError),
+    #[error("operation context does not serialize: {0}")]
+    Context(#[from] serde_json::Error),
 }
 
 #[derive(Debug, Clone)]
 pub struct ShieldConfig {
     pub sovereign_roots: Vec<PathBuf>,
     pub protected_exts: Vec<String>, // [".neuroaln", ".lifeforce.aln", ...]
-    pub donutloop_path: PathBuf,
+    /// Hash of the policy bundle this shield enforces; stamped on every denial.
+    pub policy_bundle_hash: String,
 }
 
-#[derive(Debug)]
 pub struct SoulNonTradeableShield {
     cfg: ShieldConfig,
+    ledger: Arc<DonutloopWriter>,
 }
 
 impl SoulNonTradeableShield {
-    pub fn new(cfg: ShieldConfig) -> Self {
-        Self { cfg }
+    /// `ledger` must be the node's one writer for `.donutloop.aln` (the
+    /// gate's, see `DonutloopLogger::writer`); a second writer on the same
+    /// file would fork the chain.
+    pub fn new(cfg: ShieldConfig, ledger: Arc<DonutloopWriter>) -> Self {
+        Self { cfg, ledger }
     }
 
     fn is_protected(&self, path: &Path) -> bool {
@@ -74,23 +71,16 @@ impl SoulNonTradeableShield {
     }
 
     fn log_denial(&self, ctx: &FsOpContext, reason: &str) -> Result<(), ShieldError> {
-        let entry = DonutloopEntry {
-            proposal_id: format!("fs-deny-{}", ctx.kind as u8),
-            decision: "denied".into(),
-            roh_before: 0.1,
-            roh_after: 0.1,
-            effect_bounds: "fs-export".into(),
-            token_kind: "FsGuard".into(),
-            timestamp: Utc::now(),
+        // A non-UTF-8 path does not serialize; fail the check, not the process.
+        let request_digest = digest_hex(&serde_json::to_vec(ctx)?);
+        self.ledger.append_under(EntryDraft {
+            subject_id: ctx.subject_id.clone(),
+            route: ctx.route.clone(),
+            verdict: Verdict::Reject,
+            guard_code: "SOUL_NON_TRADEABLE".into(),
             reason: reason.into(),
-        };
-        let line = serde_json::to_string(&entry).unwrap();
-
-        let mut file = OpenOptions::new()
-            .create(true)
-            .append(true)
-            .open(&self.cfg.donutloop_path)?;
-        writeln!(file, "{line}")?;
+            request_digest,
+        }, &self.cfg.policy_bundle_hash)?;
         Ok(())
     }
 
@@ -0,0 +1,303 @@
+//! Append-only JSON-lines journal shared by several handles or processes.
+//!
+//! State is a fold over every event in the file. Each access first applies
+//! whatever other handles appended since, so an event written by one guard
+//! (a revocation, a usage charge) is seen by the very next check in every
+//! other guard sharing the file.
+//!
+//! A torn final line left by a crash mid-append is cut before the next
+//! append, which would otherwise be glued onto it and corrupt the journal
+//! for good. Appends and that cut both run under an advisory lock on the
+//! file, so a partial line is only ever cut once no writer, in this process
+//! or another, is still in the middle of it.
+
+use std::fs::{File, OpenOptions};
+use std::io::{Read, Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum JournalError {
+    #[error("journal I/O: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("journal line {line} is corrupt: {message}")]
+    Corrupt { line: usize, message: String },
+}
+
+/// State rebuilt by replaying the journal's events in order.
+pub trait Replay: Default {
+    type Event: Serialize + DeserializeOwned;
+
+    fn apply(&mut self, event: Self::Event);
+}
+
+/// How far into the file the state has been brought.
+#[derive(Default)]
+struct Cursor {
+    /// Bytes of the journal already applied.
+    offset: u64,
+    lines: usize,
+}
+
+pub struct Journal<S> {
+    path: PathBuf,
+    inner: Mutex<(File, Cursor, S)>,
+}
+
+impl<S> std::fmt::Debug for Journal<S> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Journal").field("path", &self.path).finish()
+    }
+}
+
+/// Caught-up state under the journal lock, plus the means to extend it.
+pub struct Txn<'a, S> {
+    file: &'a mut File,
+    cursor: &'a mut Cursor,
+    state: &'a mut S,
+}
+
+impl<S: Replay> Txn<'_, S> {
+    pub fn state(&self) -> &S {
+        self.state
+    }
+
+    /// Append `event` durably, then read it back together with anything
+    /// appended concurrently.
+    pub fn append(&mut self, event: &S::Event) -> Result<(), JournalError> {
+        let mut line = serde_json::to_vec(event).map_err(std::io::Error::other)?;
+        line.push(b'\n');
+        {
+            let file: &File = self.file;
+            let _lock = FileLock::acquire(file)?;
+            cut_torn_tail(file, self.cursor.offset)?;
+            (&*file).write_all(&line)?;
+            file.sync_data()?;
+        }
+        catch_up(self.file, self.cursor, self.state)
+    }
+}
+
+impl<S: Replay> Journal<S> {
+    /// Open (or create) the journal at `path` and replay it.
+    pub fn open<P: AsRef<Path>>(path: P) -> Result<Self, JournalError> {
+        let path = path.as_ref().to_path_buf();
+        let file = OpenOptions::new()
+            .read(true)
+            .append(true)
+            .create(true)
+            .open(&path)?;
+        {
+            let _lock = FileLock::acquire(&file)?;
+            cut_torn_tail(&file, 0)?;
+        }
+        let journal = Self {
+            path,
+            inner: Mutex::new((file, Cursor::default(), S::default())),
+        };
+        journal.read(|_| ())?;
+        Ok(journal)
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Run `f` on state caught up with the journal, under the journal lock.
+    pub fn with<T, E: From<JournalError>>(
+        &self,
+        f: impl FnOnce(&mut Txn<'_, S>) -> Result<T, E>,
+    ) -> Result<T, E> {
+        let mut guard = self.inner.lock().expect("journal lock poisoned");
+        let (file, cursor, state) = &mut *guard;
+        catch_up(file, cursor, state)?;
+        f(&mut Txn {
+            file,
+            cursor,
+            state,
+        })
+    }
+
+    /// Read-only [`with`](Self::with).
+    pub fn read<T>(&self, f: impl FnOnce(&S) -> T) -> Result<T, JournalError> {
+        self.with(|txn| Ok(f(txn.state())))
+    }
+}
+
+/// The journal's advisory lock, released on drop.
+struct FileLock<'a>(&'a File);
+
+impl<'a> FileLock<'a> {
+    fn acquire(file: &'a File) -> std::io::Result<Self> {
+        file.lock()?;
+        Ok(Self(file))
+    }
+}
+
+impl Drop for FileLock<'_> {
+    fn drop(&mut self) {
+        let _ = self.0.unlock();
+    }
+}
+
+/// Cut a partial last line, searching from `from` (a line boundary). Only
+/// call under [`FileLock`]: unlocked, the "torn" line may be another
+/// writer's append still in flight.
+fn cut_torn_tail(file: &File, from: u64) -> std::io::Result<()> {
+    let len = file.metadata()?.len();
+    if len <= from {
+        return Ok(());
+    }
+    let mut buf = Vec::new();
+    let mut reader = file;
+    reader.seek(SeekFrom::Start(from))?;
+    reader.take(len - from).read_to_end(&mut buf)?;
+    let committed = buf.iter().rposition(|b| *b == b'\n').map_or(0, |i| i + 1);
+    if committed < buf.len() {
+        file.set_len(from + committed as u64)?;
+        file.sync_all()?;
+    }
+    Ok(())
+}
+
+/// Apply every whole line appended since the last read, by anyone.
+fn catch_up<S: Replay>(
+    file: &mut File,
+    cursor: &mut Cursor,
+    state: &mut S,
+) -> Result<(), JournalError> {
+    let len = file.metadata()?.len();
+    if len <= cursor.offset {
+        return Ok(());
+    }
+    let mut buf = Vec::new();
+    file.seek(SeekFrom::Start(cursor.offset))?;
+    file.take(len - cursor.offset).read_to_end(&mut buf)?;
+    // Only whole lines; another writer may be mid-append.
+    let complete = buf.iter().rposition(|b| *b == b'\n').map_or(0, |i| i + 1);
+    // A line's bytes count as read only once it is applied, so a corrupt
+    // line stops every later read instead of being skipped.
+    for raw in buf[..complete].split_inclusive(|b| *b == b'\n') {
+        let line = &raw[..raw.len() - 1];
+        if !line.is_empty() {
+            let event = serde_json::from_slice(line).map_err(|e| JournalError::Corrupt {
+                line: cursor.lines + 1,
+                message: e.to_string(),
+            })?;
+            state.apply(event);
+            cursor.lines += 1;
+        }
+        cursor.offset += raw.len() as u64;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde::Deserialize;
+
+    #[derive(Default)]
+    struct Sum(i64);
+
+    #[derive(Serialize, Deserialize)]
+    struct Add(i64);
+
+    impl Replay for Sum {
+        type Event = Add;
+
+        fn apply(&mut self, event: Add) {
+            self.0 += event.0;
+        }
+    }
+
+    fn add(journal: &Journal<Sum>, n: i64) {
+        journal.with(|txn| txn.append(&Add(n))).unwrap();
+    }
+
+    #[test]
+    fn handles_see_each_others_appends() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("sum.jsonl");
+        let a = Journal::<Sum>::open(&path).unwrap();
+        let b = Journal::<Sum>::open(&path).unwrap();
+        add(&a, 2);
+        add(&b, 3);
+        assert_eq!(a.read(|s| s.0).unwrap(), 5);
+        assert_eq!(
+            Journal::<Sum>::open(&path).unwrap().read(|s| s.0).unwrap(),
+            5
+        );
+    }
+
+    #[test]
+    fn torn_tail_is_discarded_on_open() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("sum.jsonl");
+        add(&Journal::open(&path).unwrap(), 1);
+        let mut f = OpenOptions::new().append(true).open(&path).unwrap();
+        f.write_all(b"4").unwrap();
+        drop(f);
+
+        let journal = Journal::<Sum>::open(&path).unwrap();
+        add(&journal, 10);
+        assert_eq!(
+            Journal::<Sum>::open(&path).unwrap().read(|s| s.0).unwrap(),
+            11
+        );
+    }
+
+    #[test]
+    fn torn_tail_left_after_open_is_cut_before_the_next_append() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("sum.jsonl");
+        let journal = Journal::<Sum>::open(&path).unwrap();
+        add(&journal, 1);
+        // Another process crashed mid-append while this handle was open.
+        let mut f = OpenOptions::new().append(true).open(&path).unwrap();
+        f.write_all(b"4").unwrap();
+        drop(f);
+
+        add(&journal, 2);
+        assert_eq!(journal.read(|s| s.0).unwrap(), 3);
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "1\n2\n");
+    }
+
+    #[test]
+    fn open_waits_for_a_writer_holding_the_lock() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("sum.jsonl");
+        let writer = OpenOptions::new()
+            .append(true)
+            .create(true)
+            .open(&path)
+            .unwrap();
+        writer.lock().unwrap();
+        (&writer).write_all(b"5").unwrap();
+
+        let opener = {
+            let path = path.clone();
+            std::thread::spawn(move || Journal::<Sum>::open(&path).unwrap().read(|s| s.0))
+        };
+        std::thread::sleep(std::time::Duration::from_millis(100));
+        // The in-flight append completes; open must not have cut it.
+        (&writer).write_all(b"\n").unwrap();
+        writer.unlock().unwrap();
+        assert_eq!(opener.join().unwrap().unwrap(), 5);
+    }
+
+    #[test]
+    fn corrupt_line_is_reported_with_its_number() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("sum.jsonl");
+        std::fs::write(&path, "1\n{}\n").unwrap();
+        assert!(matches!(
+            Journal::<Sum>::open(&path),
+            Err(JournalError::Corrupt { line: 2, .. })
+        ));
+    }
+}
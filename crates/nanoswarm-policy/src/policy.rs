@@ -1,13 +1,41 @@
-use crate::state::{BioTelem, BioLoadFlag, SwarmMode, NanosotinEnvelope};
+use crate::state::{
+    BioTelem, BioLoadFlag, CounterOffer, SwarmMode, NanosotinEnvelope, SwarmWorkload,
+    WorkloadDecision,
+};
+use thiserror::Error;
+use viability_kernel::{AxisSnapshot, Polytope};
+
+#[derive(Debug, Clone, PartialEq, Error)]
+pub enum EnvelopeError {
+    #[error("nanosotin RoH ceiling {0} is above 0.3")]
+    RohCeiling(f32),
+}
 
 pub struct NanoswarmPolicyEngine {
     env: NanosotinEnvelope,
+    /// `.vkernel.aln` constraints and `.tsafe.aln` axis bounds, compiled
+    /// once at load, plus the envelope's own bounds; workloads are
+    /// projected onto it.
+    region: Polytope,
 }
 
 impl NanoswarmPolicyEngine {
-    pub fn new(env: NanosotinEnvelope) -> Self {
-        assert!(env.roh_ceiling <= 0.3 + 1e-6);
-        Self { env }
+    pub fn new(env: NanosotinEnvelope, mut region: Polytope) -> Result<Self, EnvelopeError> {
+        if env.roh_ceiling.is_nan() || env.roh_ceiling > 0.3 + 1e-6 {
+            return Err(EnvelopeError::RohCeiling(env.roh_ceiling));
+        }
+        let inf = f32::INFINITY;
+        for (axis, min, max) in [
+            ("roh_global", -inf, env.roh_ceiling),
+            ("host_energy_d", -inf, env.max_d),
+            ("psych_risk_dw", -inf, env.max_dw),
+            ("thermal_distance_index", -inf, env.max_thermal_distance),
+            ("lifeforce_index", env.min_lifeforce, inf),
+            ("molecular_balance_index", env.min_molecular_balance, inf),
+        ] {
+            region.push_axis_bounds(axis, f64::from(min), f64::from(max));
+        }
+        Ok(Self { env, region })
     }
 
     pub fn classify_bioload(&self, t: &BioTelem) -> BioLoadFlag {
@@ -42,4 +70,177 @@ impl NanoswarmPolicyEngine {
             BioLoadFlag::Violation => SwarmMode::Rollback,
         }
     }
+
+    /// Viability-region snapshot for `t`; region axes the swarm does not
+    /// report sit at zero.
+    fn point(&self, t: &BioTelem) -> AxisSnapshot {
+        let mut point = self.region.zero_snapshot();
+        for (axis, v) in t.axes() {
+            point.insert(axis.to_string(), v as f64);
+        }
+        point
+    }
+
+    /// Maximal uniform scale-down of `w` that keeps the host inside the
+    /// compiled region and the envelope. `None` when only doing nothing
+    /// fits, or the host is already outside.
+    pub fn project(&self, t: &BioTelem, w: &SwarmWorkload) -> Option<CounterOffer> {
+        let delta: AxisSnapshot = w
+            .deltas()
+            .into_iter()
+            .map(|(axis, d)| (axis.to_string(), d as f64))
+            .collect();
+        let scale = self.region.max_scale(&self.point(t), &delta).ok()??;
+        // Round down so the offer stays inside after f32 rounding.
+        let scale = ((scale * 1e4).floor() / 1e4) as f32;
+        if scale <= 0.0 {
+            return None;
+        }
+        let workload = w.scaled(scale);
+        let after = t.after(&workload);
+        (self.region.check(&self.point(&after)).is_ok()
+            && self.classify_bioload(&after) != BioLoadFlag::Violation)
+            .then_some(CounterOffer { scale, workload })
+    }
+
+    /// Allow `w` if the host stays inside the region and the envelope
+    /// afterwards. Otherwise allow only the largest scaled-down workload
+    /// that fits, or deny if none does.
+    pub fn evaluate_workload(&self, t: &BioTelem, w: &SwarmWorkload) -> WorkloadDecision {
+        let after = t.after(w);
+        let breach = match self.region.check(&self.point(&after)) {
+            Ok(()) if self.classify_bioload(&after) != BioLoadFlag::Violation => {
+                return WorkloadDecision::Allow;
+            }
+            Ok(()) => "the nanosotin envelope".to_string(),
+            Err(e) => e.to_string(),
+        };
+        match self.project(t, w) {
+            Some(counter_offer) => WorkloadDecision::AllowWithConstraints {
+                reason: format!(
+                    "Workload {} would breach {}; allowed scaled to {:.4}",
+                    w.label, breach, counter_offer.scale
+                ),
+                counter_offer,
+            },
+            None => WorkloadDecision::Deny {
+                reason: format!("Workload {} would breach {}", w.label, breach),
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn engine() -> NanoswarmPolicyEngine {
+        // As `ViabilityKernel::compile` builds it: vkernel rows, then Tsafe axes.
+        let mut region = Polytope::compile(["roh_global + 0.5 * psych_risk_dw <= 0.35"]).unwrap();
+        for (axis, min, max) in [
+            ("roh_global", 0.0, 0.3),
+            ("host_energy_d", 0.0, 1.0),
+            ("psych_risk_dw", 0.0, 0.5),
+            ("lifeforce_index", 0.2, 1.0),
+        ] {
+            region.push_axis_bounds(axis, min, max);
+        }
+        let env = NanosotinEnvelope {
+            roh_ceiling: 0.3,
+            max_d: 1.0,
+            max_dw: 0.5,
+            min_lifeforce: 0.2,
+            max_thermal_distance: 0.8,
+            min_molecular_balance: 0.3,
+        };
+        NanoswarmPolicyEngine::new(env, region).unwrap()
+    }
+
+    fn telem() -> BioTelem {
+        BioTelem {
+            knowledge_factor_k: 0.5,
+            host_energy_d: 0.4,
+            psych_risk_dw: 0.2,
+            roh_estimate: 0.1,
+            lifeforce_index: 0.6,
+            thermal_distance_index: 0.3,
+            molecular_balance_index: 0.7,
+        }
+    }
+
+    fn workload() -> SwarmWorkload {
+        SwarmWorkload {
+            label: "sweep".into(),
+            host_budget_cost: 0.1,
+            delta_roh: 0.2,
+            delta_psych_risk: 0.2,
+            delta_lifeforce: -0.1,
+            delta_thermal: 0.0,
+            delta_molecular_balance: 0.0,
+        }
+    }
+
+    #[test]
+    fn project_stops_at_the_coupled_vkernel_row() {
+        // Every axis has room on its own; the roh/psych coupling has 0.15
+        // of slack and the workload moves it by 0.3.
+        let offer = engine().project(&telem(), &workload()).unwrap();
+        assert_eq!(offer.scale, 0.5);
+        assert!((offer.workload.delta_roh - 0.1).abs() < 1e-6);
+
+        let outside = BioTelem {
+            roh_estimate: 0.32,
+            ..telem()
+        };
+        assert!(engine().project(&outside, &workload()).is_none());
+    }
+
+    #[test]
+    fn evaluate_workload_allows_only_the_counter_offer() {
+        let engine = engine();
+        let offer = match engine.evaluate_workload(&telem(), &workload()) {
+            WorkloadDecision::AllowWithConstraints { counter_offer, .. } => counter_offer,
+            other => panic!("expected the counter-offer, got {:?}", other),
+        };
+        assert!(matches!(
+            engine.evaluate_workload(&telem(), &offer.workload),
+            WorkloadDecision::Allow
+        ));
+
+        // Only the envelope bounds thermal: 0.3 + 0.6 * s <= 0.8.
+        let hot = SwarmWorkload {
+            delta_thermal: 0.6,
+            ..offer.workload
+        };
+        let cooler = match engine.evaluate_workload(&telem(), &hot) {
+            WorkloadDecision::AllowWithConstraints { counter_offer, .. } => counter_offer,
+            other => panic!("expected the counter-offer, got {:?}", other),
+        };
+        assert!((cooler.scale - 0.8333).abs() < 1e-4, "{}", cooler.scale);
+
+        let drained = BioTelem {
+            thermal_distance_index: 0.81,
+            ..telem()
+        };
+        assert!(matches!(
+            engine.evaluate_workload(&drained, &hot),
+            WorkloadDecision::Deny { .. }
+        ));
+    }
+
+    #[test]
+    fn envelope_above_the_roh_ceiling_is_refused() {
+        let env = NanosotinEnvelope {
+            roh_ceiling: 0.35,
+            max_d: 1.0,
+            max_dw: 0.5,
+            min_lifeforce: 0.2,
+            max_thermal_distance: 0.8,
+            min_molecular_balance: 0.3,
+        };
+        assert_eq!(
+            NanoswarmPolicyEngine::new(env, Polytope::default()).err(),
+            Some(EnvelopeError::RohCeiling(0.35))
+        );
+    }
 }
@@ -34,3 +34,89 @@ pub struct NanosotinEnvelope {
     pub max_thermal_distance: f32, // keep below overheating
     pub min_molecular_balance: f32,// biochemical stability floor
 }
+
+/// A requested swarm workload, as deltas on the current telemetry.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SwarmWorkload {
+    pub label: String,
+    pub host_budget_cost: f32,        // added to host_energy_d
+    pub delta_roh: f32,
+    pub delta_psych_risk: f32,
+    pub delta_lifeforce: f32,         // negative drains lifeforce
+    pub delta_thermal: f32,
+    pub delta_molecular_balance: f32,
+}
+
+impl SwarmWorkload {
+    /// Same workload with every delta multiplied by `scale`.
+    pub fn scaled(&self, scale: f32) -> Self {
+        Self {
+            label: self.label.clone(),
+            host_budget_cost: self.host_budget_cost * scale,
+            delta_roh: self.delta_roh * scale,
+            delta_psych_risk: self.delta_psych_risk * scale,
+            delta_lifeforce: self.delta_lifeforce * scale,
+            delta_thermal: self.delta_thermal * scale,
+            delta_molecular_balance: self.delta_molecular_balance * scale,
+        }
+    }
+}
+
+impl SwarmWorkload {
+    /// Deltas keyed by the [`BioTelem::axes`] they move.
+    pub fn deltas(&self) -> [(&'static str, f32); 6] {
+        [
+            ("roh_global", self.delta_roh),
+            ("host_energy_d", self.host_budget_cost),
+            ("psych_risk_dw", self.delta_psych_risk),
+            ("lifeforce_index", self.delta_lifeforce),
+            ("thermal_distance_index", self.delta_thermal),
+            ("molecular_balance_index", self.delta_molecular_balance),
+        ]
+    }
+}
+
+impl BioTelem {
+    /// Readings keyed by the `.tsafe.aln` / `.vkernel.aln` axis names;
+    /// `roh_estimate` is the `roh_global` axis.
+    pub fn axes(&self) -> [(&'static str, f32); 6] {
+        [
+            ("roh_global", self.roh_estimate),
+            ("host_energy_d", self.host_energy_d),
+            ("psych_risk_dw", self.psych_risk_dw),
+            ("lifeforce_index", self.lifeforce_index),
+            ("thermal_distance_index", self.thermal_distance_index),
+            ("molecular_balance_index", self.molecular_balance_index),
+        ]
+    }
+
+    /// Telemetry after `w` has run.
+    pub fn after(&self, w: &SwarmWorkload) -> Self {
+        Self {
+            knowledge_factor_k: self.knowledge_factor_k,
+            host_energy_d: self.host_energy_d + w.host_budget_cost,
+            psych_risk_dw: self.psych_risk_dw + w.delta_psych_risk,
+            roh_estimate: self.roh_estimate + w.delta_roh,
+            lifeforce_index: self.lifeforce_index + w.delta_lifeforce,
+            thermal_distance_index: self.thermal_distance_index + w.delta_thermal,
+            molecular_balance_index: self.molecular_balance_index + w.delta_molecular_balance,
+        }
+    }
+}
+
+/// The largest fraction of a workload that stays inside the envelope.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CounterOffer {
+    pub scale: f32,                // in (0, 1)
+    pub workload: SwarmWorkload,   // the request's deltas times `scale`
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum WorkloadDecision {
+    Allow,
+    /// The workload as requested would breach; only `counter_offer`, its
+    /// largest scaled-down form that fits, may run.
+    AllowWithConstraints { reason: String, counter_offer: CounterOffer },
+    /// The workload is refused and no scaled-down form of it fits.
+    Deny { reason: String },
+}
@@ -0,0 +1,40 @@
+use k256::ecdsa::SigningKey;
+use policy_bundle::{BundleManifest, StakeAnchor, VerifiedBundle};
+
+const USAGE: &str = "usage:
+  policy-bundle pack   <dir> <subject_id> <sequence>   write an unsigned manifest
+  policy-bundle sign   <dir> <key_file>     append a co-signature (key file: 32-byte hex)
+  policy-bundle verify <dir> <anchor.aln>   check digests and stake quorum";
+
+fn main() -> anyhow::Result<()> {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let (cmd, dir, arg, sequence) = match args.as_slice() {
+        [cmd, dir, arg] => (cmd, dir, arg, None),
+        [cmd, dir, arg, sequence] if cmd == "pack" => (cmd, dir, arg, Some(sequence)),
+        _ => anyhow::bail!("{}", USAGE),
+    };
+    match cmd.as_str() {
+        "pack" => {
+            let Some(sequence) = sequence else {
+                anyhow::bail!("{}", USAGE);
+            };
+            let manifest = BundleManifest::pack(dir, arg, sequence.parse()?)?;
+            manifest.write(dir)?;
+            println!("{}", manifest.manifest_hash);
+        }
+        "sign" => {
+            let secret = hex::decode(std::fs::read_to_string(arg)?.trim())?;
+            let key = SigningKey::from_slice(&secret)?;
+            let mut manifest = BundleManifest::read(dir)?;
+            manifest.sign(&key);
+            manifest.write(dir)?;
+            println!("{} signature(s)", manifest.signatures.len());
+        }
+        "verify" => {
+            let bundle = VerifiedBundle::open(dir, &StakeAnchor::load(arg)?)?;
+            println!("{}", bundle.manifest_hash());
+        }
+        _ => anyhow::bail!("{}", USAGE),
+    }
+    Ok(())
+}
@@ -0,0 +1,494 @@
+//! Signed SOVEREIGNCONFIG bundles.
+//!
+//! A bundle is a shard directory plus `policy.bundle.json`, which pins the
+//! SHA-256 of every SOVEREIGNCONFIG shard under one canonical manifest hash
+//! and carries stake-quorum secp256k1 signatures over that hash. Loaders
+//! take shard bytes from a [`VerifiedBundle`] only, so an edit that bypasses
+//! the VFS (and `SovereignKernelLock`) is caught before anything is parsed.
+//!
+//! Signatures are checked against a [`StakeAnchor`] that lives outside the
+//! bundle; the `stake.aln` inside the bundle cannot vouch for itself.
+
+pub mod recoverable;
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs;
+use std::path::Path;
+
+use k256::ecdsa::{SigningKey, VerifyingKey};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use thiserror::Error;
+
+/// Manifest file name inside the bundle directory.
+pub const MANIFEST_FILE: &str = "policy.bundle.json";
+
+/// SOVEREIGNCONFIG shard names a bundle may carry. Any of these present in
+/// the directory must be listed in the manifest.
+pub const SOVEREIGN_SHARDS: [&str; 7] = [
+    "rohmodel.aln",
+    "tsafe.aln",
+    "vkernel.aln",
+    "stake.aln",
+    "neurorights.json",
+    "smart.json",
+    "evolve-token.json",
+];
+
+const DOMAIN: &str = "rust-party/policy-bundle/v1";
+
+#[derive(Debug, Error)]
+pub enum BundleError {
+    #[error("bundle is unsigned: {0}")]
+    Unsigned(String),
+    #[error("bundle manifest is malformed: {0}")]
+    Malformed(String),
+    #[error("bundle is for subject {bundle}, anchor is for {anchor}")]
+    SubjectMismatch { bundle: String, anchor: String },
+    #[error("manifest hash {stored} does not match its shard list ({computed})")]
+    ManifestMismatch { stored: String, computed: String },
+    #[error("shard {0} is not a SOVEREIGNCONFIG shard")]
+    NotSovereign(String),
+    #[error("shard {0} is present but not listed in the bundle manifest")]
+    Unlisted(String),
+    #[error("shard {0} is listed in the bundle manifest but missing")]
+    MissingShard(String),
+    #[error("shard {shard} has digest {actual}, manifest pins {pinned}")]
+    ShardMismatch {
+        shard: String,
+        pinned: String,
+        actual: String,
+    },
+    #[error("signature {index} is malformed: {message}")]
+    MalformedSignature { index: usize, message: String },
+    #[error("signature {index} is from {pubkey}, which is not in the stake anchor")]
+    UnknownSigner { index: usize, pubkey: String },
+    #[error("{valid} distinct stake signatures, threshold is {threshold}")]
+    BelowThreshold { valid: usize, threshold: u32 },
+    #[error("bundle sequence {found} does not advance past {current}")]
+    Rollback { current: u64, found: u64 },
+    #[error("{path}: {message}")]
+    Io { path: String, message: String },
+}
+
+fn io_err(path: &Path, e: impl std::fmt::Display) -> BundleError {
+    BundleError::Io {
+        path: path.display().to_string(),
+        message: e.to_string(),
+    }
+}
+
+pub fn digest_hex(bytes: &[u8]) -> String {
+    hex::encode(Sha256::digest(bytes))
+}
+
+/// `policy.bundle.json`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BundleManifest {
+    pub subject_id: String,
+    /// Signed, strictly increasing bundle number. A node never moves to a
+    /// lower one, so an older signed bundle cannot be replayed.
+    pub sequence: u64,
+    /// Shard name -> SHA-256 hex of its bytes.
+    pub shards: BTreeMap<String, String>,
+    /// See [`BundleManifest::canonical_hash`].
+    pub manifest_hash: String,
+    /// Hex `r || s || recovery_id` over the manifest hash, one per signer.
+    #[serde(default)]
+    pub signatures: Vec<String>,
+}
+
+impl BundleManifest {
+    /// SHA-256 over the canonical JSON of the domain tag, subject, sequence
+    /// and shard digests. Independent of how `policy.bundle.json` is laid out.
+    pub fn canonical_hash(
+        subject_id: &str,
+        sequence: u64,
+        shards: &BTreeMap<String, String>,
+    ) -> String {
+        #[derive(Serialize)]
+        struct Preimage<'a> {
+            domain: &'static str,
+            subject_id: &'a str,
+            sequence: u64,
+            shards: &'a BTreeMap<String, String>,
+        }
+        let pre = Preimage {
+            domain: DOMAIN,
+            subject_id,
+            sequence,
+            shards,
+        };
+        digest_hex(
+            aln_format::to_canonical_json(&pre)
+                .expect("preimage serializes")
+                .as_bytes(),
+        )
+    }
+
+    /// Unsigned manifest over every SOVEREIGNCONFIG shard present in `dir`.
+    pub fn pack<P: AsRef<Path>>(
+        dir: P,
+        subject_id: &str,
+        sequence: u64,
+    ) -> Result<Self, BundleError> {
+        let dir = dir.as_ref();
+        let mut shards = BTreeMap::new();
+        for name in SOVEREIGN_SHARDS {
+            let path = dir.join(name);
+            if path.exists() {
+                let bytes = fs::read(&path).map_err(|e| io_err(&path, e))?;
+                shards.insert(name.to_string(), digest_hex(&bytes));
+            }
+        }
+        Ok(Self {
+            manifest_hash: Self::canonical_hash(subject_id, sequence, &shards),
+            subject_id: subject_id.to_string(),
+            sequence,
+            shards,
+            signatures: Vec::new(),
+        })
+    }
+
+    /// Append one stake signer's co-signature.
+    pub fn sign(&mut self, key: &SigningKey) {
+        self.signatures
+            .push(recoverable::sign(self.manifest_hash.as_bytes(), key));
+    }
+
+    pub fn read<P: AsRef<Path>>(dir: P) -> Result<Self, BundleError> {
+        let path = dir.as_ref().join(MANIFEST_FILE);
+        let text = fs::read_to_string(&path)
+            .map_err(|e| BundleError::Unsigned(format!("{}: {}", path.display(), e)))?;
+        serde_json::from_str(&text).map_err(|e| BundleError::Malformed(e.to_string()))
+    }
+
+    pub fn write<P: AsRef<Path>>(&self, dir: P) -> Result<(), BundleError> {
+        let path = dir.as_ref().join(MANIFEST_FILE);
+        let text = serde_json::to_string_pretty(self).expect("manifest serializes");
+        fs::write(&path, text + "\n").map_err(|e| io_err(&path, e))
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AnchorSigner {
+    pub label: String,
+    /// SEC1-compressed secp256k1 public key, hex (33 bytes).
+    pub pubkey: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AnchorMultisig {
+    pub threshold: u32,
+    pub signers: Vec<AnchorSigner>,
+}
+
+/// Trust root for bundle signatures, in the same ALN shape as `.stake.aln`.
+/// Kept outside the bundle directory (e.g. `shards/keys/stake.anchor.aln`).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename = "StakeShard")]
+pub struct StakeAnchor {
+    pub subject_id: String,
+    pub multisig: AnchorMultisig,
+}
+
+impl StakeAnchor {
+    pub fn load<P: AsRef<Path>>(path: P) -> anyhow::Result<Self> {
+        let anchor: StakeAnchor = aln_format::from_path(path)?;
+        anyhow::ensure!(
+            anchor.multisig.threshold >= 1
+                && anchor.multisig.threshold as usize <= anchor.multisig.signers.len(),
+            "stake anchor threshold {} is not satisfiable by {} signers",
+            anchor.multisig.threshold,
+            anchor.multisig.signers.len()
+        );
+        Ok(anchor)
+    }
+
+    fn keys(&self) -> Result<Vec<VerifyingKey>, BundleError> {
+        self.multisig
+            .signers
+            .iter()
+            .map(|s| {
+                hex::decode(&s.pubkey)
+                    .ok()
+                    .and_then(|b| VerifyingKey::from_sec1_bytes(&b).ok())
+                    .ok_or_else(|| {
+                        BundleError::Malformed(format!("stake anchor signer {}", s.label))
+                    })
+            })
+            .collect()
+    }
+
+    /// Distinct anchor signers over `msg` must reach the threshold.
+    fn check_quorum(&self, msg: &[u8], signatures: &[String]) -> Result<(), BundleError> {
+        let keys = self.keys()?;
+        let mut approved = BTreeSet::new();
+        for (index, sig) in signatures.iter().enumerate() {
+            let key = recover_signer(msg, index, sig)?;
+            let i =
+                keys.iter()
+                    .position(|k| *k == key)
+                    .ok_or_else(|| BundleError::UnknownSigner {
+                        index,
+                        pubkey: recoverable::pubkey_hex(&key),
+                    })?;
+            approved.insert(i);
+        }
+        if approved.len() < self.multisig.threshold as usize {
+            return Err(BundleError::BelowThreshold {
+                valid: approved.len(),
+                threshold: self.multisig.threshold,
+            });
+        }
+        Ok(())
+    }
+}
+
+fn recover_signer(msg: &[u8], index: usize, sig_hex: &str) -> Result<VerifyingKey, BundleError> {
+    recoverable::recover(msg, sig_hex)
+        .map_err(|message| BundleError::MalformedSignature { index, message })
+}
+
+/// Shard bytes whose digests, manifest hash and quorum signatures all
+/// checked out. Loaders parse from here, never from the directory again.
+#[derive(Debug, Clone)]
+pub struct VerifiedBundle {
+    subject_id: String,
+    sequence: u64,
+    manifest_hash: String,
+    shards: BTreeMap<String, Vec<u8>>,
+}
+
+impl VerifiedBundle {
+    pub fn open<P: AsRef<Path>>(dir: P, anchor: &StakeAnchor) -> Result<Self, BundleError> {
+        let dir = dir.as_ref();
+        let manifest = BundleManifest::read(dir)?;
+        if manifest.subject_id != anchor.subject_id {
+            return Err(BundleError::SubjectMismatch {
+                bundle: manifest.subject_id,
+                anchor: anchor.subject_id.clone(),
+            });
+        }
+        let computed = BundleManifest::canonical_hash(
+            &manifest.subject_id,
+            manifest.sequence,
+            &manifest.shards,
+        );
+        if computed != manifest.manifest_hash {
+            return Err(BundleError::ManifestMismatch {
+                stored: manifest.manifest_hash,
+                computed,
+            });
+        }
+        if manifest.signatures.is_empty() {
+            return Err(BundleError::Unsigned(
+                "manifest carries no signatures".into(),
+            ));
+        }
+        anchor.check_quorum(manifest.manifest_hash.as_bytes(), &manifest.signatures)?;
+
+        for name in SOVEREIGN_SHARDS {
+            if dir.join(name).exists() && !manifest.shards.contains_key(name) {
+                return Err(BundleError::Unlisted(name.to_string()));
+            }
+        }
+        let mut shards = BTreeMap::new();
+        for (name, pinned) in &manifest.shards {
+            if !SOVEREIGN_SHARDS.contains(&name.as_str()) {
+                return Err(BundleError::NotSovereign(name.clone()));
+            }
+            let path = dir.join(name);
+            let bytes = fs::read(&path).map_err(|_| BundleError::MissingShard(name.clone()))?;
+            let actual = digest_hex(&bytes);
+            if actual != *pinned {
+                return Err(BundleError::ShardMismatch {
+                    shard: name.clone(),
+                    pinned: pinned.clone(),
+                    actual,
+                });
+            }
+            shards.insert(name.clone(), bytes);
+        }
+        Ok(Self {
+            subject_id: manifest.subject_id,
+            sequence: manifest.sequence,
+            manifest_hash: manifest.manifest_hash,
+            shards,
+        })
+    }
+
+    pub fn subject_id(&self) -> &str {
+        &self.subject_id
+    }
+
+    pub fn sequence(&self) -> u64 {
+        self.sequence
+    }
+
+    /// Refuse to replace the bundle at `sequence` / `manifest_hash` with this
+    /// one unless it is the same bundle or a later one.
+    pub fn ensure_not_older(&self, sequence: u64, manifest_hash: &str) -> Result<(), BundleError> {
+        let same = self.manifest_hash == manifest_hash;
+        if self.sequence < sequence || (self.sequence == sequence && !same) {
+            return Err(BundleError::Rollback {
+                current: sequence,
+                found: self.sequence,
+            });
+        }
+        Ok(())
+    }
+
+    pub fn manifest_hash(&self) -> &str {
+        &self.manifest_hash
+    }
+
+    pub fn bytes(&self, shard: &str) -> Result<&[u8], BundleError> {
+        self.shards
+            .get(shard)
+            .map(Vec::as_slice)
+            .ok_or_else(|| BundleError::MissingShard(shard.to_string()))
+    }
+
+    pub fn text(&self, shard: &str) -> Result<&str, BundleError> {
+        std::str::from_utf8(self.bytes(shard)?)
+            .map_err(|e| BundleError::Malformed(format!("{}: {}", shard, e)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SUBJECT: &str = "bostrom18sd2ujv24ual9c9pshtxys6j8knh6xaead9ye7";
+
+    fn key(byte: u8) -> SigningKey {
+        SigningKey::from_slice(&[byte; 32]).unwrap()
+    }
+
+    fn anchor(threshold: u32) -> StakeAnchor {
+        StakeAnchor {
+            subject_id: SUBJECT.into(),
+            multisig: AnchorMultisig {
+                threshold,
+                signers: (1..=3)
+                    .map(|b| AnchorSigner {
+                        label: format!("signer-{}", b),
+                        pubkey: hex::encode(
+                            key(b).verifying_key().to_encoded_point(true).as_bytes(),
+                        ),
+                    })
+                    .collect(),
+            },
+        }
+    }
+
+    fn bundle(signers: &[u8]) -> tempfile::TempDir {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(dir.path().join("rohmodel.aln"), "RohModel { ceiling: 0.3 }").unwrap();
+        fs::write(dir.path().join("neurorights.json"), "{}").unwrap();
+        fs::write(dir.path().join("ecoenv.json"), "{}").unwrap();
+        let mut manifest = BundleManifest::pack(dir.path(), SUBJECT, 1).unwrap();
+        for b in signers {
+            manifest.sign(&key(*b));
+        }
+        manifest.write(dir.path()).unwrap();
+        dir
+    }
+
+    #[test]
+    fn quorum_signed_bundle_opens() {
+        let dir = bundle(&[1, 3]);
+        let verified = VerifiedBundle::open(dir.path(), &anchor(2)).unwrap();
+        assert_eq!(
+            verified.text("rohmodel.aln").unwrap(),
+            "RohModel { ceiling: 0.3 }"
+        );
+        // Non-sovereign files are not part of the bundle.
+        assert!(verified.bytes("ecoenv.json").is_err());
+    }
+
+    #[test]
+    fn unsigned_short_or_tampered_bundles_are_refused() {
+        let dir = bundle(&[]);
+        assert!(matches!(
+            VerifiedBundle::open(dir.path(), &anchor(2)),
+            Err(BundleError::Unsigned(_))
+        ));
+        let dir = bundle(&[1, 1]);
+        assert!(matches!(
+            VerifiedBundle::open(dir.path(), &anchor(2)),
+            Err(BundleError::BelowThreshold { valid: 1, .. })
+        ));
+        let dir = bundle(&[1, 9]);
+        assert!(matches!(
+            VerifiedBundle::open(dir.path(), &anchor(1)),
+            Err(BundleError::UnknownSigner { index: 1, .. })
+        ));
+
+        let dir = bundle(&[1, 2]);
+        fs::write(dir.path().join("rohmodel.aln"), "RohModel { ceiling: 0.9 }").unwrap();
+        assert!(matches!(
+            VerifiedBundle::open(dir.path(), &anchor(2)),
+            Err(BundleError::ShardMismatch { .. })
+        ));
+
+        let dir = bundle(&[1, 2]);
+        fs::write(dir.path().join("stake.aln"), "StakeShard {}").unwrap();
+        assert!(matches!(
+            VerifiedBundle::open(dir.path(), &anchor(2)),
+            Err(BundleError::Unlisted(s)) if s == "stake.aln"
+        ));
+
+        // Re-pinning a shard without re-signing breaks the manifest hash or
+        // the signatures, whichever the editor forgot.
+        let dir = bundle(&[1, 2]);
+        let mut manifest = BundleManifest::read(dir.path()).unwrap();
+        manifest
+            .shards
+            .insert("rohmodel.aln".into(), digest_hex(b"x"));
+        manifest.write(dir.path()).unwrap();
+        assert!(matches!(
+            VerifiedBundle::open(dir.path(), &anchor(2)),
+            Err(BundleError::ManifestMismatch { .. })
+        ));
+        manifest.manifest_hash =
+            BundleManifest::canonical_hash(SUBJECT, manifest.sequence, &manifest.shards);
+        manifest.write(dir.path()).unwrap();
+        assert!(matches!(
+            VerifiedBundle::open(dir.path(), &anchor(2)),
+            Err(BundleError::UnknownSigner { .. })
+        ));
+    }
+
+    #[test]
+    fn older_or_resequenced_bundles_do_not_replace_newer_ones() {
+        let old = bundle(&[1, 2]);
+        let old = VerifiedBundle::open(old.path(), &anchor(2)).unwrap();
+        let dir = bundle(&[]);
+        fs::write(
+            dir.path().join("rohmodel.aln"),
+            "RohModel { ceiling: 0.3 }\n",
+        )
+        .unwrap();
+        let mut manifest = BundleManifest::pack(dir.path(), SUBJECT, 2).unwrap();
+        manifest.sign(&key(1));
+        manifest.sign(&key(2));
+        manifest.write(dir.path()).unwrap();
+        let new = VerifiedBundle::open(dir.path(), &anchor(2)).unwrap();
+
+        new.ensure_not_older(old.sequence(), old.manifest_hash())
+            .unwrap();
+        new.ensure_not_older(new.sequence(), new.manifest_hash())
+            .unwrap();
+        assert!(matches!(
+            old.ensure_not_older(new.sequence(), new.manifest_hash()),
+            Err(BundleError::Rollback {
+                current: 2,
+                found: 1
+            })
+        ));
+        // Same number, different content: one of them was never the head.
+        assert!(old.ensure_not_older(1, new.manifest_hash()).is_err());
+    }
+}
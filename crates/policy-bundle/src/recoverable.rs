@@ -0,0 +1,61 @@
+//! Recoverable secp256k1 signatures as used by bundle manifests, EVOLVE
+//! tokens and proposal transitions: hex of `r || s || recovery_id`
+//! (65 bytes), low-S only.
+
+use k256::ecdsa::{RecoveryId, Signature, SigningKey, VerifyingKey};
+
+/// Sign `msg` (SHA-256 prehashed by k256) and encode the recoverable form.
+pub fn sign(msg: &[u8], key: &SigningKey) -> String {
+    let (sig, recid) = key
+        .sign_recoverable(msg)
+        .expect("secp256k1 signing does not fail for valid keys");
+    let mut out = sig.to_bytes().to_vec();
+    out.push(recid.to_byte());
+    hex::encode(out)
+}
+
+/// The key behind `sig_hex` over `msg`. The error says what is malformed.
+pub fn recover(msg: &[u8], sig_hex: &str) -> Result<VerifyingKey, String> {
+    let raw = hex::decode(sig_hex).map_err(|e| e.to_string())?;
+    if raw.len() != 65 {
+        return Err(format!("expected 65 bytes, got {}", raw.len()));
+    }
+    let sig = Signature::from_slice(&raw[..64]).map_err(|e| e.to_string())?;
+    // Reject the high-S twin so each signature has exactly one encoding.
+    if sig.normalize_s().is_some() {
+        return Err("non-canonical high-S signature".into());
+    }
+    let recid = RecoveryId::from_byte(raw[64]).ok_or_else(|| "bad recovery id".to_string())?;
+    VerifyingKey::recover_from_msg(msg, &sig, recid).map_err(|e| e.to_string())
+}
+
+/// SEC1-compressed hex of `key`, the form stake shards list signers in.
+pub fn pubkey_hex(key: &VerifyingKey) -> String {
+    hex::encode(key.to_encoded_point(true).as_bytes())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trips_and_rejects_high_s_and_garbage() {
+        let key = SigningKey::from_slice(&[5; 32]).unwrap();
+        let sig = sign(b"msg", &key);
+        assert_eq!(recover(b"msg", &sig).unwrap(), *key.verifying_key());
+
+        // Flip s to n - s: the same signature in its high-S encoding.
+        let raw = hex::decode(&sig).unwrap();
+        let low = Signature::from_slice(&raw[..64]).unwrap();
+        let (r, s) = low.split_scalars();
+        let high = Signature::from_scalars(r, -s).unwrap();
+        let mut twin = high.to_bytes().to_vec();
+        twin.push(raw[64]);
+        assert!(recover(b"msg", &hex::encode(twin))
+            .unwrap_err()
+            .contains("high-S"));
+
+        assert!(recover(b"msg", "00").is_err());
+        assert!(recover(b"msg", "zz").is_err());
+    }
+}
@@ -0,0 +1,22 @@
+use std::path::PathBuf;
+
+use policy_lint::{lint_dir, Severity};
+
+/// `policy-lint [BUNDLE_DIR]`: one JSON diagnostic per line on stdout, a
+/// readable copy on stderr, exit status 1 if any diagnostic is an error.
+fn main() -> anyhow::Result<()> {
+    let dir = std::env::args_os()
+        .nth(1)
+        .map(PathBuf::from)
+        .unwrap_or_else(|| PathBuf::from("shards/root"));
+
+    let diagnostics = lint_dir(&dir);
+    for d in &diagnostics {
+        println!("{}", serde_json::to_string(d)?);
+        eprintln!("{}", d);
+    }
+    if diagnostics.iter().any(|d| d.severity == Severity::Error) {
+        std::process::exit(1);
+    }
+    Ok(())
+}
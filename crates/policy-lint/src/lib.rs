@@ -0,0 +1,562 @@
+//! Cross-file lint for a `shards/root` policy bundle.
+//!
+//! Each shard is valid on its own; the linter checks that they agree with
+//! each other: one RoH ceiling across `.rohmodel.aln`, the `roh_global` Tsafe
+//! axis, the `.vkernel.aln` constraints, the nanoswarm envelope and the value
+//! compiled into the routers, no Tsafe axis wider than its viability
+//! constraint, no eco envelope looser than the Tsafe axis it feeds, and a
+//! RoH model that weights at least one axis.
+//!
+//! Diagnostics are plain data and serialize one per line as JSON.
+
+use std::collections::BTreeMap;
+use std::fmt;
+use std::fs;
+use std::path::Path;
+
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use viability_kernel::{Polytope, TOLERANCE};
+
+/// RoH ceiling hardcoded in `TsafeRoutingEnvelope::default`, the `RoHSlice`
+/// builders and `sovereign_specs::evolve_ledger::validate_invariants`, which
+/// checks it on every `sovereign_core::EvolutionProposal`.
+pub const COMPILED_ROH_CEILING: f64 = 0.3;
+
+pub const ROH_AXIS: &str = "roh_global";
+pub const LIFEFORCE_AXIS: &str = "lifeforce_load";
+
+pub const ROHMODEL: &str = "rohmodel.aln";
+pub const TSAFE: &str = "tsafe.aln";
+pub const VKERNEL: &str = "vkernel.aln";
+pub const ECOENV: &str = "ecoenv.json";
+pub const NANOSWARM: &str = "nanoswarm.aln";
+
+/// Every shard the lint reads, relative to the bundle dir.
+pub const LINTED_FILES: [&str; 5] = [ROHMODEL, TSAFE, VKERNEL, ECOENV, NANOSWARM];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Severity {
+    Warning,
+    Error,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct Diagnostic {
+    pub severity: Severity,
+    pub code: &'static str,
+    /// Shard the diagnostic is anchored to, relative to the bundle dir.
+    pub file: &'static str,
+    pub message: String,
+    /// The numbers that disagree, keyed by where they came from.
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub values: BTreeMap<String, f64>,
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let severity = match self.severity {
+            Severity::Warning => "warning",
+            Severity::Error => "error",
+        };
+        write!(
+            f,
+            "{}[{}] {}: {}",
+            severity, self.code, self.file, self.message
+        )
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct RohModel {
+    ceiling: f64,
+    #[serde(default)]
+    weights: BTreeMap<String, f64>,
+}
+
+#[derive(Debug, Deserialize)]
+struct TsafeAxis {
+    name: String,
+    min: f64,
+    max: f64,
+}
+
+#[derive(Debug, Deserialize)]
+struct TsafeKernel {
+    axes: Vec<TsafeAxis>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ViabilityConstraint {
+    expr: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct ViabilityKernel {
+    constraints: Vec<ViabilityConstraint>,
+}
+
+/// Per-route eco envelope, as read by the gate's `EcoGuard`.
+#[derive(Debug, Deserialize)]
+struct EcoEnvelope {
+    max_lifeforce_delta: f64,
+}
+
+#[derive(Debug, Deserialize)]
+struct NanosotinEnvelope {
+    roh_ceiling: f64,
+}
+
+/// Tightest single-axis bounds implied by the viability region, with the
+/// constraint each came from.
+#[derive(Debug, Default)]
+struct ImpliedBounds {
+    max: BTreeMap<String, (f64, String)>,
+    min: BTreeMap<String, (f64, String)>,
+}
+
+impl ImpliedBounds {
+    fn of(region: &Polytope) -> Self {
+        let mut bounds = Self::default();
+        for row in region.rows() {
+            let mut terms = row.coeffs.iter().enumerate().filter(|(_, a)| **a != 0.0);
+            let (Some((i, &a)), None) = (terms.next(), terms.next()) else {
+                continue;
+            };
+            let axis = &region.axes()[i];
+            let value = row.bound / a;
+            let (map, tighter): (_, fn(f64, f64) -> bool) = if a > 0.0 {
+                (&mut bounds.max, |new, old| new < old)
+            } else {
+                (&mut bounds.min, |new, old| new > old)
+            };
+            if map.get(axis).is_none_or(|(old, _)| tighter(value, *old)) {
+                map.insert(axis.clone(), (value, row.source.clone()));
+            }
+        }
+        bounds
+    }
+}
+
+struct Lint {
+    diagnostics: Vec<Diagnostic>,
+}
+
+impl Lint {
+    fn emit(
+        &mut self,
+        severity: Severity,
+        code: &'static str,
+        file: &'static str,
+        message: String,
+        values: impl IntoIterator<Item = (String, f64)>,
+    ) {
+        self.diagnostics.push(Diagnostic {
+            severity,
+            code,
+            file,
+            message,
+            values: values.into_iter().collect(),
+        });
+    }
+
+    /// Missing required shards and any unparsable shard are diagnostics, not
+    /// early exits, so one run reports everything.
+    fn load<T: DeserializeOwned>(
+        &mut self,
+        read: &dyn Fn(&str) -> Option<anyhow::Result<Vec<u8>>>,
+        file: &'static str,
+        required: bool,
+    ) -> Option<T> {
+        let Some(bytes) = read(file) else {
+            if required {
+                self.emit(
+                    Severity::Error,
+                    "SHARD_MISSING",
+                    file,
+                    "required shard is missing".into(),
+                    [],
+                );
+            }
+            return None;
+        };
+        let parsed = bytes.and_then(|bytes| {
+            let text = String::from_utf8(bytes)?;
+            if file.ends_with(".json") {
+                Ok(serde_json::from_str(&text)?)
+            } else {
+                Ok(aln_format::from_str(&text)?)
+            }
+        });
+        match parsed {
+            Ok(value) => Some(value),
+            Err(e) => {
+                self.emit(
+                    Severity::Error,
+                    "SHARD_UNREADABLE",
+                    file,
+                    format!("{:#}", e),
+                    [],
+                );
+                None
+            }
+        }
+    }
+}
+
+/// Lint the bundle in `dir`. Diagnostics come back errors first.
+pub fn lint_dir(dir: &Path) -> Vec<Diagnostic> {
+    lint(&|file| match fs::read(dir.join(file)) {
+        Ok(bytes) => Some(Ok(bytes)),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => None,
+        Err(e) => Some(Err(e.into())),
+    })
+}
+
+/// Lint shard bytes already in hand, keyed by file name; anything not in
+/// `files` is missing. Callers that also compile the bundle lint the same
+/// bytes they compile, so nothing can change on disk in between.
+pub fn lint_files(files: &BTreeMap<&str, Vec<u8>>) -> Vec<Diagnostic> {
+    lint(&|file| files.get(file).map(|bytes| Ok(bytes.clone())))
+}
+
+fn lint(read: &dyn Fn(&str) -> Option<anyhow::Result<Vec<u8>>>) -> Vec<Diagnostic> {
+    let mut lint = Lint {
+        diagnostics: Vec::new(),
+    };
+    let roh: Option<RohModel> = lint.load(read, ROHMODEL, true);
+    let tsafe: Option<TsafeKernel> = lint.load(read, TSAFE, true);
+    let vkernel: Option<ViabilityKernel> = lint.load(read, VKERNEL, true);
+    let eco: Option<BTreeMap<String, EcoEnvelope>> = lint.load(read, ECOENV, false);
+    let nanoswarm: Option<NanosotinEnvelope> = lint.load(read, NANOSWARM, false);
+
+    let region = vkernel.and_then(|vk| {
+        match Polytope::compile(vk.constraints.iter().map(|c| c.expr.as_str())) {
+            Ok(region) => Some(region),
+            Err(e) => {
+                lint.emit(
+                    Severity::Error,
+                    "VKERNEL_INVALID",
+                    VKERNEL,
+                    e.to_string(),
+                    [],
+                );
+                None
+            }
+        }
+    });
+    let implied = region.as_ref().map(ImpliedBounds::of);
+
+    check_roh_ceiling(
+        &mut lint,
+        roh.as_ref(),
+        tsafe.as_ref(),
+        implied.as_ref(),
+        nanoswarm.as_ref(),
+    );
+    if let Some(tsafe) = &tsafe {
+        check_axes(&mut lint, tsafe, region.as_ref(), implied.as_ref());
+        if let Some(eco) = &eco {
+            check_eco(&mut lint, tsafe, eco);
+        }
+    }
+
+    lint.diagnostics
+        .sort_by_key(|d| std::cmp::Reverse(d.severity));
+    lint.diagnostics
+}
+
+fn check_roh_ceiling(
+    lint: &mut Lint,
+    roh: Option<&RohModel>,
+    tsafe: Option<&TsafeKernel>,
+    implied: Option<&ImpliedBounds>,
+    nanoswarm: Option<&NanosotinEnvelope>,
+) {
+    let mut sources = vec![("compiled-in".to_string(), COMPILED_ROH_CEILING)];
+    if let Some(roh) = roh {
+        sources.push((format!("{} ceiling", ROHMODEL), roh.ceiling));
+        if !roh.weights.values().any(|w| w.is_finite() && *w > 0.0) {
+            lint.emit(
+                Severity::Error,
+                "ROH_WEIGHTS_MISSING",
+                ROHMODEL,
+                "no positive axis weight; every RoH estimate would be 0".into(),
+                [],
+            );
+        }
+    }
+    if let Some(tsafe) = tsafe {
+        match tsafe.axes.iter().find(|a| a.name == ROH_AXIS) {
+            Some(axis) => sources.push((format!("{} {}.max", TSAFE, ROH_AXIS), axis.max)),
+            None => lint.emit(
+                Severity::Error,
+                "ROH_AXIS_MISSING",
+                TSAFE,
+                format!("no `{}` axis; the RoH ceiling is not enforced", ROH_AXIS),
+                [],
+            ),
+        }
+    }
+    if let Some(implied) = implied {
+        match implied.max.get(ROH_AXIS) {
+            Some((bound, source)) => sources.push((format!("{} `{}`", VKERNEL, source), *bound)),
+            None => lint.emit(
+                Severity::Error,
+                "ROH_AXIS_MISSING",
+                VKERNEL,
+                format!("no constraint bounds `{}` on its own", ROH_AXIS),
+                [],
+            ),
+        }
+    }
+    if let Some(env) = nanoswarm {
+        sources.push((format!("{} roh_ceiling", NANOSWARM), env.roh_ceiling));
+    }
+
+    let lo = sources
+        .iter()
+        .map(|(_, v)| *v)
+        .fold(f64::INFINITY, f64::min);
+    let hi = sources
+        .iter()
+        .map(|(_, v)| *v)
+        .fold(f64::NEG_INFINITY, f64::max);
+    if hi - lo > TOLERANCE {
+        lint.emit(
+            Severity::Error,
+            "ROH_CEILING_MISMATCH",
+            ROHMODEL,
+            format!(
+                "RoH ceiling differs across the bundle ({} to {}); every source must agree",
+                lo, hi
+            ),
+            sources,
+        );
+    }
+}
+
+fn check_axes(
+    lint: &mut Lint,
+    tsafe: &TsafeKernel,
+    region: Option<&Polytope>,
+    implied: Option<&ImpliedBounds>,
+) {
+    for axis in &tsafe.axes {
+        if axis.min > axis.max {
+            lint.emit(
+                Severity::Error,
+                "AXIS_EMPTY",
+                TSAFE,
+                format!(
+                    "axis `{}` has min {} above max {}",
+                    axis.name, axis.min, axis.max
+                ),
+                [("min".into(), axis.min), ("max".into(), axis.max)],
+            );
+        }
+        let Some(implied) = implied else { continue };
+        if let Some((bound, source)) = implied.max.get(&axis.name) {
+            if axis.max > bound + TOLERANCE {
+                lint.emit(
+                    Severity::Error,
+                    "AXIS_ABOVE_VKERNEL",
+                    TSAFE,
+                    format!(
+                        "axis `{}` allows up to {} but `{}` caps it at {}",
+                        axis.name, axis.max, source, bound
+                    ),
+                    [
+                        (format!("{} {}.max", TSAFE, axis.name), axis.max),
+                        (format!("{} `{}`", VKERNEL, source), *bound),
+                    ],
+                );
+            }
+        }
+        if let Some((bound, source)) = implied.min.get(&axis.name) {
+            if axis.min < bound - TOLERANCE {
+                lint.emit(
+                    Severity::Error,
+                    "AXIS_BELOW_VKERNEL",
+                    TSAFE,
+                    format!(
+                        "axis `{}` allows down to {} but `{}` floors it at {}",
+                        axis.name, axis.min, source, bound
+                    ),
+                    [
+                        (format!("{} {}.min", TSAFE, axis.name), axis.min),
+                        (format!("{} `{}`", VKERNEL, source), *bound),
+                    ],
+                );
+            }
+        }
+    }
+
+    let Some(region) = region else { return };
+    for name in region.axes() {
+        if !tsafe.axes.iter().any(|a| &a.name == name) {
+            lint.emit(
+                Severity::Warning,
+                "VKERNEL_UNKNOWN_AXIS",
+                VKERNEL,
+                format!("constraint mentions `{}`, which is not a Tsafe axis", name),
+                [],
+            );
+        }
+    }
+    // The idle point (every axis at its Tsafe min) must be viable, or the
+    // node can never be in a viable state.
+    let mut idle = region.zero_snapshot();
+    for axis in &tsafe.axes {
+        if let Some(v) = idle.get_mut(&axis.name) {
+            *v = axis.min;
+        }
+    }
+    if let Ok(violations) = region.violations(&idle) {
+        for v in violations {
+            lint.emit(
+                Severity::Error,
+                "VKERNEL_IDLE_INFEASIBLE",
+                VKERNEL,
+                format!("idle point (all axes at Tsafe min) violates {}", v),
+                [("lhs".into(), v.lhs), ("bound".into(), v.bound)],
+            );
+        }
+    }
+}
+
+fn check_eco(lint: &mut Lint, tsafe: &TsafeKernel, eco: &BTreeMap<String, EcoEnvelope>) {
+    let Some(axis) = tsafe.axes.iter().find(|a| a.name == LIFEFORCE_AXIS) else {
+        return;
+    };
+    for (route, env) in eco {
+        if env.max_lifeforce_delta > axis.max + TOLERANCE {
+            lint.emit(
+                Severity::Error,
+                "ECO_LOOSER_THAN_TSAFE",
+                ECOENV,
+                format!(
+                    "route {} allows lifeforce delta {} above the Tsafe `{}` max {}",
+                    route, env.max_lifeforce_delta, LIFEFORCE_AXIS, axis.max
+                ),
+                [
+                    (
+                        format!("{} {}.max_lifeforce_delta", ECOENV, route),
+                        env.max_lifeforce_delta,
+                    ),
+                    (format!("{} {}.max", TSAFE, LIFEFORCE_AXIS), axis.max),
+                ],
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TSAFE_SHIPPED: &str = r#"TsafeKernel {
+      axes: [
+        { name: "lifeforce_load", min: 0.0, max: 0.8 },
+        { name: "roh_global",     min: 0.0, max: 0.30 },
+        { name: "qpu_roh",        min: 0.0, max: 0.20 },
+      ],
+      tags: [ "QuantumSovereigntyEnvelope" ]
+    }"#;
+
+    const VKERNEL_SHIPPED: &str = r#"ViabilityKernel {
+      mode: "Normal",
+      constraints: [
+        { expr: "roh_global <= 0.30" },
+        { expr: "qpu_roh <= 0.20" },
+        { expr: "qpu_roh + roh_global <= 0.30" },
+      ]
+    }"#;
+
+    const ROHMODEL_SHIPPED: &str = r#"RohModel { ceiling: 0.3, weights: {
+      lifeforce_load: 0.1, biospec_fatigue: 0.08, qpu_roh: 0.07, route_context_risk: 0.05,
+    } }"#;
+
+    fn bundle(files: &[(&str, &str)]) -> tempfile::TempDir {
+        let dir = tempfile::tempdir().unwrap();
+        let defaults = [
+            (ROHMODEL, ROHMODEL_SHIPPED),
+            (TSAFE, TSAFE_SHIPPED),
+            (VKERNEL, VKERNEL_SHIPPED),
+        ];
+        for (name, text) in defaults.iter().chain(files) {
+            fs::write(dir.path().join(name), text).unwrap();
+        }
+        dir
+    }
+
+    fn codes(dir: &tempfile::TempDir) -> Vec<&'static str> {
+        lint_dir(dir.path()).into_iter().map(|d| d.code).collect()
+    }
+
+    #[test]
+    fn shipped_bundle_is_clean() {
+        let dir = bundle(&[(
+            ECOENV,
+            r#"{"CHAT": {"max_power_watts": 5.0, "max_lifeforce_delta": 0.1}}"#,
+        )]);
+        assert_eq!(codes(&dir), Vec::<&str>::new());
+    }
+
+    #[test]
+    fn ceiling_disagreement_names_every_source() {
+        let dir = bundle(&[(NANOSWARM, "NanosotinEnvelope { roh_ceiling: 0.35 }")]);
+        let diags = lint_dir(dir.path());
+        assert_eq!(diags.len(), 1);
+        assert_eq!(diags[0].code, "ROH_CEILING_MISMATCH");
+        assert_eq!(diags[0].values.len(), 5);
+        assert_eq!(diags[0].values["nanoswarm.aln roh_ceiling"], 0.35);
+    }
+
+    #[test]
+    fn axis_wider_than_vkernel_and_loose_eco_are_flagged() {
+        let tsafe = TSAFE_SHIPPED.replace("max: 0.20", "max: 0.25");
+        let dir = bundle(&[
+            (TSAFE, &tsafe),
+            (
+                ECOENV,
+                r#"{"BCI": {"max_power_watts": 5.0, "max_lifeforce_delta": 0.9}}"#,
+            ),
+        ]);
+        assert_eq!(codes(&dir), ["AXIS_ABOVE_VKERNEL", "ECO_LOOSER_THAN_TSAFE"]);
+        let json = serde_json::to_string(&lint_dir(dir.path())[0]).unwrap();
+        assert!(json.contains(r#""severity":"error""#), "{}", json);
+    }
+
+    #[test]
+    fn missing_and_unknown_are_reported_not_fatal() {
+        let dir = bundle(&[(
+            VKERNEL,
+            r#"ViabilityKernel { mode: "Normal", constraints: [
+            { expr: "roh_global <= 0.30" }, { expr: "dream_load <= 0.1" } ] }"#,
+        )]);
+        fs::remove_file(dir.path().join(ROHMODEL)).unwrap();
+        assert_eq!(codes(&dir), ["SHARD_MISSING", "VKERNEL_UNKNOWN_AXIS"]);
+    }
+
+    #[test]
+    fn rohmodel_without_weights_is_an_error() {
+        let dir = bundle(&[(ROHMODEL, "RohModel { ceiling: 0.3, weights: {} }")]);
+        assert_eq!(codes(&dir), ["ROH_WEIGHTS_MISSING"]);
+        let dir = bundle(&[(ROHMODEL, "RohModel { ceiling: 0.3 }")]);
+        assert_eq!(codes(&dir), ["ROH_WEIGHTS_MISSING"]);
+    }
+
+    #[test]
+    fn in_memory_files_lint_like_the_dir() {
+        let dir = bundle(&[(NANOSWARM, "NanosotinEnvelope { roh_ceiling: 0.35 }")]);
+        let files: BTreeMap<&str, Vec<u8>> = LINTED_FILES
+            .iter()
+            .filter_map(|f| fs::read(dir.path().join(f)).ok().map(|b| (*f, b)))
+            .collect();
+        let from_bytes: Vec<_> = lint_files(&files).into_iter().map(|d| d.code).collect();
+        assert_eq!(from_bytes, codes(&dir));
+        assert_eq!(from_bytes, ["ROH_CEILING_MISMATCH"]);
+    }
+}
@@ -1,45 +1,188 @@
 use tsafe_cortex_gate::{
-    TsafeCortexGate, DonutloopLogger,
+    TsafeCortexGate, TsafeCortexGateConfig, DonutloopLogger,
+    binding::SubjectBinding,
+    budget::EvolveBudgetGuard,
+    consent::ConsentGuard,
     firewall::{MetaFirewall, MetaFirewallConfig},
-    guardians::{RohGuard, NeurorightsGuard, EcoGuard},
+    policy::{EvolveApprovals, EvolveLedgerApprovals, NoApprovals, PolicyReloader, PolicySnapshot},
+    telemetry::NodeTelemetry,
     XRAction, XRActionKind, CapabilityChord, CapabilityKind, Request,
 };
-use uuid::Uuid;
+use bostrom_lcd::{LcdClient, LcdEvolveVerifier};
+use evolve_token_verifier::{
+    BostromClient, ChainEvolveVerifier, EvolveGuard, FailClosed, OfflineEvolveVerifier,
+};
+use policy_bundle::{StakeAnchor, VerifiedBundle};
+use sovereign_evolve::{Governance, QuorumEngine, StakeRoster};
+use std::sync::Arc;
+
+use chord_seal::{ChordIssuer, ChordRef, ChordStore};
+use consent_receipt::{ConsentStore, ConsentVerifier};
 
 fn main() -> anyhow::Result<()> {
-    // Load Tsafe policies from shards/root (NeuroPC local).
-    let roh_guard = RohGuard::from_file("policies/rohmodel.aln")?;
-    let nr_guard = NeurorightsGuard::from_file("policies/neurorights.json")?;
-    let eco_guard = EcoGuard::from_file("policies/ecoenv.json")?;
+    // policies/ is a stake-signed bundle; the anchor naming its signers lives
+    // outside it. A missing anchor, or an unsigned or mismatched bundle
+    // (including a missing rohmodel.aln), leaves the node CHAT-only.
+    let (policy, bundle, governance) = match StakeAnchor::load("shards/keys/stake.anchor.aln") {
+        Ok(anchor) => {
+            let bundle = VerifiedBundle::open("policies", &anchor)
+                .map_err(|e| {
+                    eprintln!("policy: bundle does not verify, failing closed to CHAT only: {}", e)
+                })
+                .ok();
+            // .evolve.jsonl is replayed under the bundle's stake roster: only
+            // its stakeholders may act, and quorums are re-tallied against
+            // it. Without one no sovereign shard change is ever approved.
+            let governance = match bundle
+                .as_ref()
+                .map(|b| Ok::<_, anyhow::Error>(StakeRoster::parse(b.text("stake.aln")?)?))
+            {
+                Some(Ok(roster)) => Some(Governance::for_ledger(
+                    QuorumEngine::new(roster),
+                    "policies/.evolve.jsonl",
+                )),
+                Some(Err(e)) => {
+                    eprintln!("evolve: stake roster unusable, approving no shard change: {:#}", e);
+                    None
+                }
+                None => None,
+            };
+            let approvals: Box<dyn EvolveApprovals> = match &governance {
+                Some(g) => Box::new(EvolveLedgerApprovals::new("policies/.evolve.jsonl", g.clone())),
+                None => Box::new(NoApprovals),
+            };
+            // RoH, neurorights and eco policy, swapped in place when policies/
+            // changes and the new bundle lints clean with EVOLVE approval for
+            // sovereign shards.
+            let policy = PolicySnapshot::load_or_fail_closed("policies", &anchor).shared();
+            PolicyReloader::new("policies", anchor, Arc::clone(&policy), approvals)
+                .spawn(std::time::Duration::from_secs(5));
+            (policy, bundle, governance)
+        }
+        Err(e) => {
+            eprintln!("policy: no stake anchor, failing closed to CHAT only: {}", e);
+            (PolicySnapshot::fail_closed(format!("stake anchor: {}", e)).shared(), None, None)
+        }
+    };
+    // With an LCD configured, EVOLVE tokens must also be finalized on Bostrom.
+    let offline = bundle.map(|bundle| OfflineEvolveVerifier::from_bundle(&bundle));
+    let evolve_client: Box<dyn BostromClient + Send + Sync> = match offline {
+        None => Box::new(FailClosed),
+        Some(Err(e)) => {
+            eprintln!("evolve: stake shard unusable, refusing every EVOLVE token: {}", e);
+            Box::new(FailClosed)
+        }
+        Some(Ok(offline)) => match std::env::var("BOSTROM_LCD_URL") {
+            Ok(url) => {
+                let lcd = LcdClient::new(&url, std::time::Duration::from_secs(5));
+                Box::new(ChainEvolveVerifier::new(offline, LcdEvolveVerifier::new(lcd, 2)))
+            }
+            Err(_) => Box::new(offline),
+        },
+    };
+    let evolve_guard = EvolveGuard::new(evolve_client);
+    // At most max_evolve_rate_per_day structural changes per rolling 24h;
+    // without citizen-stake.json or a stake roster, none.
+    let evolve_budget = match governance {
+        Some(governance) => EvolveBudgetGuard::from_citizen_stake(
+            "policies/citizen-stake.json",
+            "policies/.evolve.jsonl",
+            governance.clone(),
+        )
+        .unwrap_or_else(|e| {
+            eprintln!("evolve: no citizen stake, admitting no structural change: {:#}", e);
+            EvolveBudgetGuard::new("policies/.evolve.jsonl", governance, 0.0)
+        }),
+        None => EvolveBudgetGuard::fail_closed("policies/.evolve.jsonl"),
+    };
 
     let firewall = MetaFirewall::new(MetaFirewallConfig {
         risk_threshold_block: 0.7,
         risk_threshold_quarantine: 0.4,
     });
 
-    let donut = DonutloopLogger::new("shards/ledger/donutloop.aln");
+    // Refuse to boot on a tampered, truncated or deleted audit spine.
+    let ledger = donutloop::verify_anchored("shards/ledger/donutloop.aln")?;
+    if ledger.torn_tail {
+        eprintln!("donutloop: discarding torn final entry from previous crash");
+    }
+    let donut = DonutloopLogger::open("shards/ledger/donutloop.aln")?;
+
+    // Node-held chord key; only chords minted here are honoured by the gate.
+    let issuer = ChordIssuer::from_key_file("shards/keys/chord-issuer.key")?;
+    // Usage and revocations persist here and are shared by every guard.
+    let chord_store = Arc::new(ChordStore::open("shards/ledger/chords.jsonl")?);
+
+    // Consent receipts are signed by the subject's key; revocations persist
+    // here. Without the key or consent.json no receipt is honoured.
+    let consent_store = Arc::new(ConsentStore::open("shards/ledger/consent.jsonl")?);
+    let consent = std::fs::read_to_string("shards/keys/consent.pub")
+        .map_err(anyhow::Error::from)
+        .and_then(|key| Ok(ConsentVerifier::from_public_hex(&key)?))
+        .and_then(|verifier| {
+            ConsentGuard::from_consent_file("policies/consent.json", verifier, Arc::clone(&consent_store))
+        })
+        .unwrap_or_else(|e| {
+            eprintln!("consent: no subject consent key or scopes, refusing consented actions: {:#}", e);
+            ConsentGuard::fail_closed(consent_store)
+        });
+
+    // Primary, secure and alternate addresses of this node's sovereign subject.
+    let identity: sovereign_boundary::BostromIdentity =
+        serde_json::from_str(&std::fs::read_to_string("policies/bostrom-identity.json")?)?;
+    let subject = identity.primary_address.clone();
 
-    let gate = TsafeCortexGate::new(firewall, roh_guard, nr_guard, eco_guard, donut);
+    // Refuse to boot if any sovereign shard is exposed or misplaced.
+    let (_boundary, audit) = match sovereign_boundary::BoundaryBuilder::new(".", identity.clone()).build() {
+        Ok(booted) => booted,
+        Err(sovereign_boundary::BootError::Rejected(audit)) => {
+            eprintln!("{}", serde_json::to_string_pretty(&audit)?);
+            anyhow::bail!("sovereign boundary audit rejected boot");
+        }
+        Err(e) => return Err(e.into()),
+    };
+    eprintln!("boundary: {} shard(s) bound, {} file(s) scanned", audit.bound, audit.files_scanned);
+
+    let gate = TsafeCortexGate::new(TsafeCortexGateConfig {
+        firewall,
+        policy,
+        evolve_guard,
+        evolve_budget,
+        consent,
+        binding: SubjectBinding::new(identity),
+        // Fatigue and QPU readings for the RoH model come from the node.
+        telemetry: NodeTelemetry::new("shards/biospec/host.lifeforce.aln"),
+        chord_verifier: issuer.verifier(),
+        chord_store: Arc::clone(&chord_store),
+        donutlogger: donut,
+    });
 
     // Example: safe, suggestion-only CHAT request, no actuation.
     let action = XRAction {
         kind: XRActionKind::XRRouteStep,
-        subject_id: "bostrom18sd2u...".into(),
+        subject_id: subject,
         route: "CHAT".into(),
         requested_fields: vec!["explain_roh_model".into()],
         lifeforce_cost: 0.01,
         roh_before: 0.12,
         roh_after_estimate: 0.12,
+        evolve_token: None,
     };
 
-    let cap = CapabilityChord {
-        id: Uuid::new_v4(),
-        kind: CapabilityKind::ExplainPolicy,
-        subject_id: action.subject_id.clone(),
-        max_tokens: 1024,
-        expires_at_unix: chrono::Utc::now().timestamp() + 60,
-        actuation_rights: "SuggestOnly".into(),
-    };
+    let cap = CapabilityChord::mint(
+        &issuer,
+        CapabilityKind::ExplainPolicy,
+        action.subject_id.clone(),
+        "CHAT",
+        "SuggestOnly",
+        1024,
+        60,
+    )?;
+    let (cap_id, cap_kind) = (cap.id.to_string(), format!("{:?}", cap.kind));
+    chord_store.record_issued(
+        ChordRef { id: &cap_id, subject_id: &cap.subject_id, kind: &cap_kind },
+        cap.expires_at_unix,
+    )?;
 
     let req = Request {
         subject_id: action.subject_id.clone(),
@@ -47,6 +190,8 @@ fn main() -> anyhow::Result<()> {
         raw_prompt: Some("Explain my current RoH model safely".into()),
         action,
         capability: cap,
+        purpose: None,
+        consent: None,
     };
 
     match gate.authorize(req) {
@@ -0,0 +1,491 @@
+//! Boot-time construction of a [`SovereignExecutionBoundary`] from a real
+//! node: read the workspace manifest, walk the shard roots it declares, bind
+//! every recognised shard and audit the result before anything else runs.
+
+use std::collections::BTreeSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+use sovereign_types::NeuroWorkspaceManifest;
+use subject_id::SubjectId;
+use thiserror::Error;
+
+use crate::{
+    AlnPolicyShards, BostromIdentity, BoundaryInvariants, ShardBinding, ShardClass,
+    SovereignExecutionBoundary,
+};
+
+/// Manifest location relative to the NeuroXFS root.
+pub const MANIFEST_PATH: &str = "neuroworkspace/neuro-workspace.manifest.aln";
+
+/// Compound extensions per class. A file name matches when it equals the
+/// extension (`neurorights.json`) or ends with `.` + extension
+/// (`day1.nstream.neuroaln`); the longest match wins.
+const EXTENSIONS: &[(&str, ShardClass)] = &[
+    ("nstream.neuroaln", ShardClass::NeuroStream),
+    ("neuroaln", ShardClass::NeuroStream),
+    ("lifaln", ShardClass::NeuroStream),
+    ("ocpu", ShardClass::BioSpec),
+    ("ocpuenv", ShardClass::BioSpec),
+    ("lifeforce.aln", ShardClass::BioSpec),
+    ("biosession.aln", ShardClass::BioSpec),
+    ("nnetx", ShardClass::Model),
+    ("nnetw", ShardClass::Model),
+    ("nnetq", ShardClass::Model),
+    ("nfeat.aln", ShardClass::Model),
+    ("nmap.aln", ShardClass::Model),
+    ("donutloop.aln", ShardClass::Ledger),
+    ("evolve.jsonl", ShardClass::Ledger),
+    ("nnet-loop.aln", ShardClass::Ledger),
+    ("answer.ndjson", ShardClass::Ledger),
+    ("rohmodel.aln", ShardClass::SovereignConfig),
+    ("tsafe.aln", ShardClass::SovereignConfig),
+    ("vkernel.aln", ShardClass::SovereignConfig),
+    ("stake.aln", ShardClass::SovereignConfig),
+    ("neurorights.json", ShardClass::SovereignConfig),
+    ("smart.json", ShardClass::SovereignConfig),
+    ("evolve-token.json", ShardClass::SovereignConfig),
+    ("bchainproof.json", ShardClass::Proof),
+    ("nnet-proof.bchain.json", ShardClass::Proof),
+];
+
+impl ShardClass {
+    /// Class of a shard by its compound extension, if it is a shard at all.
+    pub fn classify(file_name: &str) -> Option<ShardClass> {
+        EXTENSIONS
+            .iter()
+            .filter(|(ext, _)| {
+                file_name == *ext
+                    || file_name
+                        .strip_suffix(ext)
+                        .is_some_and(|stem| stem.ends_with('.'))
+            })
+            .max_by_key(|(ext, _)| ext.len())
+            .map(|(_, class)| *class)
+    }
+
+    /// Classes whose shards fail boot when exposed or misplaced.
+    pub fn is_sovereign(&self) -> bool {
+        matches!(self, ShardClass::SovereignConfig | ShardClass::NeuroStream)
+    }
+
+    /// Raw bytes of proofs are meant to be published; nothing else leaves
+    /// the node unless explicitly allowed.
+    fn ai_export_by_default(&self) -> bool {
+        matches!(self, ShardClass::Proof)
+    }
+
+    fn declared_root<'m>(&self, manifest: &'m NeuroWorkspaceManifest) -> &'m str {
+        match self {
+            ShardClass::NeuroStream => &manifest.shards_neuro,
+            ShardClass::BioSpec => &manifest.shards_biospec,
+            ShardClass::Model => &manifest.shards_model,
+            ShardClass::Ledger | ShardClass::Proof => &manifest.shards_ledger,
+            ShardClass::SovereignConfig => &manifest.shards_root,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "violation", rename_all = "snake_case")]
+pub enum Violation {
+    AiExportable,
+    WorldReadable { mode: u32 },
+    OutsideDeclaredRoot { declared_root: PathBuf },
+    MissingConfigShard,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AuditFinding {
+    /// Path relative to the NeuroXFS root.
+    pub path: PathBuf,
+    pub class: ShardClass,
+    #[serde(flatten)]
+    pub violation: Violation,
+}
+
+/// What the boot scan saw and everything it objected to.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BoundaryAudit {
+    pub root: PathBuf,
+    pub files_scanned: usize,
+    pub unclassified: usize,
+    pub bound: usize,
+    pub findings: Vec<AuditFinding>,
+}
+
+impl BoundaryAudit {
+    pub fn is_clean(&self) -> bool {
+        self.findings.is_empty()
+    }
+}
+
+#[derive(Debug, Error)]
+pub enum BootError {
+    #[error("{path}: {message}")]
+    Io { path: PathBuf, message: String },
+    #[error("manifest {path}: {message}")]
+    Manifest { path: PathBuf, message: String },
+    #[error("manifest is for subject {0}, not this node")]
+    ForeignSubject(String),
+    #[error("boundary audit failed with {} finding(s)", .0.findings.len())]
+    Rejected(BoundaryAudit),
+    #[error("boundary invariant violated: {0}")]
+    Invariant(String),
+}
+
+fn io_err(path: &Path, e: std::io::Error) -> BootError {
+    BootError::Io {
+        path: path.to_path_buf(),
+        message: e.to_string(),
+    }
+}
+
+/// Builds the boundary for one subject's NeuroXFS root.
+pub struct BoundaryBuilder {
+    root: PathBuf,
+    subject: BostromIdentity,
+    invariants: BoundaryInvariants,
+    ai_export: BTreeSet<PathBuf>,
+}
+
+impl BoundaryBuilder {
+    pub fn new(root: impl Into<PathBuf>, subject: BostromIdentity) -> Self {
+        Self {
+            root: root.into(),
+            subject,
+            invariants: BoundaryInvariants::default(),
+            ai_export: BTreeSet::new(),
+        }
+    }
+
+    pub fn invariants(mut self, invariants: BoundaryInvariants) -> Self {
+        self.invariants = invariants;
+        self
+    }
+
+    /// Mark one shard (relative to the root) as AI-exportable. Doing so for a
+    /// SOVEREIGNCONFIG or NeuroStream shard fails the audit.
+    pub fn allow_ai_export(mut self, rel: impl Into<PathBuf>) -> Self {
+        self.ai_export.insert(rel.into());
+        self
+    }
+
+    /// Scan, bind and audit. Any finding rejects boot with the full report;
+    /// on success the audit is returned alongside the boundary.
+    pub fn build(self) -> Result<(SovereignExecutionBoundary, BoundaryAudit), BootError> {
+        let manifest_path = self.root.join(MANIFEST_PATH);
+        let manifest: NeuroWorkspaceManifest =
+            aln_format::from_path(&manifest_path).map_err(|e| BootError::Manifest {
+                path: manifest_path.clone(),
+                message: format!("{:#}", e),
+            })?;
+        let manifest_subject: Option<SubjectId> = manifest.subject_id.parse().ok();
+        if manifest_subject
+            .as_ref()
+            .and_then(|s| self.subject.resolve(s))
+            .is_none()
+        {
+            return Err(BootError::ForeignSubject(manifest.subject_id));
+        }
+
+        let mut audit = BoundaryAudit {
+            root: self.root.clone(),
+            files_scanned: 0,
+            unclassified: 0,
+            bound: 0,
+            findings: Vec::new(),
+        };
+        let cfg = &manifest.sovereign_config;
+        let declared_config: BTreeSet<PathBuf> = [
+            &cfg.rohmodel,
+            &cfg.tsafe,
+            &cfg.vkernel,
+            &cfg.neurorights,
+            &cfg.smart,
+            &cfg.evolve_token,
+            &cfg.stake,
+        ]
+        .into_iter()
+        .chain(cfg.nnetfs_index.as_ref())
+        .chain(cfg.neurofs_index.as_ref())
+        .map(PathBuf::from)
+        .collect();
+
+        let mut files = Vec::new();
+        for shard_root in declared_roots(&manifest)? {
+            walk(&self.root, &shard_root, &mut files)?;
+        }
+        let mut bindings = Vec::new();
+        for rel in files {
+            audit.files_scanned += 1;
+            let name = rel.file_name().and_then(|n| n.to_str()).unwrap_or_default();
+            let class = if declared_config.contains(&rel) {
+                ShardClass::SovereignConfig
+            } else if let Some(class) = ShardClass::classify(name) {
+                class
+            } else {
+                audit.unclassified += 1;
+                continue;
+            };
+            let binding = ShardBinding {
+                class,
+                ai_export_allowed: class.ai_export_by_default() || self.ai_export.contains(&rel),
+                path: rel,
+            };
+            self.audit_shard(&manifest, &binding, &mut audit)?;
+            bindings.push(binding);
+        }
+        for rel in &declared_config {
+            if !bindings.iter().any(|b| &b.path == rel) {
+                audit.findings.push(AuditFinding {
+                    path: rel.clone(),
+                    class: ShardClass::SovereignConfig,
+                    violation: Violation::MissingConfigShard,
+                });
+            }
+        }
+        audit.bound = bindings.len();
+        if !audit.is_clean() {
+            return Err(BootError::Rejected(audit));
+        }
+
+        let boundary = SovereignExecutionBoundary {
+            subject: self.subject,
+            neuroxfs_root: self.root.clone(),
+            shard_bindings: bindings,
+            aln_policies: AlnPolicyShards {
+                rohmodel_path: cfg.rohmodel.clone().into(),
+                tsafe_path: cfg.tsafe.clone().into(),
+                vkernel_path: cfg.vkernel.clone().into(),
+                neurorights_path: cfg.neurorights.clone().into(),
+                smart_policy_path: cfg.smart.clone().into(),
+                evolve_token_path: cfg.evolve_token.clone().into(),
+                stake_path: cfg.stake.clone().into(),
+                manifest_path: MANIFEST_PATH.into(),
+            },
+            invariants: self.invariants,
+        };
+        boundary
+            .validate_static_invariants()
+            .map_err(BootError::Invariant)?;
+        Ok((boundary, audit))
+    }
+
+    fn audit_shard(
+        &self,
+        manifest: &NeuroWorkspaceManifest,
+        binding: &ShardBinding,
+        audit: &mut BoundaryAudit,
+    ) -> Result<(), BootError> {
+        if !binding.class.is_sovereign() {
+            return Ok(());
+        }
+        let mut flag = |violation| {
+            audit.findings.push(AuditFinding {
+                path: binding.path.clone(),
+                class: binding.class,
+                violation,
+            })
+        };
+        if binding.ai_export_allowed {
+            flag(Violation::AiExportable);
+        }
+        let abs = self.root.join(&binding.path);
+        if let Some(mode) = world_readable(&abs)? {
+            flag(Violation::WorldReadable { mode });
+        }
+        let declared_root = PathBuf::from(binding.class.declared_root(manifest));
+        if !self.within(&binding.path, &declared_root)? {
+            flag(Violation::OutsideDeclaredRoot { declared_root });
+        }
+        Ok(())
+    }
+
+    /// Whether `rel` lies under `declared_root`, after following symlinks so
+    /// a link cannot smuggle a shard in from elsewhere.
+    fn within(&self, rel: &Path, declared_root: &Path) -> Result<bool, BootError> {
+        if !rel.starts_with(declared_root) {
+            return Ok(false);
+        }
+        let root = self.root.join(declared_root);
+        let root = fs::canonicalize(&root).map_err(|e| io_err(&root, e))?;
+        let abs = self.root.join(rel);
+        let real = fs::canonicalize(&abs).map_err(|e| io_err(&abs, e))?;
+        Ok(real.starts_with(root))
+    }
+}
+
+/// The manifest's shard roots, outermost first with nested ones dropped so
+/// no file is scanned twice. Each must stay inside the NeuroXFS root.
+fn declared_roots(manifest: &NeuroWorkspaceManifest) -> Result<Vec<PathBuf>, BootError> {
+    let mut roots: Vec<PathBuf> = [
+        &manifest.shards_root,
+        &manifest.shards_biospec,
+        &manifest.shards_neuro,
+        &manifest.shards_model,
+        &manifest.shards_ledger,
+    ]
+    .into_iter()
+    .map(PathBuf::from)
+    .collect();
+    if let Some(bad) = roots.iter().find(|r| {
+        r.as_os_str().is_empty()
+            || !r
+                .components()
+                .all(|c| matches!(c, std::path::Component::Normal(_)))
+    }) {
+        return Err(BootError::Manifest {
+            path: MANIFEST_PATH.into(),
+            message: format!("shard root {} is not relative to the node", bad.display()),
+        });
+    }
+    roots.sort();
+    roots.dedup_by(|inner, outer| inner.starts_with(outer));
+    Ok(roots)
+}
+
+/// Every regular file (or link to one) under `root/rel`, relative to
+/// `root`. A declared root that does not exist yet holds no shards.
+fn walk(root: &Path, rel: &Path, out: &mut Vec<PathBuf>) -> Result<(), BootError> {
+    let dir = root.join(rel);
+    let entries = match fs::read_dir(&dir) {
+        Ok(entries) => entries,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(()),
+        Err(e) => return Err(io_err(&dir, e)),
+    };
+    for entry in entries {
+        let entry = entry.map_err(|e| io_err(&dir, e))?;
+        let child = rel.join(entry.file_name());
+        let file_type = entry
+            .file_type()
+            .map_err(|e| io_err(&root.join(&child), e))?;
+        if file_type.is_dir() {
+            walk(root, &child, out)?;
+        } else if file_type.is_file() || file_type.is_symlink() {
+            out.push(child);
+        }
+    }
+    Ok(())
+}
+
+#[cfg(unix)]
+fn world_readable(path: &Path) -> Result<Option<u32>, BootError> {
+    use std::os::unix::fs::PermissionsExt;
+    let mode = fs::metadata(path)
+        .map_err(|e| io_err(path, e))?
+        .permissions()
+        .mode();
+    Ok((mode & 0o004 != 0).then_some(mode & 0o7777))
+}
+
+#[cfg(not(unix))]
+fn world_readable(_path: &Path) -> Result<Option<u32>, BootError> {
+    Ok(None)
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+    use sovereign_kernel::fixtures::{write, write_workspace};
+    use std::os::unix::fs::PermissionsExt;
+
+    const PRIMARY: &str = "bostrom18sd2ujv24ual9c9pshtxys6j8knh6xaead9ye7";
+
+    fn identity() -> BostromIdentity {
+        BostromIdentity {
+            primary_address: PRIMARY.parse().unwrap(),
+            secure_address: None,
+            safe_alternates: Vec::new(),
+        }
+    }
+
+    fn node(root: &Path) {
+        write_workspace(root, PRIMARY);
+        write(root, "shards/neuro/day1.nstream.neuroaln", "");
+        write(root, "shards/ledger/tx.bchainproof.json", "{}");
+        write(root, "shards/ledger/notes.txt", "");
+        write(root, "README.md", "");
+        write(root, "target/debug/leftover.neuroaln", "");
+    }
+
+    fn violations(err: BootError) -> Vec<(String, Violation)> {
+        match err {
+            BootError::Rejected(audit) => audit
+                .findings
+                .into_iter()
+                .map(|f| (f.path.display().to_string(), f.violation))
+                .collect(),
+            other => panic!("expected an audit rejection, got {}", other),
+        }
+    }
+
+    #[test]
+    fn classifies_by_longest_compound_extension() {
+        use ShardClass::*;
+        let class = ShardClass::classify;
+        assert!(matches!(class("a.nstream.neuroaln"), Some(NeuroStream)));
+        assert!(matches!(class("host.lifeforce.aln"), Some(BioSpec)));
+        assert!(matches!(class("x.nnet-proof.bchain.json"), Some(Proof)));
+        assert!(matches!(class("neurorights.json"), Some(SovereignConfig)));
+        assert!(class("notstake.aln").is_none());
+        assert!(class("notes.json").is_none());
+    }
+
+    #[test]
+    fn clean_node_boots_with_bindings_and_report() {
+        let root = tempfile::tempdir().unwrap();
+        node(root.path());
+        let (boundary, audit) = BoundaryBuilder::new(root.path(), identity())
+            .build()
+            .unwrap();
+        assert!(audit.is_clean());
+        assert_eq!(audit.bound, 11);
+        assert_eq!(audit.unclassified, 1);
+        assert_eq!(audit.files_scanned, 12);
+        let proof = boundary
+            .shard_bindings
+            .iter()
+            .find(|b| matches!(b.class, ShardClass::Proof))
+            .unwrap();
+        assert!(proof.ai_export_allowed);
+    }
+
+    #[test]
+    fn exposed_or_misplaced_sovereign_shards_fail_boot() {
+        let root = tempfile::tempdir().unwrap();
+        node(root.path());
+        fs::set_permissions(
+            root.path().join("shards/root/tsafe.aln"),
+            fs::Permissions::from_mode(0o644),
+        )
+        .unwrap();
+        write(root.path(), "shards/model/stray.neuroaln", "");
+        fs::remove_file(root.path().join("shards/root/stake.aln")).unwrap();
+
+        let err = BoundaryBuilder::new(root.path(), identity())
+            .allow_ai_export("shards/neuro/day1.nstream.neuroaln")
+            .build()
+            .unwrap_err();
+        let found = violations(err);
+        assert!(found.contains(&(
+            "shards/root/tsafe.aln".into(),
+            Violation::WorldReadable { mode: 0o644 }
+        )));
+        assert!(found.contains(&(
+            "shards/model/stray.neuroaln".into(),
+            Violation::OutsideDeclaredRoot {
+                declared_root: "shards/neuro".into()
+            }
+        )));
+        assert!(found.contains(&(
+            "shards/neuro/day1.nstream.neuroaln".into(),
+            Violation::AiExportable
+        )));
+        assert!(found.contains(&(
+            "shards/root/stake.aln".into(),
+            Violation::MissingConfigShard
+        )));
+    }
+}
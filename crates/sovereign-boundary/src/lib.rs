@@ -1,5 +1,10 @@
 use serde::{Deserialize, Serialize};
 use std::path::PathBuf;
+use subject_id::SubjectId;
+
+pub mod boot;
+
+pub use boot::{AuditFinding, BootError, BoundaryAudit, BoundaryBuilder, Violation};
 
 /// High-level boundary descriptor tying Bostrom identity, NeuroXFS shard classes,
 /// and ALN policy shards into a single Sovereign Execution Boundary surface.
@@ -11,14 +16,30 @@ use std::path::PathBuf;
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct BostromIdentity {
     /// Canonical subject root (e.g., bostrom18sd2u…).
-    pub primary_address: String,
+    pub primary_address: SubjectId,
     /// Optional secure / monitored alternate (e.g., bostrom1ldgmtf…).
-    pub secure_address: Option<String>,
+    pub secure_address: Option<SubjectId>,
     /// Additional safe alternates (zeta…, 0x… anchors).
-    pub safe_alternates: Vec<String>,
+    pub safe_alternates: Vec<SubjectId>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+impl BostromIdentity {
+    /// Every address that speaks for this subject, primary first.
+    pub fn addresses(&self) -> impl Iterator<Item = &SubjectId> {
+        std::iter::once(&self.primary_address)
+            .chain(self.secure_address.as_ref())
+            .chain(self.safe_alternates.iter())
+    }
+
+    /// Canonical subject (the primary address) if `address` is one of ours.
+    pub fn resolve(&self, address: &SubjectId) -> Option<&SubjectId> {
+        self.addresses()
+            .any(|a| a == address)
+            .then_some(&self.primary_address)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum ShardClass {
     NeuroStream,     // .neuroaln, .nstream.neuroaln, .lifaln
     BioSpec,         // .ocpu, .ocpuenv, .lifeforce.aln, .biosession.aln
@@ -63,10 +84,25 @@ pub struct BoundaryInvariants {
     pub enforce_donutloop_append_only: bool,
 }
 
+impl Default for BoundaryInvariants {
+    /// The only posture a sovereign node may boot with.
+    fn default() -> Self {
+        Self {
+            roh_ceiling: 0.3,
+            enforce_roh_monotonicity: true,
+            enforce_neurorights: true,
+            enforce_donutloop_append_only: true,
+        }
+    }
+}
+
 /// Full, typed description of a Rust-Party Sovereign Execution Boundary.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SovereignExecutionBoundary {
     pub subject: BostromIdentity,
+    /// Directory every `ShardBinding::path` is relative to.
+    #[serde(default)]
+    pub neuroxfs_root: PathBuf,
     pub shard_bindings: Vec<ShardBinding>,
     pub aln_policies: AlnPolicyShards,
     pub invariants: BoundaryInvariants,
@@ -81,6 +117,19 @@ impl SovereignExecutionBoundary {
         if !self.invariants.enforce_donutloop_append_only {
             return Err("Donutloop append-only invariant must be enabled".into());
         }
+        // The audit spine must verify end to end before anything else runs.
+        for shard in &self.shard_bindings {
+            let is_donutloop = shard
+                .path
+                .file_name()
+                .and_then(|n| n.to_str())
+                .map(|n| n.ends_with("donutloop.aln"))
+                .unwrap_or(false);
+            if matches!(shard.class, ShardClass::Ledger) && is_donutloop {
+                donutloop::verify_anchored(self.neuroxfs_root.join(&shard.path))
+                    .map_err(|e| format!("Donutloop {:?} failed verification: {}", shard.path, e))?;
+            }
+        }
         // Example: ensure SovereignConfig shards are never AI-exportable.
         for shard in &self.shard_bindings {
             if matches!(shard.class, ShardClass::SovereignConfig) && shard.ai_export_allowed {
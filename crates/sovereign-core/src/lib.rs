@@ -3,7 +3,13 @@
 //! that other crates use to enforce neurorights, RoH ceilings, and Tsafe.
 
 use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
 use std::fmt;
+use viability_kernel::{AxisSnapshot, Polytope, ViabilityError};
+
+pub mod capabilities;
+
+use capabilities::SovereignClassification;
 
 /// Hex-encoded hash stamp used across donutloop and evolution streams.
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash)]
@@ -39,36 +45,205 @@ pub enum EvolutionKind {
     KernelChange,
 }
 
-/// Single EvolutionProposal entry as stored in .evolve.jsonl.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+/// Lifecycle of an EVOLVE proposal. Every transition is one more line in
+/// .evolve.jsonl; `RolledBack` and `Rejected` are terminal.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
+pub enum ProposalState {
+    Draft,
+    UnderReview,
+    QuorumReached,
+    Applied,
+    RolledBack,
+    Rejected,
+}
+
+impl ProposalState {
+    /// Legal moves: forward through review to `Applied`, `Rejected` from any
+    /// state before `Applied`, and `RolledBack` only once applied.
+    pub fn can_transition_to(self, next: ProposalState) -> bool {
+        use ProposalState::*;
+        matches!(
+            (self, next),
+            (Draft, UnderReview)
+                | (UnderReview, QuorumReached)
+                | (QuorumReached, Applied)
+                | (Applied, RolledBack)
+                | (Draft | UnderReview | QuorumReached, Rejected)
+        )
+    }
+
+    pub fn is_terminal(self) -> bool {
+        matches!(self, ProposalState::RolledBack | ProposalState::Rejected)
+    }
+}
+
+/// Schema version of [`EvolutionProposal`] lines written today.
+pub const EVOLVE_SCHEMA_VERSION: u32 = 3;
+
+/// A shard a proposal changes, pinned to the content it changes it to.
+/// Approvals, the quorum ballot and the journal's apply step all read this
+/// pair, so what stakeholders vote on is exactly what gets written.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AffectedShard {
+    /// Shard name relative to the shard root, e.g. `rohmodel.aln`.
+    pub shard: String,
+    /// Hex SHA-256 of the shard's bytes once the proposal is applied.
+    pub sha256: String,
+}
+
+/// Neurorights a proposal commits to upholding for the data it touches.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct NeurorightsPosture {
+    pub mental_privacy: bool,
+    pub dream_state_sensitive: bool,
+    pub forbid_decision_use: bool,
+    pub soul_non_tradeable: bool,
+}
+
+/// The one `.evolve.jsonl` record: a proposal as of one lifecycle
+/// transition. Lines are hash-chained through `prev_hexstamp`, so the
+/// journal, the CI checker and every reader parse and verify the same thing.
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct EvolutionProposal {
+    pub schema_version: u32,
     pub proposal_id: String,
+    /// Hexstamp of the line before this one; `None` only for the first line.
+    pub prev_hexstamp: Option<HexStamp>,
+    pub subject_id: String,
+    #[serde(default)]
+    pub description: String,
     pub kind: EvolutionKind,
     pub effect_bounds: (Roh, Roh),
     pub roh_before: Roh,
     pub roh_after: Roh,
-    pub decision: String,
+    /// State this line moves the proposal into.
+    pub decision: ProposalState,
+    /// When this transition was recorded.
+    pub timestamp_unix: i64,
     pub token_kind: String,
-    pub signatures: Vec<String>,
+    /// Sovereign classification label, e.g. "SOVEREIGNCONFIG".
+    pub class_label: String,
+    /// Bostrom tx anchoring the approval; required from `QuorumReached` on
+    /// for EVOLVE-class proposals.
+    #[serde(default)]
+    pub bostrom_tx: String,
+    #[serde(default)]
+    pub consent_proofs: Vec<String>,
+    /// Shards the proposal changes and the content each is changed to.
+    #[serde(default)]
+    pub affected_shards: Vec<AffectedShard>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub neurorights_posture: Option<NeurorightsPosture>,
+    /// Scope, actuation rights and safety profile CI keys on; its label and
+    /// token kind must agree with `class_label` and `token_kind`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sovereign_classification: Option<SovereignClassification>,
+    /// SEC1-compressed secp256k1 key (hex) of whoever made this transition.
+    pub actor: String,
+    /// `actor`'s signature over `hexstamp`.
+    pub actor_signature: String,
+    /// Pre-apply snapshot of `affected_shards`, set from `Applied` on.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub snapshot: Option<HexStamp>,
+    /// Digest of the quorum certificate, set from `QuorumReached` on.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub quorum_certificate: Option<HexStamp>,
     pub hexstamp: HexStamp,
 }
 
+impl EvolutionProposal {
+    /// EVOLVE-class proposals change sovereign configuration.
+    pub fn is_evolve_class(&self) -> bool {
+        self.token_kind == "Evolve" || self.class_label == "SOVEREIGNCONFIG"
+    }
+}
+
+/// Axes the RoH estimate is built from; `.rohmodel.aln` weights are keyed
+/// by these names.
+pub const ROH_AXES: [&str; 4] = [
+    "lifeforce_load",
+    "biospec_fatigue",
+    "qpu_roh",
+    "route_context_risk",
+];
+
+/// Baseline risk of a route in [0, 1]; unknown routes count as riskiest.
+pub fn route_context_risk(route: &str) -> f32 {
+    match route {
+        "CHAT" => 0.0,
+        "GOV" => 0.2,
+        "XR" => 0.3,
+        "OTA" => 0.5,
+        "BCI" => 0.6,
+        "NANOSWARM" => 0.8,
+        _ => 1.0,
+    }
+}
+
+/// A weighted RoH estimate with what each axis contributed, for audit.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct RohEstimate {
+    pub roh: Roh,
+    /// `weight * value` per axis, for every axis in [`ROH_AXES`].
+    pub contributions: BTreeMap<String, f32>,
+}
+
+impl RohEstimate {
+    /// Weighted sum over [`ROH_AXES`]. Axis values are clamped to [0, 1] and
+    /// negative weights to 0, so no shard or caller can subtract risk.
+    /// `route_context_risk` is always derived from `route`, never taken from
+    /// `axes`.
+    pub fn weighted(
+        weights: &HashMap<String, f32>,
+        axes: &BTreeMap<String, f32>,
+        route: &str,
+    ) -> Self {
+        let mut contributions = BTreeMap::new();
+        for axis in ROH_AXES {
+            let value = match axis {
+                "route_context_risk" => route_context_risk(route),
+                _ => axes.get(axis).copied().unwrap_or(0.0),
+            };
+            let value = if value.is_nan() { 1.0 } else { value.clamp(0.0, 1.0) };
+            let weight = weights.get(axis).copied().unwrap_or(0.0).max(0.0);
+            contributions.insert(axis.to_string(), weight * value);
+        }
+        let roh = Roh(contributions.values().sum());
+        Self { roh, contributions }
+    }
+
+    /// The RoH to enforce: a caller may report more risk than the model
+    /// sees, never less.
+    pub fn floor(&self, caller_estimate: f32) -> Roh {
+        if caller_estimate.is_nan() {
+            return self.roh;
+        }
+        Roh(self.roh.0.max(caller_estimate))
+    }
+}
+
 /// RoH model shard loaded from .rohmodel.aln (simplified public surface).
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct RohModel {
     pub global_ceiling: Roh,
-    // implementation detail: axes, weights, etc., go into internal fields.
+    /// Per-axis weights, keyed by the names in [`ROH_AXES`].
+    #[serde(default)]
+    pub weights: HashMap<String, f32>,
 }
 
 impl RohModel {
-    /// Apply model-specific estimation logic (implemented in backend crate).
+    /// Estimate from route context alone, for flows without telemetry.
     pub fn estimate(&self, prompt: &str, route: &str) -> Roh {
-        // Placeholder: real implementation is delegated to a bound model.
-        let _ = (prompt, route);
-        // Always cap inside consumer code; here we return zero.
-        Roh::zero()
+        let _ = prompt;
+        self.estimate_axes(&BTreeMap::new(), route).roh
+    }
+
+    /// Weighted estimate from named axis telemetry plus route context.
+    pub fn estimate_axes(&self, axes: &BTreeMap<String, f32>, route: &str) -> RohEstimate {
+        RohEstimate::weighted(&self.weights, axes, route)
     }
 }
 
@@ -103,14 +278,28 @@ impl NeuroRights {
 #[serde(rename_all = "camelCase")]
 pub struct TSafeSpec {
     pub name: String,
-    // Ax ≤ b representation and CyberRank weights are maintained internally.
+    /// Ax ≤ b viability region, written as `.vkernel.aln` constraint strings.
+    #[serde(default)]
+    pub region: Polytope,
 }
 
 impl TSafeSpec {
+    /// The RoH estimate is placed on the `roh_global` axis; all other axes
+    /// are zero until the caller supplies telemetry via [`TSafeSpec::check`].
     pub fn permits(&self, roh: Roh, route: &str) -> bool {
         let _ = route;
-        // Stub for polytopic check; real implementation must enforce Ax ≤ b.
-        roh.0 <= 0.3 && !self.name.is_empty()
+        let mut snapshot = self.region.zero_snapshot();
+        snapshot.insert("roh_global".to_string(), roh.0 as f64);
+        self.check(&snapshot).is_ok()
+    }
+
+    /// Full polytopic check of a named-axis snapshot. An empty region fails
+    /// closed: a spec without constraints permits nothing.
+    pub fn check(&self, snapshot: &AxisSnapshot) -> Result<(), ViabilityError> {
+        if self.name.is_empty() {
+            return Err(ViabilityError::EmptyRegion);
+        }
+        self.region.check(snapshot)
     }
 }
 
@@ -196,3 +385,43 @@ impl ChatContext {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn model() -> RohModel {
+        RohModel {
+            global_ceiling: Roh(0.3),
+            weights: [
+                ("lifeforce_load".to_string(), 0.2),
+                ("biospec_fatigue".to_string(), 0.1),
+                ("route_context_risk".to_string(), 0.25),
+                ("qpu_roh".to_string(), -1.0),
+            ]
+            .into(),
+        }
+    }
+
+    #[test]
+    fn estimate_records_each_axis() {
+        let axes = BTreeMap::from([
+            ("lifeforce_load".to_string(), 0.5),
+            ("biospec_fatigue".to_string(), 2.0),
+            ("qpu_roh".to_string(), 0.9),
+        ]);
+        let est = model().estimate_axes(&axes, "BCI");
+        assert_eq!(est.contributions.len(), ROH_AXES.len());
+        assert!((est.contributions["biospec_fatigue"] - 0.1).abs() < 1e-6);
+        assert_eq!(est.contributions["qpu_roh"], 0.0);
+        assert!((est.roh.0 - (0.1 + 0.1 + 0.15)).abs() < 1e-6);
+    }
+
+    #[test]
+    fn caller_cannot_report_less_than_the_model() {
+        let est = model().estimate_axes(&BTreeMap::new(), "NANOSWARM");
+        assert!((est.floor(0.0).0 - 0.2).abs() < 1e-6);
+        assert_eq!(est.floor(0.29).0, 0.29);
+        assert_eq!(est.floor(f32::NAN), est.roh);
+    }
+}
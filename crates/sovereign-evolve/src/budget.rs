@@ -0,0 +1,156 @@
+//! Rolling 24-hour budget on structural evolution, replayed from
+//! `.evolve.jsonl` so it survives restarts and cannot be reset by a caller.
+
+use sovereign_core::{EvolutionProposal, ProposalState};
+use std::io::Read;
+use std::path::Path;
+
+use crate::{open_anchored, EvolveError, EvolveRecord, EvolveStreamGuard, Governance};
+
+/// Length of the rolling window.
+pub const BUDGET_WINDOW_SECS: i64 = 24 * 60 * 60;
+
+/// RoH span that costs one extra unit on top of the base unit per change.
+const COST_SPAN: f32 = 0.3;
+
+/// Cost of one structural change with effect bounds `(lo, hi)`: one unit,
+/// plus the width of the bounds measured in units of the 0.3 RoH ceiling.
+/// A change that may move RoH across the whole ceiling costs two units.
+pub fn change_cost(lo: f32, hi: f32) -> f32 {
+    1.0 + (hi - lo).abs() / COST_SPAN
+}
+
+pub fn proposal_cost(proposal: &EvolutionProposal) -> f32 {
+    change_cost(proposal.effect_bounds.0 .0, proposal.effect_bounds.1 .0)
+}
+
+/// Budget is spent; nothing structural may change before `refills_at_unix`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BudgetExhausted {
+    pub spent: f32,
+    pub limit: f32,
+    pub requested: f32,
+    /// Earliest time at which `requested` fits in the window again, or
+    /// `None` if it exceeds the whole daily budget on its own.
+    pub refills_at_unix: Option<i64>,
+}
+
+/// Applied changes in the window ending now, oldest first.
+#[derive(Debug, Clone)]
+pub struct EvolveBudget {
+    limit: f32,
+    now_unix: i64,
+    applied: Vec<(i64, f32)>,
+}
+
+impl EvolveBudget {
+    /// Replay a ledger validated under `governance`. Every `Applied` line
+    /// inside the window counts; a later rollback does not refund it.
+    pub fn replay<R: Read>(
+        ledger: R,
+        governance: &Governance,
+        limit: f32,
+        now_unix: i64,
+    ) -> Result<Self, EvolveError> {
+        let records = EvolveStreamGuard::new(ledger, governance.clone()).records();
+        Self::from_records(records, limit, now_unix)
+    }
+
+    /// Replay the ledger at `path` against the head anchor its journal
+    /// keeps, so deleting `Applied` lines from the end does not refund them.
+    pub fn replay_path<P: AsRef<Path>>(
+        path: P,
+        governance: &Governance,
+        limit: f32,
+        now_unix: i64,
+    ) -> Result<Self, EvolveError> {
+        Self::from_records(open_anchored(path, governance)?.records(), limit, now_unix)
+    }
+
+    fn from_records(
+        records: impl Iterator<Item = Result<EvolveRecord, EvolveError>>,
+        limit: f32,
+        now_unix: i64,
+    ) -> Result<Self, EvolveError> {
+        let since = now_unix - BUDGET_WINDOW_SECS;
+        let mut applied = Vec::new();
+        for record in records {
+            let p = record?.proposal;
+            if p.decision == ProposalState::Applied && p.timestamp_unix > since {
+                applied.push((p.timestamp_unix, proposal_cost(&p)));
+            }
+        }
+        applied.sort_by_key(|(at, _)| *at);
+        Ok(Self {
+            limit,
+            now_unix,
+            applied,
+        })
+    }
+
+    pub fn limit(&self) -> f32 {
+        self.limit
+    }
+
+    pub fn spent(&self) -> f32 {
+        self.applied.iter().map(|(_, cost)| cost).sum()
+    }
+
+    /// Whether a change costing `cost` fits; if not, when it will.
+    pub fn check(&self, cost: f32) -> Result<(), BudgetExhausted> {
+        let spent = self.spent();
+        if spent + cost <= self.limit {
+            return Ok(());
+        }
+        let exhausted = |refills_at_unix| BudgetExhausted {
+            spent,
+            limit: self.limit,
+            requested: cost,
+            refills_at_unix,
+        };
+        if cost > self.limit {
+            return Err(exhausted(None));
+        }
+        // Changes age out oldest first; find the one whose expiry frees enough.
+        let mut remaining = spent;
+        for (at, c) in &self.applied {
+            remaining -= c;
+            if remaining + cost <= self.limit {
+                return Err(exhausted(Some(at + BUDGET_WINDOW_SECS)));
+            }
+        }
+        Err(exhausted(Some(self.now_unix + BUDGET_WINDOW_SECS)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn budget(limit: f32, applied: &[(i64, f32)]) -> EvolveBudget {
+        EvolveBudget {
+            limit,
+            now_unix: 100_000,
+            applied: applied.to_vec(),
+        }
+    }
+
+    #[test]
+    fn cost_grows_with_effect_bounds() {
+        assert_eq!(change_cost(0.0, 0.0), 1.0);
+        assert!((change_cost(0.0, 0.3) - 2.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn exhausted_budget_reports_refill_time() {
+        let b = budget(3.0, &[(90_000, 1.0), (95_000, 1.5)]);
+        assert!(b.check(0.5).is_ok());
+        let err = b.check(1.0).unwrap_err();
+        assert_eq!(err.refills_at_unix, Some(90_000 + BUDGET_WINDOW_SECS));
+
+        let err = b.check(2.5).unwrap_err();
+        assert_eq!(err.refills_at_unix, Some(95_000 + BUDGET_WINDOW_SECS));
+
+        assert_eq!(b.check(4.0).unwrap_err().refills_at_unix, None);
+    }
+}
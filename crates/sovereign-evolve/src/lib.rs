@@ -1,11 +1,30 @@
 //! Sovereign-evolve: streaming guardian for .evolve.jsonl / .evolve.ndjson.
 //! Enforces schema validation and context boundaries before prompt building.
 
+use donutloop::Anchor;
 use serde::de::DeserializeOwned;
+use serde::Serialize;
 use serde_json::Value;
-use sovereign_core::{ChatRequest, EvolutionProposal, HexStamp};
+use sovereign_core::{ChatRequest, EvolutionProposal, HexStamp, ProposalState};
+use std::collections::HashMap;
 use std::io::{BufRead, BufReader, Read};
 
+mod budget;
+mod lifecycle;
+mod quorum;
+
+pub use budget::{
+    change_cost, proposal_cost, BudgetExhausted, EvolveBudget, BUDGET_WINDOW_SECS,
+};
+pub use lifecycle::{
+    actor_id, certificate_dir, check_transition, open_anchored, sign_transition, verify_transition,
+    EvolveJournal, Governance, ShardStore,
+};
+pub use quorum::{
+    proposal_classes, proposal_content_digest, QuorumCertificate, QuorumEngine, QuorumError,
+    RosterMultisig, StakeRoster, Stakeholder, Vote, VETO_PREFIX,
+};
+
 /// Parsed line with associated hexstamp and raw JSON value for auditing.
 #[derive(Debug, Clone)]
 pub struct EvolveRecord {
@@ -25,6 +44,39 @@ pub enum EvolveError {
     EmptyLine,
     #[error("Invalid proposal: {0}")]
     InvalidProposal(String),
+    #[error("Illegal transition for {proposal_id}: {from:?} -> {to:?}")]
+    IllegalTransition {
+        proposal_id: String,
+        from: Option<ProposalState>,
+        to: ProposalState,
+    },
+    #[error("Hexstamp mismatch (stored {stored}, computed {computed})")]
+    HexstampMismatch { stored: String, computed: String },
+    #[error("Broken chain at {proposal_id}: prev_hexstamp {found:?}, previous line is {expected:?}")]
+    BrokenChain {
+        proposal_id: String,
+        expected: Option<HexStamp>,
+        found: Option<HexStamp>,
+    },
+    #[error("Ledger has {lines} line(s) but its head anchor is line {}", .anchor_seq + 1)]
+    Truncated { anchor_seq: u64, lines: u64 },
+    #[error("Line {} is {found}, but its head anchor is {expected}", .seq + 1)]
+    AnchorMismatch {
+        seq: u64,
+        expected: String,
+        found: String,
+    },
+    #[error("Bad actor signature on {proposal_id}: {message}")]
+    BadActorSignature {
+        proposal_id: String,
+        message: String,
+    },
+    #[error("Actor {actor} on {proposal_id} is not a stakeholder")]
+    UnknownActor { proposal_id: String, actor: String },
+    #[error("Shard snapshot error: {0}")]
+    Snapshot(String),
+    #[error("Quorum error: {0}")]
+    Quorum(#[from] QuorumError),
 }
 
 /// Maximum allowed line length for a single JSONL record.
@@ -33,21 +85,49 @@ const MAX_LINE_LEN: usize = 16 * 1024;
 /// Streaming parser for .evolve.jsonl.
 pub struct EvolveStreamGuard<R> {
     reader: BufReader<R>,
+    anchor: Option<Anchor>,
+    governance: Governance,
 }
 
 impl<R: Read> EvolveStreamGuard<R> {
-    pub fn new(inner: R) -> Self {
+    /// Replay under `governance`: its roster decides who may act and
+    /// re-tallies every quorum certificate the ledger names.
+    pub fn new(inner: R, governance: Governance) -> Self {
+        Self::anchored(inner, None, governance)
+    }
+
+    /// Also require the stream to reach `anchor`, the head the journal last
+    /// wrote (see [`EvolveJournal`]). Without it, lines cut off the end of
+    /// the ledger read as a shorter honest ledger.
+    pub fn anchored(inner: R, anchor: Option<Anchor>, governance: Governance) -> Self {
         Self {
             reader: BufReader::new(inner),
+            anchor,
+            governance,
         }
     }
 
-    /// Iterate over validated EvolutionProposal records.
+    /// Iterate over validated EvolutionProposal records. Besides the
+    /// per-line schema, each line must link to the one before it through
+    /// `prev_hexstamp` and be a legal transition from the state its
+    /// proposal was last left in, signed by a stakeholder.
     pub fn records(mut self) -> impl Iterator<Item = Result<EvolveRecord, EvolveError>> {
+        let mut states: HashMap<String, ProposalState> = HashMap::new();
+        let mut head: Option<HexStamp> = None;
+        let mut seq: u64 = 0;
         std::iter::from_fn(move || {
             let mut line = String::new();
             match self.reader.read_line(&mut line) {
-                Ok(0) => None,
+                Ok(0) => self
+                    .anchor
+                    .take()
+                    .filter(|a| a.seq >= seq)
+                    .map(|a| {
+                        Err(EvolveError::Truncated {
+                            anchor_seq: a.seq,
+                            lines: seq,
+                        })
+                    }),
                 Ok(_) => {
                     if line.trim().is_empty() {
                         return Some(Err(EvolveError::EmptyLine));
@@ -55,7 +135,32 @@ impl<R: Read> EvolveStreamGuard<R> {
                     if line.len() > MAX_LINE_LEN {
                         return Some(Err(EvolveError::LineTooLong));
                     }
-                    Some(parse_evolve_line(&line))
+                    let record = parse_evolve_line(&line).and_then(|r| {
+                        let p = &r.proposal;
+                        if p.prev_hexstamp != head {
+                            return Err(EvolveError::BrokenChain {
+                                proposal_id: p.proposal_id.clone(),
+                                expected: head.clone(),
+                                found: p.prev_hexstamp.clone(),
+                            });
+                        }
+                        let previous = states.get(&p.proposal_id).copied();
+                        check_transition(previous, p, &self.governance)?;
+                        if let Some(a) = self.anchor.as_ref().filter(|a| a.seq == seq) {
+                            if a.hexstamp != p.hexstamp.0 {
+                                return Err(EvolveError::AnchorMismatch {
+                                    seq,
+                                    expected: a.hexstamp.clone(),
+                                    found: p.hexstamp.0.clone(),
+                                });
+                            }
+                        }
+                        states.insert(p.proposal_id.clone(), p.decision);
+                        head = Some(p.hexstamp.clone());
+                        seq += 1;
+                        Ok(r)
+                    });
+                    Some(record)
                 }
                 Err(e) => Some(Err(EvolveError::Io(e))),
             }
@@ -101,6 +206,24 @@ pub fn build_guarded_chat_request(
 }
 
 /// Utility for constructing a HexStamp from raw bytes (e.g., SHA-256 digest).
+/// The bytes are encoded as given; to stamp a record use [`canonical_hexstamp`].
 pub fn hexstamp_from_bytes(bytes: &[u8]) -> HexStamp {
     HexStamp(hex::encode(bytes))
 }
+
+/// SHA-256 of the record's canonical JSON (sorted keys, normalized floats,
+/// externally tagged enums), leaving out the record's own `hexstamp` field.
+pub fn canonical_hexstamp<T: Serialize>(record: &T) -> Result<HexStamp, EvolveError> {
+    aln_format::canonical_hexstamp(record, &["hexstamp"])
+        .map(HexStamp)
+        .map_err(|e| EvolveError::InvalidProposal(e.to_string()))
+}
+
+/// The defined preimage for `EvolutionProposal.hexstamp`: the canonical
+/// record without `hexstamp` and `actor_signature`, since the actor signs
+/// the stamp itself. `prev_hexstamp` is part of it, which chains the ledger.
+pub fn proposal_hexstamp(proposal: &EvolutionProposal) -> HexStamp {
+    aln_format::canonical_hexstamp(proposal, &["hexstamp", "actor_signature"])
+        .map(HexStamp)
+        .expect("EvolutionProposal serializes")
+}
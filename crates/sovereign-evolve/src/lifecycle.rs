@@ -0,0 +1,772 @@
+//! EVOLVE proposal lifecycle: signed transitions appended to `.evolve.jsonl`
+//! and the shard snapshots that make `Applied` reversible.
+
+use donutloop::{anchor_path, Anchor};
+use k256::ecdsa::SigningKey;
+use policy_bundle::{digest_hex, recoverable};
+use serde::{Deserialize, Serialize};
+use sovereign_core::{EvolutionProposal, HexStamp, ProposalState, EVOLVE_SCHEMA_VERSION};
+use std::collections::{BTreeMap, HashMap};
+use std::fs::{File, OpenOptions};
+use std::io::Write;
+use std::path::{Component, Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::{proposal_hexstamp, EvolveError, EvolveStreamGuard, QuorumCertificate, QuorumEngine};
+
+/// Hex `r || s || recovery_id` over the proposal's hexstamp.
+pub fn sign_transition(hexstamp: &HexStamp, key: &SigningKey) -> String {
+    recoverable::sign(hexstamp.0.as_bytes(), key)
+}
+
+/// SEC1-compressed hex of the key that signs as `actor`.
+pub fn actor_id(key: &SigningKey) -> String {
+    recoverable::pubkey_hex(key.verifying_key())
+}
+
+/// SEC1-compressed hex of the key behind a `r || s || recovery_id` signature.
+pub(crate) fn recover_signer(msg: &[u8], sig_hex: &str) -> Result<String, String> {
+    recoverable::recover(msg, sig_hex).map(|key| recoverable::pubkey_hex(&key))
+}
+
+/// Recomputes the hexstamp and checks `actor_signature` recovers to `actor`.
+pub fn verify_transition(proposal: &EvolutionProposal) -> Result<(), EvolveError> {
+    let computed = proposal_hexstamp(proposal);
+    if computed != proposal.hexstamp {
+        return Err(EvolveError::HexstampMismatch {
+            stored: proposal.hexstamp.0.clone(),
+            computed: computed.0,
+        });
+    }
+    let signer = recover_signer(proposal.hexstamp.0.as_bytes(), &proposal.actor_signature)
+        .map_err(|message| EvolveError::BadActorSignature {
+            proposal_id: proposal.proposal_id.clone(),
+            message,
+        })?;
+    if !signer.eq_ignore_ascii_case(&proposal.actor) {
+        return Err(EvolveError::BadActorSignature {
+            proposal_id: proposal.proposal_id.clone(),
+            message: format!("signed by {}, not actor {}", signer, proposal.actor),
+        });
+    }
+    Ok(())
+}
+
+/// Checks one line against the state its proposal was last left in
+/// (`None` for a proposal not seen before). The actor must be a stakeholder
+/// in `governance`'s roster, and a `QuorumReached` or `Applied` line's
+/// certificate must still re-tally under it.
+pub fn check_transition(
+    previous: Option<ProposalState>,
+    proposal: &EvolutionProposal,
+    governance: &Governance,
+) -> Result<(), EvolveError> {
+    let next = proposal.decision;
+    let legal = match previous {
+        None => next == ProposalState::Draft,
+        Some(prev) => prev.can_transition_to(next),
+    };
+    if !legal {
+        return Err(EvolveError::IllegalTransition {
+            proposal_id: proposal.proposal_id.clone(),
+            from: previous,
+            to: next,
+        });
+    }
+    let needs_snapshot = matches!(next, ProposalState::Applied | ProposalState::RolledBack);
+    if needs_snapshot && proposal.snapshot.is_none() {
+        return Err(EvolveError::InvalidProposal(format!(
+            "{}: {:?} without a shard snapshot",
+            proposal.proposal_id, next
+        )));
+    }
+    let needs_quorum = matches!(next, ProposalState::QuorumReached | ProposalState::Applied);
+    if needs_quorum && proposal.quorum_certificate.is_none() {
+        return Err(EvolveError::InvalidProposal(format!(
+            "{}: {:?} without a quorum certificate",
+            proposal.proposal_id, next
+        )));
+    }
+    verify_transition(proposal)?;
+    governance.check_actor(proposal)?;
+    if needs_quorum {
+        governance.verify_quorum(proposal)?;
+    }
+    Ok(())
+}
+
+/// Pre-apply contents of a proposal's shards. `None` marks a shard that did
+/// not exist yet, which rollback removes again.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ShardSnapshot {
+    shards: BTreeMap<String, Option<String>>,
+}
+
+impl ShardSnapshot {
+    fn stamp(&self) -> HexStamp {
+        HexStamp(aln_format::canonical_hexstamp(self, &[]).expect("snapshot serializes"))
+    }
+}
+
+/// Shard root plus the content-addressed snapshot directory next to it.
+pub struct ShardStore {
+    root: PathBuf,
+    snapshots: PathBuf,
+}
+
+impl ShardStore {
+    pub fn new<P: AsRef<Path>, Q: AsRef<Path>>(root: P, snapshots: Q) -> Self {
+        Self {
+            root: root.as_ref().to_path_buf(),
+            snapshots: snapshots.as_ref().to_path_buf(),
+        }
+    }
+
+    fn shard_path(&self, name: &str) -> Result<PathBuf, EvolveError> {
+        let rel = Path::new(name);
+        let plain = rel.components().all(|c| matches!(c, Component::Normal(_)));
+        if name.is_empty() || !plain {
+            return Err(EvolveError::Snapshot(format!(
+                "shard name {:?} is not a plain relative path",
+                name
+            )));
+        }
+        Ok(self.root.join(rel))
+    }
+
+    /// Copy the current bytes of `shards` into the store; the returned stamp
+    /// is the SHA-256 of the snapshot's canonical JSON.
+    pub fn snapshot(&self, shards: &[String]) -> Result<HexStamp, EvolveError> {
+        let mut snap = ShardSnapshot {
+            shards: BTreeMap::new(),
+        };
+        for name in shards {
+            let bytes = match std::fs::read(self.shard_path(name)?) {
+                Ok(b) => Some(hex::encode(b)),
+                Err(e) if e.kind() == std::io::ErrorKind::NotFound => None,
+                Err(e) => return Err(e.into()),
+            };
+            snap.shards.insert(name.clone(), bytes);
+        }
+        let stamp = snap.stamp();
+        std::fs::create_dir_all(&self.snapshots)?;
+        write_atomic(
+            &self.snapshots.join(format!("{}.json", stamp)),
+            &serde_json::to_vec(&snap)?,
+        )?;
+        Ok(stamp)
+    }
+
+    /// Put every shard in the snapshot back exactly as it was.
+    pub fn restore(&self, stamp: &HexStamp) -> Result<(), EvolveError> {
+        let raw = std::fs::read(self.snapshots.join(format!("{}.json", stamp)))?;
+        let snap: ShardSnapshot = serde_json::from_slice(&raw)?;
+        if snap.stamp() != *stamp {
+            return Err(EvolveError::Snapshot(format!(
+                "snapshot {} does not match its contents",
+                stamp
+            )));
+        }
+        for (name, contents) in &snap.shards {
+            let path = self.shard_path(name)?;
+            match contents {
+                Some(hex_bytes) => {
+                    let bytes = hex::decode(hex_bytes)
+                        .map_err(|e| EvolveError::Snapshot(format!("{}: {}", name, e)))?;
+                    write_atomic(&path, &bytes)?;
+                }
+                None => {
+                    if let Err(e) = std::fs::remove_file(&path) {
+                        if e.kind() != std::io::ErrorKind::NotFound {
+                            return Err(e.into());
+                        }
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn write_shard(&self, name: &str, bytes: &[u8]) -> Result<(), EvolveError> {
+        write_atomic(&self.shard_path(name)?, bytes)
+    }
+}
+
+fn write_atomic(path: &Path, bytes: &[u8]) -> Result<(), EvolveError> {
+    if let Some(dir) = path.parent().filter(|d| !d.as_os_str().is_empty()) {
+        std::fs::create_dir_all(dir)?;
+    }
+    let tmp = path.with_extension("tmp");
+    let mut file = File::create(&tmp)?;
+    file.write_all(bytes)?;
+    file.sync_all()?;
+    std::fs::rename(&tmp, path)?;
+    Ok(())
+}
+
+/// Directory next to `.evolve.jsonl` holding the quorum certificates its
+/// lines name, one `<digest>.json` each.
+pub fn certificate_dir<P: AsRef<Path>>(ledger: P) -> PathBuf {
+    let mut p = ledger.as_ref().as_os_str().to_owned();
+    p.push(".quorum");
+    PathBuf::from(p)
+}
+
+/// The stake roster an `.evolve.jsonl` is governed by, plus the store of
+/// the quorum certificates its lines name by digest.
+#[derive(Clone)]
+pub struct Governance {
+    engine: QuorumEngine,
+    certificates: PathBuf,
+}
+
+impl Governance {
+    pub fn new<P: AsRef<Path>>(engine: QuorumEngine, certificates: P) -> Self {
+        Self {
+            engine,
+            certificates: certificates.as_ref().to_path_buf(),
+        }
+    }
+
+    /// Certificates kept in the [`certificate_dir`] of `ledger`.
+    pub fn for_ledger<P: AsRef<Path>>(engine: QuorumEngine, ledger: P) -> Self {
+        Self::new(engine, certificate_dir(ledger))
+    }
+
+    pub fn engine(&self) -> &QuorumEngine {
+        &self.engine
+    }
+
+    fn check_actor(&self, proposal: &EvolutionProposal) -> Result<(), EvolveError> {
+        let signers = &self.engine.roster().multisig.signers;
+        if signers
+            .iter()
+            .any(|s| s.pubkey.eq_ignore_ascii_case(&proposal.actor))
+        {
+            return Ok(());
+        }
+        Err(EvolveError::UnknownActor {
+            proposal_id: proposal.proposal_id.clone(),
+            actor: proposal.actor.clone(),
+        })
+    }
+
+    /// Store `certificate` once it verifies for `proposal`; the returned
+    /// digest is what the `QuorumReached` line records.
+    pub fn record_certificate(
+        &self,
+        proposal: &EvolutionProposal,
+        certificate: &QuorumCertificate,
+    ) -> Result<HexStamp, EvolveError> {
+        self.engine.verify(proposal, certificate)?;
+        let digest = certificate.digest();
+        write_atomic(
+            &self.certificates.join(format!("{}.json", digest)),
+            &serde_json::to_vec(certificate)?,
+        )?;
+        Ok(digest)
+    }
+
+    /// Load the certificate `proposal` names and re-tally its votes for the
+    /// proposal's content under the roster.
+    pub fn verify_quorum(
+        &self,
+        proposal: &EvolutionProposal,
+    ) -> Result<QuorumCertificate, EvolveError> {
+        let digest = proposal.quorum_certificate.as_ref().ok_or_else(|| {
+            EvolveError::InvalidProposal(format!("{}: no quorum certificate", proposal.proposal_id))
+        })?;
+        if digest.0.len() != 64 || !digest.0.bytes().all(|b| b.is_ascii_hexdigit()) {
+            return Err(EvolveError::InvalidProposal(format!(
+                "{}: quorum certificate {:?} is not a SHA-256 digest",
+                proposal.proposal_id, digest.0
+            )));
+        }
+        let raw = std::fs::read(self.certificates.join(format!("{}.json", digest)))?;
+        let certificate: QuorumCertificate = serde_json::from_slice(&raw)?;
+        if certificate.digest() != *digest {
+            return Err(EvolveError::InvalidProposal(format!(
+                "{}: stored certificate does not match digest {}",
+                proposal.proposal_id, digest
+            )));
+        }
+        self.engine.verify(proposal, &certificate)?;
+        Ok(certificate)
+    }
+}
+
+/// Open `.evolve.jsonl` for replay under `governance` against the head
+/// anchor its journal keeps at `<ledger>.head`. A missing ledger is empty,
+/// which fails any anchor that was ever written.
+pub fn open_anchored<P: AsRef<Path>>(
+    path: P,
+    governance: &Governance,
+) -> Result<EvolveStreamGuard<Box<dyn std::io::Read>>, EvolveError> {
+    let path = path.as_ref();
+    let anchor = Anchor::load(anchor_path(path))?;
+    let inner: Box<dyn std::io::Read> = match File::open(path) {
+        Ok(f) => Box::new(f),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Box::new(std::io::empty()),
+        Err(e) => return Err(e.into()),
+    };
+    Ok(EvolveStreamGuard::anchored(
+        inner,
+        anchor,
+        governance.clone(),
+    ))
+}
+
+fn next_head(head: &Option<Anchor>, hexstamp: &HexStamp) -> Anchor {
+    Anchor {
+        seq: head.as_ref().map_or(0, |h| h.seq + 1),
+        hexstamp: hexstamp.0.clone(),
+    }
+}
+
+/// Append-only `.evolve.jsonl` that only accepts legal, signed transitions,
+/// each chained to the line before it.
+pub struct EvolveJournal {
+    path: PathBuf,
+    file: File,
+    governance: Governance,
+    latest: HashMap<String, EvolutionProposal>,
+    /// Hexstamp and 0-based index of the last line.
+    head: Option<Anchor>,
+}
+
+impl EvolveJournal {
+    /// Open (or create) the journal, replaying it through
+    /// [`EvolveStreamGuard`] under `governance` against its head anchor; a
+    /// journal that does not validate is not extended.
+    pub fn open<P: AsRef<Path>>(path: P, governance: Governance) -> Result<Self, EvolveError> {
+        let path = path.as_ref().to_path_buf();
+        if let Some(dir) = path.parent().filter(|d| !d.as_os_str().is_empty()) {
+            std::fs::create_dir_all(dir)?;
+        }
+        let file = OpenOptions::new()
+            .read(true)
+            .append(true)
+            .create(true)
+            .open(&path)?;
+
+        let mut latest = HashMap::new();
+        let mut head = None;
+        for record in open_anchored(&path, &governance)?.records() {
+            let p = record?.proposal;
+            head = Some(next_head(&head, &p.hexstamp));
+            latest.insert(p.proposal_id.clone(), p);
+        }
+        Ok(Self {
+            path,
+            file,
+            governance,
+            latest,
+            head,
+        })
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// The proposal as of its most recent transition.
+    pub fn latest(&self, proposal_id: &str) -> Option<&EvolutionProposal> {
+        self.latest.get(proposal_id)
+    }
+
+    /// Record a new proposal as `Draft`, signed by `actor`.
+    pub fn propose(
+        &mut self,
+        mut draft: EvolutionProposal,
+        actor: &SigningKey,
+    ) -> Result<EvolutionProposal, EvolveError> {
+        draft.decision = ProposalState::Draft;
+        draft.snapshot = None;
+        self.append_signed(draft, actor)
+    }
+
+    /// Move a proposal to `UnderReview` or `Rejected`. `QuorumReached`
+    /// needs a certificate ([`reach_quorum`](Self::reach_quorum)); `Applied`
+    /// and `RolledBack` touch shards and go through [`apply`](Self::apply)
+    /// and [`roll_back`](Self::roll_back).
+    pub fn advance(
+        &mut self,
+        proposal_id: &str,
+        next: ProposalState,
+        actor: &SigningKey,
+    ) -> Result<EvolutionProposal, EvolveError> {
+        let mut line = self.next_line(proposal_id, next)?;
+        if !matches!(next, ProposalState::UnderReview | ProposalState::Rejected) {
+            return Err(EvolveError::InvalidProposal(format!(
+                "{}: {:?} needs reach_quorum/apply/roll_back",
+                proposal_id, next
+            )));
+        }
+        line.decision = next;
+        self.append_signed(line, actor)
+    }
+
+    /// Record `QuorumReached` once `certificate` re-tallies under the
+    /// journal's roster, keeping the certificate where replay re-tallies it.
+    pub fn reach_quorum(
+        &mut self,
+        proposal_id: &str,
+        certificate: &QuorumCertificate,
+        actor: &SigningKey,
+    ) -> Result<EvolutionProposal, EvolveError> {
+        let mut line = self.next_line(proposal_id, ProposalState::QuorumReached)?;
+        line.decision = ProposalState::QuorumReached;
+        line.quorum_certificate = Some(self.governance.record_certificate(&line, certificate)?);
+        self.append_signed(line, actor)
+    }
+
+    /// Snapshot the affected shards, write `changes`, then record `Applied`;
+    /// if the line cannot be recorded the snapshot is put back, so shards
+    /// never hold content the ledger does not account for. The certificate
+    /// recorded at `QuorumReached` must still re-tally, and `changes` must
+    /// write every shard in `affected_shards`, and nothing else, with
+    /// exactly the content pinned there.
+    pub fn apply(
+        &mut self,
+        proposal_id: &str,
+        store: &ShardStore,
+        changes: &BTreeMap<String, Vec<u8>>,
+        actor: &SigningKey,
+    ) -> Result<EvolutionProposal, EvolveError> {
+        let mut line = self.next_line(proposal_id, ProposalState::Applied)?;
+        self.governance.verify_quorum(&line)?;
+        let pinned: BTreeMap<&str, &str> = line
+            .affected_shards
+            .iter()
+            .map(|a| (a.shard.as_str(), a.sha256.as_str()))
+            .collect();
+        if let Some(name) = changes.keys().find(|n| !pinned.contains_key(n.as_str())) {
+            return Err(EvolveError::InvalidProposal(format!(
+                "{}: shard {} is not in affected_shards",
+                proposal_id, name
+            )));
+        }
+        for (shard, sha256) in &pinned {
+            let written = changes.get(*shard).map(|b| digest_hex(b));
+            if written.as_deref() != Some(*sha256) {
+                return Err(EvolveError::InvalidProposal(format!(
+                    "{}: change to {} is {}, proposal pins sha256:{}",
+                    proposal_id,
+                    shard,
+                    written.map_or("missing".into(), |d| format!("sha256:{}", d)),
+                    sha256
+                )));
+            }
+        }
+        let names: Vec<String> = pinned.keys().map(|n| n.to_string()).collect();
+        let snapshot = store.snapshot(&names)?;
+        line.decision = ProposalState::Applied;
+        line.snapshot = Some(snapshot.clone());
+        let applied = changes
+            .iter()
+            .try_for_each(|(name, bytes)| store.write_shard(name, bytes))
+            .and_then(|()| self.append_signed(line, actor));
+        if applied.is_err() {
+            // The original error is the one worth reporting.
+            let _ = store.restore(&snapshot);
+        }
+        applied
+    }
+
+    /// Restore the shards from the `Applied` snapshot and record `RolledBack`.
+    pub fn roll_back(
+        &mut self,
+        proposal_id: &str,
+        store: &ShardStore,
+        actor: &SigningKey,
+    ) -> Result<EvolutionProposal, EvolveError> {
+        let mut line = self.next_line(proposal_id, ProposalState::RolledBack)?;
+        let snapshot = line.snapshot.clone().ok_or_else(|| {
+            EvolveError::InvalidProposal(format!("{}: applied without a snapshot", proposal_id))
+        })?;
+        store.restore(&snapshot)?;
+        line.decision = ProposalState::RolledBack;
+        self.append_signed(line, actor)
+    }
+
+    fn next_line(
+        &self,
+        proposal_id: &str,
+        next: ProposalState,
+    ) -> Result<EvolutionProposal, EvolveError> {
+        let current = self.latest.get(proposal_id).ok_or_else(|| {
+            EvolveError::InvalidProposal(format!("unknown proposal {}", proposal_id))
+        })?;
+        if !current.decision.can_transition_to(next) {
+            return Err(EvolveError::IllegalTransition {
+                proposal_id: proposal_id.to_string(),
+                from: Some(current.decision),
+                to: next,
+            });
+        }
+        Ok(current.clone())
+    }
+
+    fn append_signed(
+        &mut self,
+        mut line: EvolutionProposal,
+        actor: &SigningKey,
+    ) -> Result<EvolutionProposal, EvolveError> {
+        line.schema_version = EVOLVE_SCHEMA_VERSION;
+        line.prev_hexstamp = self.head.as_ref().map(|h| HexStamp(h.hexstamp.clone()));
+        line.actor = actor_id(actor);
+        line.timestamp_unix = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_secs() as i64)
+            .unwrap_or(0);
+        line.actor_signature = String::new();
+        line.hexstamp = proposal_hexstamp(&line);
+        line.actor_signature = sign_transition(&line.hexstamp, actor);
+
+        let previous = self.latest.get(&line.proposal_id).map(|p| p.decision);
+        check_transition(previous, &line, &self.governance)?;
+
+        let mut bytes = serde_json::to_vec(&line)?;
+        bytes.push(b'\n');
+        self.file.write_all(&bytes)?;
+        self.file.sync_data()?;
+
+        let head = next_head(&self.head, &line.hexstamp);
+        head.store(anchor_path(&self.path))?;
+        self.head = Some(head);
+        self.latest.insert(line.proposal_id.clone(), line.clone());
+        Ok(line)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sovereign_core::{AffectedShard, EvolutionKind, Roh};
+
+    fn key(byte: u8) -> SigningKey {
+        SigningKey::from_slice(&[byte; 32]).unwrap()
+    }
+
+    fn draft(id: &str) -> EvolutionProposal {
+        EvolutionProposal {
+            schema_version: EVOLVE_SCHEMA_VERSION,
+            proposal_id: id.into(),
+            prev_hexstamp: None,
+            subject_id: "bostrom18sd2ujv24ual9c9pshtxys6j8knh6xaead9ye7".into(),
+            description: "tighten vkernel".into(),
+            kind: EvolutionKind::KernelChange,
+            effect_bounds: (Roh(0.0), Roh(0.05)),
+            roh_before: Roh(0.2),
+            roh_after: Roh(0.1),
+            decision: ProposalState::Draft,
+            timestamp_unix: 0,
+            token_kind: "Evolve".into(),
+            class_label: "SOVEREIGNCONFIG".into(),
+            bostrom_tx: String::new(),
+            consent_proofs: Vec::new(),
+            affected_shards: vec![
+                AffectedShard {
+                    shard: "vkernel.aln".into(),
+                    sha256: digest_hex(b"changed"),
+                },
+                AffectedShard {
+                    shard: "new.aln".into(),
+                    sha256: digest_hex(b"fresh"),
+                },
+            ],
+            neurorights_posture: None,
+            sovereign_classification: None,
+            actor: String::new(),
+            actor_signature: String::new(),
+            snapshot: None,
+            quorum_certificate: None,
+            hexstamp: HexStamp(String::new()),
+        }
+    }
+
+    #[test]
+    fn transition_table() {
+        use ProposalState::*;
+        assert!(Draft.can_transition_to(UnderReview));
+        assert!(QuorumReached.can_transition_to(Applied));
+        assert!(Applied.can_transition_to(RolledBack));
+        assert!(!Draft.can_transition_to(Applied));
+        assert!(!Applied.can_transition_to(Rejected));
+        assert!(!RolledBack.can_transition_to(Applied));
+        assert!(!Rejected.can_transition_to(UnderReview));
+    }
+
+    /// Keys 1 to 3 are stakeholders; key 1 alone holds quorum.
+    fn governance(ledger: &Path) -> Governance {
+        let text = format!(
+            r#"StakeRoster {{
+  subject_id: "bostrom18sd2ujv24ual9c9pshtxys6j8knh6xaead9ye7",
+  multisig: {{
+    threshold: 1,
+    signers: [
+      {{ label: "host", pubkey: "{}", stake_points: 10 }},
+      {{ label: "guardian", pubkey: "{}" }},
+      {{ label: "clinic", pubkey: "{}" }}
+    ]
+  }}
+}}"#,
+            actor_id(&key(1)),
+            actor_id(&key(2)),
+            actor_id(&key(3))
+        );
+        let roster = crate::StakeRoster::parse(&text).unwrap();
+        Governance::for_ledger(QuorumEngine::new(roster), ledger)
+    }
+
+    #[test]
+    fn apply_then_roll_back_restores_bytes() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = ShardStore::new(dir.path().join("shards"), dir.path().join("snapshots"));
+        let original = b"ViabilityKernel {\n  roh_global <= 0.3\n}\n".to_vec();
+        std::fs::create_dir_all(dir.path().join("shards")).unwrap();
+        std::fs::write(dir.path().join("shards/vkernel.aln"), &original).unwrap();
+
+        let ledger = dir.path().join(".evolve.jsonl");
+        let governance = governance(&ledger);
+        let mut journal = EvolveJournal::open(&ledger, governance.clone()).unwrap();
+        journal.propose(draft("p1"), &key(1)).unwrap();
+        journal
+            .advance("p1", ProposalState::UnderReview, &key(2))
+            .unwrap();
+        let latest = journal.latest("p1").unwrap();
+        let votes = [crate::Vote::cast(latest, true, &key(1))];
+        let cert = governance.engine().tally(latest, &votes).unwrap();
+        journal.reach_quorum("p1", &cert, &key(2)).unwrap();
+
+        // Only the content the proposal pinned, for every pinned shard.
+        let swapped = BTreeMap::from([
+            ("vkernel.aln".to_string(), b"other".to_vec()),
+            ("new.aln".to_string(), b"fresh".to_vec()),
+        ]);
+        let partial = BTreeMap::from([("new.aln".to_string(), b"fresh".to_vec())]);
+        for bad in [swapped, partial] {
+            assert!(matches!(
+                journal.apply("p1", &store, &bad, &key(1)),
+                Err(EvolveError::InvalidProposal(_))
+            ));
+        }
+        assert_eq!(
+            std::fs::read(dir.path().join("shards/vkernel.aln")).unwrap(),
+            original
+        );
+
+        let changes = BTreeMap::from([
+            ("vkernel.aln".to_string(), b"changed".to_vec()),
+            ("new.aln".to_string(), b"fresh".to_vec()),
+        ]);
+        journal.apply("p1", &store, &changes, &key(1)).unwrap();
+        assert_eq!(
+            std::fs::read(dir.path().join("shards/vkernel.aln")).unwrap(),
+            b"changed"
+        );
+
+        journal.roll_back("p1", &store, &key(3)).unwrap();
+        assert_eq!(
+            std::fs::read(dir.path().join("shards/vkernel.aln")).unwrap(),
+            original
+        );
+        assert!(!dir.path().join("shards/new.aln").exists());
+
+        // The journal replays cleanly and refuses to leave a terminal state.
+        let mut reopened = EvolveJournal::open(&ledger, governance.clone()).unwrap();
+        assert_eq!(
+            reopened.latest("p1").unwrap().decision,
+            ProposalState::RolledBack
+        );
+        assert!(matches!(
+            reopened.advance("p1", ProposalState::UnderReview, &key(1)),
+            Err(EvolveError::IllegalTransition { .. })
+        ));
+
+        // Replay re-tallies the certificates the ledger names.
+        std::fs::remove_dir_all(certificate_dir(&ledger)).unwrap();
+        assert!(EvolveJournal::open(&ledger, governance).is_err());
+    }
+
+    #[test]
+    fn deleted_or_truncated_lines_fail_replay() {
+        let dir = tempfile::tempdir().unwrap();
+        let ledger = dir.path().join(".evolve.jsonl");
+        let governance = governance(&ledger);
+        let mut journal = EvolveJournal::open(&ledger, governance.clone()).unwrap();
+        for id in ["p1", "p2", "p3"] {
+            journal.propose(draft(id), &key(1)).unwrap();
+        }
+        let text = std::fs::read_to_string(&ledger).unwrap();
+        let lines: Vec<&str> = text.lines().collect();
+
+        std::fs::write(&ledger, format!("{}\n{}\n", lines[0], lines[2])).unwrap();
+        assert!(matches!(
+            EvolveJournal::open(&ledger, governance.clone()),
+            Err(EvolveError::BrokenChain { .. })
+        ));
+
+        std::fs::write(&ledger, format!("{}\n{}\n", lines[0], lines[1])).unwrap();
+        assert!(matches!(
+            EvolveJournal::open(&ledger, governance.clone()),
+            Err(EvolveError::Truncated {
+                anchor_seq: 2,
+                lines: 2
+            })
+        ));
+
+        std::fs::remove_file(&ledger).unwrap();
+        assert!(matches!(
+            crate::EvolveBudget::replay_path(&ledger, &governance, 10.0, 0),
+            Err(EvolveError::Truncated { .. })
+        ));
+    }
+
+    #[test]
+    fn guard_rejects_skipped_state_and_forged_or_foreign_actor() {
+        let governance = governance(Path::new(".evolve.jsonl"));
+        let mut p = draft("p2");
+        p.actor = actor_id(&key(1));
+        p.hexstamp = proposal_hexstamp(&p);
+        p.actor_signature = sign_transition(&p.hexstamp, &key(1));
+        assert!(check_transition(None, &p, &governance).is_ok());
+
+        let mut skip = p.clone();
+        skip.decision = ProposalState::QuorumReached;
+        skip.hexstamp = proposal_hexstamp(&skip);
+        skip.actor_signature = sign_transition(&skip.hexstamp, &key(1));
+        assert!(matches!(
+            check_transition(Some(ProposalState::Draft), &skip, &governance),
+            Err(EvolveError::IllegalTransition { .. })
+        ));
+
+        let mut forged = p.clone();
+        forged.actor_signature = sign_transition(&forged.hexstamp, &key(9));
+        assert!(matches!(
+            check_transition(None, &forged, &governance),
+            Err(EvolveError::BadActorSignature { .. })
+        ));
+
+        // A well-signed line from a key outside the roster.
+        let mut foreign = p.clone();
+        foreign.actor = actor_id(&key(9));
+        foreign.hexstamp = proposal_hexstamp(&foreign);
+        foreign.actor_signature = sign_transition(&foreign.hexstamp, &key(9));
+        assert!(matches!(
+            check_transition(None, &foreign, &governance),
+            Err(EvolveError::UnknownActor { .. })
+        ));
+
+        // A QuorumReached line naming a certificate nobody stored.
+        let mut unproven = p.clone();
+        unproven.decision = ProposalState::QuorumReached;
+        unproven.quorum_certificate = Some(HexStamp("cc".repeat(32)));
+        unproven.hexstamp = proposal_hexstamp(&unproven);
+        unproven.actor_signature = sign_transition(&unproven.hexstamp, &key(1));
+        assert!(
+            check_transition(Some(ProposalState::UnderReview), &unproven, &governance).is_err()
+        );
+    }
+}
@@ -0,0 +1,561 @@
+//! Stake-weighted governance quorum over EVOLVE proposals.
+//!
+//! Stakeholders come from `.stake.aln` (keys, weights, roles, veto powers),
+//! with the host's own weight taken from its `CitizenStake`. Votes are
+//! secp256k1 signatures over a ballot naming the proposal's content digest,
+//! so a vote for one proposal never counts toward an edited one.
+
+use k256::ecdsa::SigningKey;
+use policy_bundle::recoverable;
+use serde::{Deserialize, Serialize};
+use sovereign_core::capabilities::SovereignClassification;
+use sovereign_core::{
+    AffectedShard, EvolutionKind, EvolutionProposal, HexStamp, NeurorightsPosture,
+};
+use std::collections::{BTreeMap, BTreeSet};
+
+use crate::actor_id;
+use crate::lifecycle::recover_signer;
+
+/// Veto powers are spelled `BLOCK_EVOLVE_<CLASS>`; `BLOCK_EVOLVE_ALL` blocks
+/// every class.
+pub const VETO_PREFIX: &str = "BLOCK_EVOLVE_";
+
+/// Node classes and roles the workspace manifest forbids as stakeholders.
+const NON_STAKEHOLDER_MARKERS: &[&str] = &["urban", "authority", "smart_city", "smartcity"];
+
+#[derive(Debug, thiserror::Error)]
+pub enum QuorumError {
+    #[error("stake roster is invalid: {0}")]
+    Roster(String),
+    #[error("stakeholder {label} is an urban or authority node and cannot hold stake")]
+    ForbiddenStakeholder { label: String },
+    #[error("vote {index} is malformed: {message}")]
+    MalformedVote { index: usize, message: String },
+    #[error("vote {index} is for {found}, not proposal {expected}")]
+    WrongProposal {
+        index: usize,
+        expected: String,
+        found: String,
+    },
+    #[error("vote {index} is from {pubkey}, who is not a stakeholder")]
+    UnknownVoter { index: usize, pubkey: String },
+    #[error("stakeholder {label} voted more than once")]
+    DuplicateVote { label: String },
+    #[error("{label} vetoed the proposal with {power}")]
+    Vetoed { label: String, power: String },
+    #[error("{approved} of {total} stake points approve, quorum is {required}")]
+    BelowQuorum {
+        approved: u64,
+        total: u64,
+        required: u64,
+    },
+    #[error("certificate does not match the tally it claims: {0}")]
+    CertificateMismatch(String),
+}
+
+/// One `.stake.aln` signer, with the governance fields the quorum needs.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Stakeholder {
+    pub label: String,
+    /// SEC1-compressed secp256k1 public key, hex (33 bytes).
+    pub pubkey: String,
+    #[serde(default)]
+    pub node_class: String,
+    #[serde(default)]
+    pub stake_points: u64,
+    #[serde(default)]
+    pub roles: Vec<String>,
+    #[serde(default)]
+    pub veto_powers: Vec<String>,
+}
+
+impl Stakeholder {
+    fn is_forbidden(&self) -> bool {
+        std::iter::once(&self.node_class)
+            .chain(self.roles.iter())
+            .map(|s| s.to_ascii_lowercase())
+            .any(|s| NON_STAKEHOLDER_MARKERS.iter().any(|m| s.contains(m)))
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RosterMultisig {
+    pub threshold: u32,
+    pub signers: Vec<Stakeholder>,
+    /// Approving stake points required; defaults to a strict majority.
+    #[serde(default)]
+    pub quorum_points: Option<u64>,
+}
+
+/// `.stake.aln` read as a weighted governance roster.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StakeRoster {
+    pub subject_id: String,
+    pub multisig: RosterMultisig,
+}
+
+impl StakeRoster {
+    pub fn parse(text: &str) -> Result<Self, QuorumError> {
+        let roster: StakeRoster =
+            aln_format::from_str(text).map_err(|e| QuorumError::Roster(e.to_string()))?;
+        roster.validate()?;
+        Ok(roster)
+    }
+
+    /// Fold the host's `CitizenStake` into its stake.aln entry.
+    pub fn with_citizen_stake(
+        mut self,
+        pubkey: &str,
+        stake_points: u64,
+        roles: &[String],
+        veto_powers: &[String],
+    ) -> Result<Self, QuorumError> {
+        let host = self
+            .multisig
+            .signers
+            .iter_mut()
+            .find(|s| s.pubkey.eq_ignore_ascii_case(pubkey))
+            .ok_or_else(|| QuorumError::Roster(format!("no stake.aln signer {}", pubkey)))?;
+        host.stake_points = stake_points;
+        host.roles = roles.to_vec();
+        host.veto_powers = veto_powers.to_vec();
+        self.validate()?;
+        Ok(self)
+    }
+
+    fn validate(&self) -> Result<(), QuorumError> {
+        let m = &self.multisig;
+        if m.threshold == 0 || m.threshold as usize > m.signers.len() {
+            return Err(QuorumError::Roster(format!(
+                "threshold {} is not satisfiable by {} signers",
+                m.threshold,
+                m.signers.len()
+            )));
+        }
+        let mut keys = BTreeSet::new();
+        for s in &m.signers {
+            if s.is_forbidden() {
+                return Err(QuorumError::ForbiddenStakeholder {
+                    label: s.label.clone(),
+                });
+            }
+            if !keys.insert(s.pubkey.to_ascii_lowercase()) {
+                return Err(QuorumError::Roster(format!("duplicate signer {}", s.pubkey)));
+            }
+        }
+        if self.total_points() == 0 {
+            return Err(QuorumError::Roster("no stake points assigned".into()));
+        }
+        if self.required_points() > self.total_points() {
+            return Err(QuorumError::Roster(format!(
+                "quorum {} exceeds total stake {}",
+                self.required_points(),
+                self.total_points()
+            )));
+        }
+        Ok(())
+    }
+
+    pub fn total_points(&self) -> u64 {
+        self.multisig.signers.iter().map(|s| s.stake_points).sum()
+    }
+
+    pub fn required_points(&self) -> u64 {
+        self.multisig
+            .quorum_points
+            .unwrap_or(self.total_points() / 2 + 1)
+    }
+
+    /// SHA-256 of the roster's canonical JSON; certificates name the roster
+    /// they were tallied against.
+    pub fn digest(&self) -> HexStamp {
+        HexStamp(aln_format::canonical_hexstamp(self, &[]).expect("roster serializes"))
+    }
+}
+
+/// Proposal classes a veto power can name: one for the kind, one per
+/// affected shard (`nanoswarm.aln` -> `NANOSWARM`).
+pub fn proposal_classes(proposal: &EvolutionProposal) -> BTreeSet<String> {
+    let kind = match proposal.kind {
+        EvolutionKind::QPolicyUpdate => "QPOLICY",
+        EvolutionKind::BioScaleUpgrade => "BIOSCALE",
+        EvolutionKind::ModeShift => "MODESHIFT",
+        EvolutionKind::KernelChange => "KERNEL",
+    };
+    let mut classes = BTreeSet::from([kind.to_string()]);
+    for AffectedShard { shard, .. } in &proposal.affected_shards {
+        let file = shard.rsplit('/').next().unwrap_or(shard);
+        let stem = file.split('.').find(|s| !s.is_empty()).unwrap_or(file);
+        classes.insert(stem.to_ascii_uppercase().replace('-', "_"));
+    }
+    classes
+}
+
+/// Digest of what a proposal would change, for whom and why, including the
+/// content each affected shard is pinned to and the rights and
+/// classification it commits to; independent of its lifecycle
+/// state, actor and snapshot. This is what stakeholders vote on.
+pub fn proposal_content_digest(proposal: &EvolutionProposal) -> HexStamp {
+    #[derive(Serialize)]
+    struct Content<'a> {
+        proposal_id: &'a str,
+        subject_id: &'a str,
+        description: &'a str,
+        class_label: &'a str,
+        kind: &'a EvolutionKind,
+        effect_bounds: (f32, f32),
+        roh_before: f32,
+        roh_after: f32,
+        token_kind: &'a str,
+        affected_shards: &'a [AffectedShard],
+        neurorights_posture: &'a Option<NeurorightsPosture>,
+        sovereign_classification: &'a Option<SovereignClassification>,
+    }
+    let content = Content {
+        proposal_id: &proposal.proposal_id,
+        subject_id: &proposal.subject_id,
+        description: &proposal.description,
+        class_label: &proposal.class_label,
+        kind: &proposal.kind,
+        effect_bounds: (proposal.effect_bounds.0 .0, proposal.effect_bounds.1 .0),
+        roh_before: proposal.roh_before.0,
+        roh_after: proposal.roh_after.0,
+        token_kind: &proposal.token_kind,
+        affected_shards: &proposal.affected_shards,
+        neurorights_posture: &proposal.neurorights_posture,
+        sovereign_classification: &proposal.sovereign_classification,
+    };
+    HexStamp(aln_format::canonical_hexstamp(&content, &[]).expect("proposal content serializes"))
+}
+
+/// One stakeholder's signed approval or rejection.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Vote {
+    pub proposal_id: String,
+    pub content_digest: HexStamp,
+    pub approve: bool,
+    pub voter: String,
+    /// Hex `r || s || recovery_id` over [`Vote::ballot_bytes`].
+    pub signature: String,
+}
+
+impl Vote {
+    pub fn cast(proposal: &EvolutionProposal, approve: bool, key: &SigningKey) -> Self {
+        let mut vote = Vote {
+            proposal_id: proposal.proposal_id.clone(),
+            content_digest: proposal_content_digest(proposal),
+            approve,
+            voter: actor_id(key),
+            signature: String::new(),
+        };
+        vote.signature = recoverable::sign(&vote.ballot_bytes(), key);
+        vote
+    }
+
+    /// Canonical JSON of everything but the signature.
+    pub fn ballot_bytes(&self) -> Vec<u8> {
+        #[derive(Serialize)]
+        struct Ballot<'a> {
+            proposal_id: &'a str,
+            content_digest: &'a str,
+            approve: bool,
+            voter: &'a str,
+        }
+        aln_format::to_canonical_json(&Ballot {
+            proposal_id: &self.proposal_id,
+            content_digest: &self.content_digest.0,
+            approve: self.approve,
+            voter: &self.voter,
+        })
+        .expect("ballot serializes")
+        .into_bytes()
+    }
+}
+
+/// Proof that a proposal's content reached quorum under one roster. Anyone
+/// holding the roster can re-tally the embedded votes with
+/// [`QuorumEngine::verify`].
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct QuorumCertificate {
+    pub proposal_id: String,
+    pub content_digest: HexStamp,
+    pub roster_digest: HexStamp,
+    pub approved_points: u64,
+    pub total_points: u64,
+    pub required_points: u64,
+    pub votes: Vec<Vote>,
+}
+
+impl QuorumCertificate {
+    /// SHA-256 of the certificate's canonical JSON, recorded on the
+    /// `QuorumReached` and `Applied` lines.
+    pub fn digest(&self) -> HexStamp {
+        HexStamp(aln_format::canonical_hexstamp(self, &[]).expect("certificate serializes"))
+    }
+}
+
+/// Tallies signed votes against a [`StakeRoster`].
+#[derive(Clone)]
+pub struct QuorumEngine {
+    roster: StakeRoster,
+}
+
+impl QuorumEngine {
+    pub fn new(roster: StakeRoster) -> Self {
+        Self { roster }
+    }
+
+    pub fn roster(&self) -> &StakeRoster {
+        &self.roster
+    }
+
+    /// Weighted tally. Any rejection from a stakeholder holding a veto power
+    /// for one of the proposal's classes fails the proposal outright.
+    pub fn tally(
+        &self,
+        proposal: &EvolutionProposal,
+        votes: &[Vote],
+    ) -> Result<QuorumCertificate, QuorumError> {
+        let content_digest = proposal_content_digest(proposal);
+        let classes = proposal_classes(proposal);
+        let mut seen: BTreeMap<usize, bool> = BTreeMap::new();
+
+        for (index, vote) in votes.iter().enumerate() {
+            if vote.proposal_id != proposal.proposal_id || vote.content_digest != content_digest {
+                return Err(QuorumError::WrongProposal {
+                    index,
+                    expected: format!("{} @ {}", proposal.proposal_id, content_digest),
+                    found: format!("{} @ {}", vote.proposal_id, vote.content_digest),
+                });
+            }
+            let signer = recover_signer(&vote.ballot_bytes(), &vote.signature)
+                .map_err(|message| QuorumError::MalformedVote { index, message })?;
+            if !signer.eq_ignore_ascii_case(&vote.voter) {
+                return Err(QuorumError::MalformedVote {
+                    index,
+                    message: format!("signed by {}, not voter {}", signer, vote.voter),
+                });
+            }
+            let at = self
+                .roster
+                .multisig
+                .signers
+                .iter()
+                .position(|s| s.pubkey.eq_ignore_ascii_case(&signer))
+                .ok_or(QuorumError::UnknownVoter {
+                    index,
+                    pubkey: signer,
+                })?;
+            let holder = &self.roster.multisig.signers[at];
+            if seen.insert(at, vote.approve).is_some() {
+                return Err(QuorumError::DuplicateVote {
+                    label: holder.label.clone(),
+                });
+            }
+            if !vote.approve {
+                let veto = holder.veto_powers.iter().find(|p| {
+                    p.strip_prefix(VETO_PREFIX)
+                        .is_some_and(|class| class == "ALL" || classes.contains(class))
+                });
+                if let Some(power) = veto {
+                    return Err(QuorumError::Vetoed {
+                        label: holder.label.clone(),
+                        power: power.clone(),
+                    });
+                }
+            }
+        }
+
+        let signers = &self.roster.multisig.signers;
+        let approvers: Vec<usize> = seen
+            .iter()
+            .filter(|(_, approve)| **approve)
+            .map(|(i, _)| *i)
+            .collect();
+        let approved = approvers.iter().map(|i| signers[*i].stake_points).sum();
+        let required = self.roster.required_points();
+        if approved < required || approvers.len() < self.roster.multisig.threshold as usize {
+            return Err(QuorumError::BelowQuorum {
+                approved,
+                total: self.roster.total_points(),
+                required,
+            });
+        }
+
+        Ok(QuorumCertificate {
+            proposal_id: proposal.proposal_id.clone(),
+            content_digest,
+            roster_digest: self.roster.digest(),
+            approved_points: approved,
+            total_points: self.roster.total_points(),
+            required_points: required,
+            votes: votes.to_vec(),
+        })
+    }
+
+    /// Re-tally a certificate's votes for `proposal` under this roster and
+    /// check the result matches what the certificate claims.
+    pub fn verify(
+        &self,
+        proposal: &EvolutionProposal,
+        certificate: &QuorumCertificate,
+    ) -> Result<(), QuorumError> {
+        if certificate.roster_digest != self.roster.digest() {
+            return Err(QuorumError::CertificateMismatch(format!(
+                "tallied under roster {}, current roster is {}",
+                certificate.roster_digest,
+                self.roster.digest()
+            )));
+        }
+        let recomputed = self.tally(proposal, &certificate.votes)?;
+        if recomputed.digest() != certificate.digest() {
+            return Err(QuorumError::CertificateMismatch(
+                "recorded totals differ from the votes".into(),
+            ));
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sovereign_core::{ProposalState, Roh};
+
+    fn key(byte: u8) -> SigningKey {
+        SigningKey::from_slice(&[byte; 32]).unwrap()
+    }
+
+    fn roster() -> StakeRoster {
+        let text = format!(
+            r#"StakeRoster {{
+  subject_id: "bostrom18sd2ujv24ual9c9pshtxys6j8knh6xaead9ye7",
+  multisig: {{
+    threshold: 2,
+    signers: [
+      {{ label: "host", pubkey: "{}", node_class: "citizen" }},
+      {{ label: "guardian", pubkey: "{}", node_class: "citizen", stake_points: 30 }},
+      {{ label: "clinic", pubkey: "{}", node_class: "citizen", stake_points: 20,
+         veto_powers: ["BLOCK_EVOLVE_NANOSWARM"] }}
+    ]
+  }}
+}}"#,
+            actor_id(&key(1)),
+            actor_id(&key(2)),
+            actor_id(&key(3))
+        );
+        StakeRoster::parse(&text)
+            .unwrap()
+            .with_citizen_stake(&actor_id(&key(1)), 50, &["Host".into()], &[])
+            .unwrap()
+    }
+
+    fn proposal(shards: &[&str]) -> EvolutionProposal {
+        EvolutionProposal {
+            schema_version: sovereign_core::EVOLVE_SCHEMA_VERSION,
+            proposal_id: "p1".into(),
+            prev_hexstamp: None,
+            subject_id: "bostrom18sd2ujv24ual9c9pshtxys6j8knh6xaead9ye7".into(),
+            description: String::new(),
+            kind: EvolutionKind::QPolicyUpdate,
+            effect_bounds: (Roh(0.0), Roh(0.05)),
+            roh_before: Roh(0.2),
+            roh_after: Roh(0.1),
+            decision: ProposalState::UnderReview,
+            timestamp_unix: 0,
+            token_kind: "Evolve".into(),
+            class_label: "SOVEREIGNCONFIG".into(),
+            bostrom_tx: String::new(),
+            consent_proofs: Vec::new(),
+            affected_shards: shards
+                .iter()
+                .map(|s| AffectedShard {
+                    shard: s.to_string(),
+                    sha256: "00".repeat(32),
+                })
+                .collect(),
+            neurorights_posture: None,
+            sovereign_classification: None,
+            actor: String::new(),
+            actor_signature: String::new(),
+            snapshot: None,
+            quorum_certificate: None,
+            hexstamp: HexStamp(String::new()),
+        }
+    }
+
+    #[test]
+    fn weighted_quorum_issues_verifiable_certificate() {
+        let engine = QuorumEngine::new(roster());
+        let p = proposal(&["qpolicy.aln"]);
+
+        let short = [Vote::cast(&p, true, &key(2)), Vote::cast(&p, true, &key(3))];
+        assert!(matches!(
+            engine.tally(&p, &short),
+            Err(QuorumError::BelowQuorum { approved: 50, .. })
+        ));
+
+        let votes = [Vote::cast(&p, true, &key(1)), Vote::cast(&p, true, &key(3))];
+        let cert = engine.tally(&p, &votes).unwrap();
+        assert_eq!(cert.approved_points, 70);
+        engine.verify(&p, &cert).unwrap();
+
+        let mut edited = p.clone();
+        edited.roh_after = Roh(0.25);
+        assert!(engine.verify(&edited, &cert).is_err());
+        let mut edited = p.clone();
+        edited.description = "loosen the ceiling".into();
+        assert!(engine.verify(&edited, &cert).is_err());
+        let mut edited = p.clone();
+        edited.affected_shards[0].sha256 = "11".repeat(32);
+        assert!(engine.verify(&edited, &cert).is_err());
+    }
+
+    #[test]
+    fn veto_power_blocks_matching_class_only() {
+        let engine = QuorumEngine::new(roster());
+        let swarm = proposal(&["nanoswarm.aln"]);
+        let votes = [
+            Vote::cast(&swarm, true, &key(1)),
+            Vote::cast(&swarm, true, &key(2)),
+            Vote::cast(&swarm, false, &key(3)),
+        ];
+        assert!(matches!(
+            engine.tally(&swarm, &votes),
+            Err(QuorumError::Vetoed { .. })
+        ));
+
+        let other = proposal(&["qpolicy.aln"]);
+        let votes = [
+            Vote::cast(&other, true, &key(1)),
+            Vote::cast(&other, true, &key(2)),
+            Vote::cast(&other, false, &key(3)),
+        ];
+        assert!(engine.tally(&other, &votes).is_ok());
+    }
+
+    #[test]
+    fn urban_and_authority_nodes_cannot_hold_stake() {
+        let text = format!(
+            r#"StakeRoster {{
+  subject_id: "bostrom18sd2ujv24ual9c9pshtxys6j8knh6xaead9ye7",
+  multisig: {{
+    threshold: 1,
+    signers: [
+      {{ label: "host", pubkey: "{}", stake_points: 10 }},
+      {{ label: "city", pubkey: "{}", node_class: "URBAN_INFRA", stake_points: 10 }}
+    ]
+  }}
+}}"#,
+            actor_id(&key(1)),
+            actor_id(&key(2))
+        );
+        assert!(matches!(
+            StakeRoster::parse(&text),
+            Err(QuorumError::ForbiddenStakeholder { .. })
+        ));
+    }
+}
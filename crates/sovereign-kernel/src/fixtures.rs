@@ -0,0 +1,77 @@
+//! A complete on-disk neuro-workspace for tests, here and in crates that
+//! boot from one (`sovereign-boundary`). Enabled by the `fixtures` feature.
+
+use std::fs;
+use std::path::Path;
+
+use crate::workspace::MANIFEST_PATH;
+
+/// Write `rel` under `dir`, owner-only so boot audits see a clean node.
+pub fn write(dir: &Path, rel: &str, text: &str) {
+    let path = dir.join(rel);
+    fs::create_dir_all(path.parent().unwrap()).unwrap();
+    fs::write(&path, text).unwrap();
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        fs::set_permissions(&path, fs::Permissions::from_mode(0o600)).unwrap();
+    }
+}
+
+/// Manifest for `subject` plus every SOVEREIGNCONFIG shard it names and the
+/// biospec `.ocpuenv` / `.lifeforce.aln` pair, all under `dir`.
+pub fn write_workspace(dir: &Path, subject: &str) {
+    write(
+        dir,
+        MANIFEST_PATH,
+        &format!(
+            r#"NeuroWorkspaceManifest {{
+              subjectid: "{}",
+              shardsroot: "shards/root", shardsbiospec: "shards/biospec",
+              shardsneuro: "shards/neuro", shardsmodel: "shards/model",
+              shardsledger: "shards/ledger",
+              sovereign_config: {{
+                rohmodel: "shards/root/rohmodel.aln", stake: "shards/root/stake.aln",
+                neurorights: "shards/root/neurorights.json", smart: "shards/root/smart.json",
+                evolve_token: "shards/root/evolve-token.json",
+                tsafe: "shards/root/tsafe.aln", vkernel: "shards/root/vkernel.aln",
+              }},
+              network_policies: {{}},
+            }}"#,
+            subject
+        ),
+    );
+    write(
+        dir,
+        "shards/root/rohmodel.aln",
+        "RohModel { ceiling: 0.3, weights: {} }",
+    );
+    write(dir, "shards/root/stake.aln", "Stake { threshold: 2 }");
+    write(
+        dir,
+        "shards/root/neurorights.json",
+        r#"{"mentalPrivacy": true, "mentalIntegrity": true, "cognitiveLiberty": true,
+            "noncommercialNeuralData": true, "soulnontradeable": true,
+            "dreamstateSensitive": true, "forbidDecisionUse": true,
+            "minExplanationTokens": 64, "domainSensitive": true,
+            "allowDisableTelemetry": false}"#,
+    );
+    write(dir, "shards/root/smart.json", "{}");
+    write(dir, "shards/root/evolve-token.json", "{}");
+    write(dir, "shards/root/tsafe.aln", "TsafeKernel { axes: [] }");
+    write(
+        dir,
+        "shards/root/vkernel.aln",
+        "ViabilityKernel { constraints: [] }",
+    );
+    write(
+        dir,
+        "shards/biospec/host.ocpuenv",
+        "OcpuEnv { maxThreads: 4, maxMemoryBytes: 1073741824, maxDutyCyclePercent: 60.0 }",
+    );
+    write(
+        dir,
+        "shards/biospec/host.lifeforce.aln",
+        "RuntimeMetrics { currentDutyCycle: 12.5, fatigueIndex: 0.2, ecoImpact: 0.1 }",
+    );
+}
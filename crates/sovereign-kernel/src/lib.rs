@@ -1,10 +1,17 @@
 //! Sovereign-kernel: compiles injector prefixes, LLM params, and system tuning
 //! into a single neurorights-aware SovereignConfig for Rust-Party / NeuroPC.
 
+#[cfg(any(test, feature = "fixtures"))]
+pub mod fixtures;
+pub mod workspace;
+
+use std::path::Path;
+
 use serde::{Deserialize, Serialize};
 use sovereign_types::{
     Envelopes, NeurorightsPolicy, RoHModel, RuntimeMetrics, SovereignError, WorkspaceRef, OcpuEnv,
 };
+use workspace::{load_envelopes, load_neurorights, load_roh_model, Workspace};
 
 /// Injector roles derived from injector-prefix::[--role:...].
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -168,10 +175,20 @@ pub struct SovereignConfig {
 
 impl SovereignConfig {
     /// Top-level constructor: compiles prefixes, params, tuning into a safe config.
+    /// Workspaces are looked up under [`Workspace::default_root`].
     pub fn new(request: SovereignConfigRequest) -> Result<Self, SovereignError> {
-        let nr = load_neurorights(&request.workspace_ref)?;
-        let roh = load_roh_model(&request.workspace_ref)?;
-        let envelopes = load_envelopes(&request.workspace_ref)?;
+        Self::from_workspaces(request, &Workspace::default_root())
+    }
+
+    /// As [`SovereignConfig::new`], resolving `workspace_ref` under `root`.
+    pub fn from_workspaces(
+        request: SovereignConfigRequest,
+        root: &Path,
+    ) -> Result<Self, SovereignError> {
+        let ws = Workspace::resolve(root, &request.workspace_ref)?;
+        let nr = load_neurorights(&ws)?;
+        let roh = load_roh_model(&ws)?;
+        let envelopes = load_envelopes(&ws)?;
 
         let session =
             SovereignSessionConfig::build(request.session, &nr, &roh, &envelopes.ocpuenv)?;
@@ -194,46 +211,3 @@ impl SovereignConfig {
         })
     }
 }
-
-/// Stubbed loaders: in production, bind to ALN/JSONL parsers and filesystem.
-fn load_neurorights(ws: &WorkspaceRef) -> Result<NeurorightsPolicy, SovereignError> {
-    let _ = ws;
-    // Replace with real loading from .neurorights.json.
-    Ok(NeurorightsPolicy {
-        mental_privacy: true,
-        mental_integrity: true,
-        cognitive_liberty: true,
-        noncommercial_neural_data: true,
-        soulnontradeable: true,
-        dreamstate_sensitive: true,
-        forbid_decision_use: false,
-        min_explanation_tokens: 32,
-        domain_sensitive: true,
-        allow_disable_telemetry: false,
-    })
-}
-
-fn load_roh_model(ws: &WorkspaceRef) -> Result<RoHModel, SovereignError> {
-    let _ = ws;
-    // Replace with real loading from .rohmodel.aln.
-    Ok(RoHModel {
-        global_ceiling: sovereign_types::RoH(0.3),
-    })
-}
-
-fn load_envelopes(ws: &WorkspaceRef) -> Result<Envelopes, SovereignError> {
-    let _ = ws;
-    // Replace with real loading from .ocpuenv, .lifeforce.aln, etc.
-    Ok(Envelopes {
-        ocpuenv: OcpuEnv {
-            max_threads: 8,
-            max_memory_bytes: 2 * 1024 * 1024 * 1024,
-            max_duty_cycle_percent: 80.0,
-        },
-        runtime_metrics: RuntimeMetrics {
-            current_duty_cycle: 10.0,
-            fatigue_index: 0.1,
-            eco_impact: 0.1,
-        },
-    })
-}
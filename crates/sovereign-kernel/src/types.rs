@@ -1,4 +1,6 @@
+use evolve_token_verifier::{EvolveGated, EvolveToken};
 use serde::{Deserialize, Serialize};
+use subject_id::SubjectId;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum SovereignActionKind {
@@ -14,12 +16,36 @@ pub enum SovereignActionKind {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SovereignAction {
     pub kind: SovereignActionKind,
-    pub subject_id: String,       // Bostrom address
+    pub subject_id: SubjectId,    // Bostrom address, checksummed on deserialize
     pub route: String,            // "BCI" | "OTA" | "GOV" | "CHAT" | "INFRA"
     pub requested_fields: Vec<String>,
     pub lifeforce_cost: f32,
     pub roh_before: f32,
     pub roh_after_estimate: f32,
+    /// EVOLVE token authorizing structural / OTA actions.
+    #[serde(default)]
+    pub evolve_token: Option<EvolveToken>,
+}
+
+impl EvolveGated for SovereignAction {
+    fn requires_evolve_token(&self) -> bool {
+        matches!(
+            self.kind,
+            SovereignActionKind::ProposeEvolve | SovereignActionKind::ApplyOta
+        )
+    }
+
+    fn evolve_token(&self) -> Option<&EvolveToken> {
+        self.evolve_token.as_ref()
+    }
+
+    fn evolve_scope(&self) -> String {
+        format!("{:?}", self.kind)
+    }
+
+    fn roh_span(&self) -> (f32, f32) {
+        (self.roh_before, self.roh_after_estimate)
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -0,0 +1,212 @@
+//! Resolve a `WorkspaceRef` to its neuro-workspace manifest and load the
+//! SOVEREIGNCONFIG and biospec shards it points at.
+//!
+//! Layout: `<root>/<workspace_id>/neuroworkspace/neuro-workspace.manifest.aln`,
+//! with every manifest path relative to `<root>/<workspace_id>`. Any missing
+//! or unparsable shard is a `SovereignError::WorkspaceError`; nothing falls
+//! back to built-in values.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use serde::Deserialize;
+use sovereign_types::{
+    Envelopes, NeuroWorkspaceManifest, NeurorightsPolicy, OcpuEnv, RoH, RoHModel, RuntimeMetrics,
+    SovereignError, WorkspaceRef,
+};
+
+/// Environment variable naming the directory that holds workspaces.
+pub const WORKSPACES_ROOT_ENV: &str = "RUST_PARTY_WORKSPACES";
+
+pub(crate) const MANIFEST_PATH: &str = "neuroworkspace/neuro-workspace.manifest.aln";
+
+fn ws_err(path: &Path, message: impl std::fmt::Display) -> SovereignError {
+    SovereignError::WorkspaceError(format!("{}: {}", path.display(), message))
+}
+
+/// A resolved workspace: its directory and parsed manifest.
+#[derive(Debug, Clone)]
+pub struct Workspace {
+    pub dir: PathBuf,
+    pub manifest: NeuroWorkspaceManifest,
+}
+
+impl Workspace {
+    /// Workspaces root from `RUST_PARTY_WORKSPACES`, else the working directory.
+    pub fn default_root() -> PathBuf {
+        std::env::var_os(WORKSPACES_ROOT_ENV)
+            .map(PathBuf::from)
+            .unwrap_or_else(|| PathBuf::from("."))
+    }
+
+    /// Find and parse the manifest for `ws`, check it belongs to the same
+    /// subject, and check every SOVEREIGNCONFIG shard it names is present
+    /// and parses.
+    pub fn resolve(root: &Path, ws: &WorkspaceRef) -> Result<Self, SovereignError> {
+        if ws.workspace_id.is_empty()
+            || ws.workspace_id.contains(['/', '\\'])
+            || ws.workspace_id.starts_with('.')
+        {
+            return Err(SovereignError::WorkspaceError(format!(
+                "invalid workspace id `{}`",
+                ws.workspace_id
+            )));
+        }
+        let dir = root.join(&ws.workspace_id);
+        let manifest_path = dir.join(MANIFEST_PATH);
+        let manifest: NeuroWorkspaceManifest =
+            aln_format::from_path(&manifest_path).map_err(|e| ws_err(&manifest_path, e))?;
+        if manifest.subject_id != ws.subject_id {
+            return Err(ws_err(
+                &manifest_path,
+                format!(
+                    "manifest is for subject {}, not {}",
+                    manifest.subject_id, ws.subject_id
+                ),
+            ));
+        }
+
+        let workspace = Self { dir, manifest };
+        let cfg = &workspace.manifest.sovereign_config;
+        for rel in [
+            &cfg.rohmodel,
+            &cfg.neurorights,
+            &cfg.tsafe,
+            &cfg.vkernel,
+            &cfg.stake,
+            &cfg.smart,
+            &cfg.evolve_token,
+        ] {
+            workspace.check_shard(rel)?;
+        }
+        Ok(workspace)
+    }
+
+    pub fn path(&self, rel: &str) -> PathBuf {
+        self.dir.join(rel)
+    }
+
+    /// Read a shard as text; a missing shard is an error, never a default.
+    fn read(&self, rel: &str) -> Result<(PathBuf, String), SovereignError> {
+        let path = self.path(rel);
+        let text = fs::read_to_string(&path).map_err(|e| ws_err(&path, e))?;
+        Ok((path, text))
+    }
+
+    /// `.json` shards must be JSON, everything else ALN.
+    fn check_shard(&self, rel: &str) -> Result<(), SovereignError> {
+        let (path, text) = self.read(rel)?;
+        if rel.ends_with(".json") {
+            serde_json::from_str::<serde_json::Value>(&text).map_err(|e| ws_err(&path, e))?;
+        } else {
+            aln_format::parse(&text).map_err(|e| ws_err(&path, e))?;
+        }
+        Ok(())
+    }
+
+    /// The single file in the biospec shard dir whose name ends in `suffix`.
+    fn biospec_shard(&self, suffix: &str) -> Result<PathBuf, SovereignError> {
+        let dir = self.path(&self.manifest.shards_biospec);
+        let entries = fs::read_dir(&dir).map_err(|e| ws_err(&dir, e))?;
+        let mut found = Vec::new();
+        for entry in entries {
+            let path = entry.map_err(|e| ws_err(&dir, e))?.path();
+            let name = path
+                .file_name()
+                .and_then(|n| n.to_str())
+                .unwrap_or_default();
+            if name.ends_with(suffix) {
+                found.push(path);
+            }
+        }
+        match found.len() {
+            1 => Ok(found.remove(0)),
+            0 => Err(ws_err(&dir, format!("no `*{}` shard", suffix))),
+            n => Err(ws_err(
+                &dir,
+                format!("{} `*{}` shards; expected one", n, suffix),
+            )),
+        }
+    }
+}
+
+/// `.rohmodel.aln` carries more than the kernel needs; only the ceiling is read.
+#[derive(Deserialize)]
+#[serde(rename = "RohModel")]
+struct RohModelShard {
+    ceiling: f32,
+}
+
+pub fn load_neurorights(ws: &Workspace) -> Result<NeurorightsPolicy, SovereignError> {
+    let (path, text) = ws.read(&ws.manifest.sovereign_config.neurorights)?;
+    serde_json::from_str(&text).map_err(|e| ws_err(&path, e))
+}
+
+pub fn load_roh_model(ws: &Workspace) -> Result<RoHModel, SovereignError> {
+    let (path, text) = ws.read(&ws.manifest.sovereign_config.rohmodel)?;
+    let shard: RohModelShard = aln_format::from_str(&text).map_err(|e| ws_err(&path, e))?;
+    Ok(RoHModel {
+        global_ceiling: RoH(shard.ceiling),
+    })
+}
+
+/// `.ocpuenv` and `.lifeforce.aln` from the manifest's biospec shard dir.
+pub fn load_envelopes(ws: &Workspace) -> Result<Envelopes, SovereignError> {
+    let ocpu_path = ws.biospec_shard(".ocpuenv")?;
+    let ocpuenv: OcpuEnv = aln_format::from_path(&ocpu_path).map_err(|e| ws_err(&ocpu_path, e))?;
+    let life_path = ws.biospec_shard(".lifeforce.aln")?;
+    let runtime_metrics: RuntimeMetrics =
+        aln_format::from_path(&life_path).map_err(|e| ws_err(&life_path, e))?;
+    Ok(Envelopes {
+        ocpuenv,
+        runtime_metrics,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SUBJECT: &str = "bostrom18sd2ujv24ual9c9pshtxys6j8knh6xaead9ye7";
+
+    fn workspace(root: &Path) -> WorkspaceRef {
+        crate::fixtures::write_workspace(&root.join("neuropc"), SUBJECT);
+        WorkspaceRef {
+            subject_id: SUBJECT.into(),
+            workspace_id: "neuropc".into(),
+        }
+    }
+
+    #[test]
+    fn loads_shards_named_by_the_manifest() {
+        let root = tempfile::tempdir().unwrap();
+        let ws = Workspace::resolve(root.path(), &workspace(root.path())).unwrap();
+        assert_eq!(load_neurorights(&ws).unwrap().min_explanation_tokens, 64);
+        assert_eq!(load_roh_model(&ws).unwrap().global_ceiling(), 0.3);
+        let env = load_envelopes(&ws).unwrap();
+        assert_eq!(env.ocpuenv.max_threads, 4);
+        assert_eq!(env.runtime_metrics.current_duty_cycle, 12.5);
+    }
+
+    #[test]
+    fn missing_or_foreign_shards_are_workspace_errors() {
+        let root = tempfile::tempdir().unwrap();
+        let ws_ref = workspace(root.path());
+        fs::remove_file(root.path().join("neuropc/shards/root/stake.aln")).unwrap();
+        let err = Workspace::resolve(root.path(), &ws_ref).unwrap_err();
+        assert!(matches!(err, SovereignError::WorkspaceError(m) if m.contains("stake.aln")));
+
+        let other = WorkspaceRef {
+            subject_id: "bostrom13t9y7dnhf7p2vlzs0juujenefqhze3m88l0c07".into(),
+            ..workspace(root.path())
+        };
+        assert!(Workspace::resolve(root.path(), &other).is_err());
+
+        let ws = Workspace::resolve(root.path(), &workspace(root.path())).unwrap();
+        fs::remove_file(root.path().join("neuropc/shards/biospec/host.ocpuenv")).unwrap();
+        assert!(matches!(
+            load_envelopes(&ws),
+            Err(SovereignError::WorkspaceError(_))
+        ));
+    }
+}
@@ -1,23 +1,86 @@
-use sovereign_specs::evolve_ledger::EvolutionProposalRecord;
-use std::fs::File;
-use std::io::{BufRead, BufReader};
+use sovereign_evolve::{Governance, QuorumEngine, StakeRoster};
+use sovereign_specs::evolve_ledger::{verify_ledger, LedgerReport};
+use std::process::ExitCode;
 
-fn main() -> anyhow::Result<()> {
-    let path = "policies/.evolve.jsonl";
-    let file = File::open(path)?;
-    let reader = BufReader::new(file);
+const USAGE: &str = "usage: check_evolve_ledger [--json] [--stake STAKE_ALN] [LEDGER...]
 
-    for (idx, line) in reader.lines().enumerate() {
-        let line = line?;
-        if line.trim().is_empty() {
+Verifies hexstamps, the prev_hexstamp chain, forks, RoH invariants and
+EVOLVE consent for each ledger (default: policies/.evolve.jsonl), and that
+every actor and quorum certificate checks out against the stake roster
+(default: policies/stake.aln; certificates in LEDGER.quorum/). An empty
+ledger needs no roster. Exits 1 if any ledger has findings.";
+
+fn load_engine(stake: &str) -> anyhow::Result<QuorumEngine> {
+    let text = std::fs::read_to_string(stake).map_err(|e| anyhow::anyhow!("{}: {}", stake, e))?;
+    let roster = StakeRoster::parse(&text).map_err(|e| anyhow::anyhow!("{}: {}", stake, e))?;
+    Ok(QuorumEngine::new(roster))
+}
+
+fn main() -> anyhow::Result<ExitCode> {
+    let mut json = false;
+    let mut stake = String::from("policies/stake.aln");
+    let mut paths = Vec::new();
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--json" => json = true,
+            "--stake" => stake = args.next().ok_or_else(|| anyhow::anyhow!("{}", USAGE))?,
+            "-h" | "--help" => {
+                println!("{}", USAGE);
+                return Ok(ExitCode::SUCCESS);
+            }
+            _ if arg.starts_with('-') => anyhow::bail!("{}", USAGE),
+            _ => paths.push(arg),
+        }
+    }
+    if paths.is_empty() {
+        paths.push("policies/.evolve.jsonl".into());
+    }
+
+    let mut engine = None;
+    let mut clean = true;
+    for path in &paths {
+        let text = std::fs::read_to_string(path).map_err(|e| anyhow::anyhow!("{}: {}", path, e))?;
+        let report = if text.trim().is_empty() {
+            LedgerReport {
+                records: 0,
+                head: None,
+                findings: Vec::new(),
+            }
+        } else {
+            if engine.is_none() {
+                engine = Some(load_engine(&stake)?);
+            }
+            let engine = engine.clone().expect("loaded above");
+            verify_ledger(&text, &Governance::for_ledger(engine, path))
+        };
+        clean &= report.is_clean();
+
+        if json {
+            // One JSON object per ledger, so CI can consume it line by line.
+            let mut value = serde_json::to_value(&report)?;
+            value["path"] = path.as_str().into();
+            println!("{}", value);
             continue;
         }
-        let record: EvolutionProposalRecord = serde_json::from_str(&line)?;
-        if let Err(e) = record.validate_invariants() {
-            eprintln!("Evolution record {} failed invariants: {}", idx, e);
-            std::process::exit(1);
+        for finding in &report.findings {
+            println!("{}: {}", path, finding);
+        }
+        if report.is_clean() {
+            println!(
+                "{}: ok, {} record(s), head {}",
+                path,
+                report.records,
+                report.head.as_deref().unwrap_or("genesis")
+            );
+        } else {
+            println!("{}: {} finding(s)", path, report.findings.len());
         }
     }
 
-    Ok(())
+    Ok(if clean {
+        ExitCode::SUCCESS
+    } else {
+        ExitCode::FAILURE
+    })
 }
@@ -1,58 +1,405 @@
-use crate::capabilities::SovereignClassification;
-use serde::{Deserialize, Serialize};
-
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct NeurorightsPosture {
-    pub mental_privacy: bool,
-    pub dream_state_sensitive: bool,
-    pub forbid_decision_use: bool,
-    pub soul_non_tradeable: bool,
-}
-
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct EvolutionProposalRecord {
-    pub schema_version: u32,
-    pub proposal_id: String,
-    pub prev_hexstamp: Option<String>,
-    pub hexstamp: String,
-    pub timestamp: String,
-    pub subject_id: String,
-    pub description: String,
-    pub roh_before: f32,
-    pub roh_after: f32,
-    pub neurorights_posture: NeurorightsPosture,
-    pub token_kind: String,
-    pub bostrom_tx: String,
-    pub consent_proofs: Vec<String>,
-    pub affected_shards: Vec<String>,
-    pub sovereign_classification: SovereignClassification,
-}
-
-impl EvolutionProposalRecord {
-    /// CI helper: basic invariants the record must satisfy.
-    pub fn validate_invariants(&self) -> Result<(), String> {
-        if self.roh_after > self.roh_before {
-            return Err("RoH monotone safety violated (roh_after > roh_before)".into());
-        }
-        if self.roh_after > 0.3 {
-            return Err("RoH ceiling 0.3 violated".into());
-        }
-        if self.sovereign_classification.required_token_kind.to_string() != self.token_kind {
+//! CI verification of `.evolve.jsonl`. Lines are the one
+//! [`EvolutionProposal`] schema the EVOLVE journal writes, so this checks
+//! exactly what `sovereign_evolve::EvolveStreamGuard` replays, but keeps
+//! going after the first problem.
+
+use serde::Serialize;
+use sovereign_core::{EvolutionProposal, ProposalState};
+use sovereign_evolve::{check_transition, proposal_hexstamp, EvolveError, Governance};
+use std::collections::HashMap;
+use std::fmt;
+
+/// RoH ceiling no proposal may end above.
+const ROH_CEILING: f32 = 0.3;
+
+/// Basic invariants every line must satisfy.
+pub fn validate_invariants(record: &EvolutionProposal) -> Result<(), String> {
+    if record.roh_after > record.roh_before {
+        return Err("RoH monotone safety violated (roh_after > roh_before)".into());
+    }
+    if record.roh_after.0 > ROH_CEILING {
+        return Err("RoH ceiling 0.3 violated".into());
+    }
+    if record.class_label == "SOVEREIGNCONFIG" && record.token_kind != "Evolve" {
+        return Err("SOVEREIGNCONFIG change without an Evolve token kind".into());
+    }
+    if let Some(class) = &record.sovereign_classification {
+        if class.class_label != record.class_label {
+            return Err("class_label does not match sovereign classification".into());
+        }
+        if class.required_token_kind.to_string() != record.token_kind {
             return Err("Token kind does not match sovereign classification".into());
         }
-        Ok(())
     }
+    Ok(())
 }
 
-// Convenience Display for TokenKind so the string compare above works.
-use crate::capabilities::TokenKind;
-impl ToString for TokenKind {
-    fn to_string(&self) -> String {
+/// What is wrong with one ledger line.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub enum LedgerIssue {
+    /// Line is not a parseable record.
+    Malformed { message: String },
+    /// [`validate_invariants`] failed.
+    Invariant { message: String },
+    /// Stored hexstamp differs from the one recomputed over the record.
+    HexstampMismatch { stored: String, computed: String },
+    /// `prev_hexstamp` does not point at the preceding record.
+    BrokenLink {
+        expected: Option<String>,
+        found: Option<String>,
+    },
+    /// Another record already extends the same predecessor.
+    Fork {
+        prev_hexstamp: Option<String>,
+        first_line: u64,
+    },
+    /// Not a legal transition from the proposal's last state, signed by a
+    /// stakeholder, with a certificate that re-tallies where one is due.
+    Transition {
+        from: Option<ProposalState>,
+        to: ProposalState,
+        message: String,
+    },
+    /// EVOLVE-class record past review without consent proofs.
+    MissingConsentProofs,
+    /// EVOLVE-class record past review without a Bostrom tx.
+    MissingBostromTx,
+}
+
+impl fmt::Display for LedgerIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let stamp = |s: &Option<String>| s.clone().unwrap_or_else(|| "genesis".into());
         match self {
-            TokenKind::Smart => "Smart".into(),
-            TokenKind::Evolve => "Evolve".into(),
-            TokenKind::Chat => "Chat".into(),
-            TokenKind::Other(s) => s.clone(),
+            LedgerIssue::Malformed { message } => write!(f, "malformed record: {}", message),
+            LedgerIssue::Invariant { message } => write!(f, "invariant violated: {}", message),
+            LedgerIssue::HexstampMismatch { stored, computed } => write!(
+                f,
+                "hexstamp mismatch (stored {}, computed {})",
+                stored, computed
+            ),
+            LedgerIssue::BrokenLink { expected, found } => write!(
+                f,
+                "prev_hexstamp {} does not match previous record {}",
+                stamp(found),
+                stamp(expected)
+            ),
+            LedgerIssue::Fork {
+                prev_hexstamp,
+                first_line,
+            } => write!(
+                f,
+                "fork: {} is already extended by the record at line {}",
+                stamp(prev_hexstamp),
+                first_line
+            ),
+            LedgerIssue::Transition { from, to, message } => {
+                write!(f, "transition {:?} -> {:?} rejected: {}", from, to, message)
+            }
+            LedgerIssue::MissingConsentProofs => {
+                write!(f, "EVOLVE-class record carries no consent_proofs")
+            }
+            LedgerIssue::MissingBostromTx => write!(f, "EVOLVE-class record carries no bostrom_tx"),
+        }
+    }
+}
+
+/// One problem, located. `line` is 1-based.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct LedgerFinding {
+    pub line: u64,
+    pub proposal_id: Option<String>,
+    pub issue: LedgerIssue,
+}
+
+impl fmt::Display for LedgerFinding {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "line {}", self.line)?;
+        if let Some(id) = &self.proposal_id {
+            write!(f, " ({})", id)?;
+        }
+        write!(f, ": {}", self.issue)
+    }
+}
+
+/// Outcome of walking a whole `.evolve.jsonl`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct LedgerReport {
+    pub records: u64,
+    /// Hexstamp of the last record in file order, `None` when empty.
+    pub head: Option<String>,
+    pub findings: Vec<LedgerFinding>,
+}
+
+impl LedgerReport {
+    pub fn is_clean(&self) -> bool {
+        self.findings.is_empty()
+    }
+}
+
+/// Walk every line, checking content hexstamps, the `prev_hexstamp` chain,
+/// forks, lifecycle transitions, actors and quorum certificates against
+/// `governance`, invariants and EVOLVE consent. Unlike the donutloop
+/// verifier this keeps going after the first problem so CI sees them all;
+/// after a broken link the chain resumes from the offending record.
+pub fn verify_ledger(text: &str, governance: &Governance) -> LedgerReport {
+    let mut report = LedgerReport {
+        records: 0,
+        head: None,
+        findings: Vec::new(),
+    };
+    // Predecessor hexstamp (`None` = genesis) -> line of the first record extending it.
+    let mut successors: HashMap<Option<String>, u64> = HashMap::new();
+    let mut states: HashMap<String, ProposalState> = HashMap::new();
+
+    for (idx, raw) in text.lines().enumerate() {
+        let line = idx as u64 + 1;
+        if raw.trim().is_empty() {
+            continue;
+        }
+        let mut finding = |proposal_id: Option<&str>, issue| {
+            report.findings.push(LedgerFinding {
+                line,
+                proposal_id: proposal_id.map(str::to_string),
+                issue,
+            })
+        };
+        let record: EvolutionProposal = match serde_json::from_str(raw) {
+            Ok(r) => r,
+            Err(e) => {
+                finding(
+                    None,
+                    LedgerIssue::Malformed {
+                        message: e.to_string(),
+                    },
+                );
+                continue;
+            }
+        };
+        let id = Some(record.proposal_id.as_str());
+
+        let computed = proposal_hexstamp(&record).0;
+        if computed != record.hexstamp.0 {
+            finding(
+                id,
+                LedgerIssue::HexstampMismatch {
+                    stored: record.hexstamp.0.clone(),
+                    computed,
+                },
+            );
+        }
+        let prev = record.prev_hexstamp.as_ref().map(|h| h.0.clone());
+        if let Some(first_line) = successors.get(&prev) {
+            finding(
+                id,
+                LedgerIssue::Fork {
+                    prev_hexstamp: prev,
+                    first_line: *first_line,
+                },
+            );
+        } else {
+            if prev != report.head {
+                finding(
+                    id,
+                    LedgerIssue::BrokenLink {
+                        expected: report.head.clone(),
+                        found: prev.clone(),
+                    },
+                );
+            }
+            successors.insert(prev, line);
         }
+        let from = states.insert(record.proposal_id.clone(), record.decision);
+        match check_transition(from, &record, governance) {
+            // Already reported above.
+            Ok(()) | Err(EvolveError::HexstampMismatch { .. }) => {}
+            Err(e) => finding(
+                id,
+                LedgerIssue::Transition {
+                    from,
+                    to: record.decision,
+                    message: e.to_string(),
+                },
+            ),
+        }
+        if let Err(message) = validate_invariants(&record) {
+            finding(id, LedgerIssue::Invariant { message });
+        }
+        let past_review = matches!(
+            record.decision,
+            ProposalState::QuorumReached | ProposalState::Applied | ProposalState::RolledBack
+        );
+        if record.is_evolve_class() && past_review {
+            if record.consent_proofs.iter().all(|p| p.trim().is_empty()) {
+                finding(id, LedgerIssue::MissingConsentProofs);
+            }
+            if record.bostrom_tx.trim().is_empty() {
+                finding(id, LedgerIssue::MissingBostromTx);
+            }
+        }
+
+        report.records += 1;
+        report.head = Some(record.hexstamp.0);
+    }
+    report
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::capabilities::{RightsProfile, SovereignClassification};
+    use k256::ecdsa::SigningKey;
+    use sovereign_core::{AffectedShard, EvolutionKind, HexStamp, Roh, EVOLVE_SCHEMA_VERSION};
+    use sovereign_evolve::{actor_id, sign_transition, QuorumEngine, StakeRoster};
+
+    fn key() -> SigningKey {
+        SigningKey::from_slice(&[1; 32]).unwrap()
+    }
+
+    fn governance() -> Governance {
+        let roster = StakeRoster::parse(&format!(
+            r#"StakeRoster {{
+  subject_id: "bostrom18sd2ujv24ual9c9pshtxys6j8knh6xaead9ye7",
+  multisig: {{ threshold: 1, signers: [{{ label: "host", pubkey: "{}", stake_points: 1 }}] }}
+}}"#,
+            actor_id(&key())
+        ))
+        .unwrap();
+        Governance::new(QuorumEngine::new(roster), "no-certificates")
+    }
+
+    fn unsigned(id: &str, prev: Option<&str>, decision: ProposalState) -> EvolutionProposal {
+        EvolutionProposal {
+            schema_version: EVOLVE_SCHEMA_VERSION,
+            proposal_id: id.into(),
+            prev_hexstamp: prev.map(|p| HexStamp(p.to_string())),
+            subject_id: "bostrom18sd2ujv24ual9c9pshtxys6j8knh6xaead9ye7".into(),
+            description: "tighten lifeforce envelope".into(),
+            kind: EvolutionKind::KernelChange,
+            effect_bounds: (Roh(0.0), Roh(0.05)),
+            roh_before: Roh(0.2),
+            roh_after: Roh(0.1),
+            decision,
+            timestamp_unix: 1_767_225_600,
+            token_kind: "Evolve".into(),
+            class_label: "SOVEREIGNCONFIG".into(),
+            bostrom_tx: String::new(),
+            consent_proofs: Vec::new(),
+            affected_shards: vec![AffectedShard {
+                shard: "vkernel.aln".into(),
+                sha256: "00".repeat(32),
+            }],
+            neurorights_posture: None,
+            sovereign_classification: None,
+            actor: actor_id(&key()),
+            actor_signature: String::new(),
+            snapshot: None,
+            quorum_certificate: None,
+            hexstamp: HexStamp(String::new()),
+        }
+    }
+
+    fn sign(mut r: EvolutionProposal) -> EvolutionProposal {
+        r.hexstamp = proposal_hexstamp(&r);
+        r.actor_signature = sign_transition(&r.hexstamp, &key());
+        r
+    }
+
+    fn record(id: &str, prev: Option<&str>) -> EvolutionProposal {
+        sign(unsigned(id, prev, ProposalState::Draft))
+    }
+
+    fn jsonl(records: &[EvolutionProposal]) -> String {
+        records
+            .iter()
+            .map(|r| serde_json::to_string(r).unwrap() + "\n")
+            .collect()
+    }
+
+    #[test]
+    fn clean_chain_verifies() {
+        let a = record("p-1", None);
+        let b = sign(unsigned(
+            "p-1",
+            Some(&a.hexstamp.0),
+            ProposalState::UnderReview,
+        ));
+        let report = verify_ledger(&jsonl(&[a, b.clone()]), &governance());
+        assert!(report.is_clean(), "{:?}", report.findings);
+        assert_eq!((report.records, report.head), (2, Some(b.hexstamp.0)));
+    }
+
+    #[test]
+    fn classification_must_agree_with_the_record() {
+        let rights = RightsProfile {
+            mental_privacy: true,
+            dream_state_sensitive: true,
+            forbid_decision_use: true,
+            soul_non_tradeable: true,
+            extra_flags: Vec::new(),
+        };
+        let mut r = unsigned("p-1", None, ProposalState::Draft);
+        r.sovereign_classification = Some(SovereignClassification::evolve_structural(
+            "SOVEREIGNCONFIG",
+            rights.clone(),
+        ));
+        assert!(validate_invariants(&r).is_ok());
+        r.sovereign_classification = Some(SovereignClassification::smart_tuning(
+            "SOVEREIGNCONFIG",
+            rights,
+        ));
+        assert!(validate_invariants(&r).is_err());
+    }
+
+    #[test]
+    fn actor_outside_the_roster_is_a_transition_finding() {
+        let stranger = SigningKey::from_slice(&[2; 32]).unwrap();
+        let mut r = unsigned("p-1", None, ProposalState::Draft);
+        r.actor = actor_id(&stranger);
+        r.hexstamp = proposal_hexstamp(&r);
+        r.actor_signature = sign_transition(&r.hexstamp, &stranger);
+        let report = verify_ledger(&jsonl(&[r]), &governance());
+        assert!(matches!(
+            report.findings.as_slice(),
+            [LedgerFinding {
+                issue: LedgerIssue::Transition { .. },
+                ..
+            }]
+        ));
+    }
+
+    #[test]
+    fn detects_edits_forks_breaks_skips_and_missing_consent() {
+        let a = record("p-1", None);
+        let b = record("p-2", Some(&a.hexstamp.0));
+        let fork = record("p-3", Some(&a.hexstamp.0));
+        let mut edited = record("p-4", Some("00"));
+        edited.roh_after = Roh(0.05);
+        let skipped = sign(unsigned(
+            "p-1",
+            Some(&edited.hexstamp.0),
+            ProposalState::QuorumReached,
+        ));
+
+        let report = verify_ledger(&jsonl(&[a, b, fork, edited, skipped]), &governance());
+        let issues: Vec<(u64, &LedgerIssue)> =
+            report.findings.iter().map(|f| (f.line, &f.issue)).collect();
+        assert!(matches!(
+            issues.as_slice(),
+            [
+                (3, LedgerIssue::Fork { first_line: 2, .. }),
+                (4, LedgerIssue::HexstampMismatch { .. }),
+                (4, LedgerIssue::BrokenLink { .. }),
+                (
+                    5,
+                    LedgerIssue::Transition {
+                        from: Some(ProposalState::Draft),
+                        to: ProposalState::QuorumReached,
+                        ..
+                    }
+                ),
+                (5, LedgerIssue::MissingConsentProofs),
+                (5, LedgerIssue::MissingBostromTx),
+            ]
+        ));
     }
 }
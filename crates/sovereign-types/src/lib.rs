@@ -133,3 +133,56 @@ pub struct WorkspaceRef {
     pub subject_id: String,
     pub workspace_id: String,
 }
+
+/// SOVEREIGNCONFIG shard paths declared by the workspace manifest.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SovereignConfigPaths {
+    pub rohmodel: String,
+    pub stake: String,
+    pub neurorights: String,
+    pub smart: String,
+    pub evolve_token: String,
+    pub tsafe: String,
+    pub vkernel: String,
+    #[serde(default)]
+    pub nnetfs_index: Option<String>,
+    #[serde(default)]
+    pub neurofs_index: Option<String>,
+}
+
+/// Outbound network boundary for the AI-shell / LLM side of the node.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NetworkPolicies {
+    #[serde(default)]
+    pub allowed_outbound_hosts: Vec<String>,
+    #[serde(default)]
+    pub forbidden_outbound_cidrs: Vec<String>,
+    #[serde(default)]
+    pub forbid_smart_city_integration: bool,
+}
+
+/// neuro-workspace.manifest.aln: where a subject's shards live and which
+/// invariants bind them. Keys mirror the on-disk ALN spelling.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NeuroWorkspaceManifest {
+    #[serde(rename = "subjectid")]
+    pub subject_id: String,
+    #[serde(default)]
+    pub description: String,
+    #[serde(rename = "shardsroot")]
+    pub shards_root: String,
+    #[serde(rename = "shardsbiospec")]
+    pub shards_biospec: String,
+    #[serde(rename = "shardsneuro")]
+    pub shards_neuro: String,
+    #[serde(rename = "shardsmodel")]
+    pub shards_model: String,
+    #[serde(rename = "shardsledger")]
+    pub shards_ledger: String,
+    pub sovereign_config: SovereignConfigPaths,
+    #[serde(default)]
+    pub routes: Vec<String>,
+    pub network_policies: NetworkPolicies,
+    #[serde(default)]
+    pub invariants: Vec<String>,
+}
@@ -0,0 +1,278 @@
+//! Typed sovereign subject identifiers.
+//!
+//! Subjects arrive as strings from JSON, ALN shards and LLM tool calls. A
+//! [`SubjectId`] only exists once the string has parsed and checksummed as
+//! one of the anchors the stack recognises:
+//!
+//! - `bostrom1…` and `zeta1…` bech32 account addresses,
+//! - `0x…` EVM anchors (EIP-55 checksum enforced when mixed-case),
+//! - `did:key:z…` identifiers for Ed25519, secp256k1 or P-256 keys.
+//!
+//! Two ids are equal when their canonical forms are: lowercase bech32,
+//! EIP-55 checksummed hex, and the did as written.
+
+use std::fmt;
+use std::str::FromStr;
+
+use bech32::{FromBase32, Variant};
+use serde::{Deserialize, Serialize};
+use sha3::{Digest, Keccak256};
+use thiserror::Error;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum SubjectKind {
+    Bostrom,
+    Zeta,
+    Evm,
+    DidKey,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum SubjectIdError {
+    #[error("subject id is empty")]
+    Empty,
+    #[error("`{0}` is not a bostrom, zeta, 0x or did:key subject")]
+    UnknownScheme(String),
+    #[error("`{input}` is not valid bech32: {message}")]
+    Bech32 { input: String, message: String },
+    #[error("`{input}` has {len} payload bytes; expected {expected}")]
+    Length {
+        input: String,
+        len: usize,
+        expected: &'static str,
+    },
+    #[error("`{0}` fails its EIP-55 checksum")]
+    Eip55(String),
+    #[error("`{input}` is not a did:key: {message}")]
+    DidKey { input: String, message: String },
+}
+
+/// A validated subject in canonical form.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct SubjectId {
+    canonical: String,
+}
+
+impl SubjectId {
+    pub fn parse(input: &str) -> Result<Self, SubjectIdError> {
+        let s = input.trim();
+        if s.is_empty() {
+            return Err(SubjectIdError::Empty);
+        }
+        let lower = s.to_ascii_lowercase();
+        let canonical = if lower.starts_with("bostrom1") || lower.starts_with("zeta1") {
+            parse_bech32(s)?
+        } else if lower.starts_with("0x") {
+            parse_evm(s)?
+        } else if s.starts_with("did:key:") {
+            parse_did_key(s)?
+        } else {
+            return Err(SubjectIdError::UnknownScheme(s.to_string()));
+        };
+        Ok(Self { canonical })
+    }
+
+    pub fn kind(&self) -> SubjectKind {
+        match self.canonical.as_bytes() {
+            [b'b', ..] => SubjectKind::Bostrom,
+            [b'z', ..] => SubjectKind::Zeta,
+            [b'0', ..] => SubjectKind::Evm,
+            _ => SubjectKind::DidKey,
+        }
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.canonical
+    }
+}
+
+fn parse_bech32(s: &str) -> Result<String, SubjectIdError> {
+    let bad = |message: String| SubjectIdError::Bech32 {
+        input: s.to_string(),
+        message,
+    };
+    let (hrp, data, variant) = bech32::decode(s).map_err(|e| bad(e.to_string()))?;
+    if variant != Variant::Bech32 {
+        return Err(bad("bech32m is not used for account addresses".into()));
+    }
+    if hrp != "bostrom" && hrp != "zeta" {
+        return Err(bad(format!("unexpected prefix `{}`", hrp)));
+    }
+    let bytes = Vec::<u8>::from_base32(&data).map_err(|e| bad(e.to_string()))?;
+    // 20-byte accounts; 32-byte module and contract addresses.
+    if bytes.len() != 20 && bytes.len() != 32 {
+        return Err(SubjectIdError::Length {
+            input: s.to_string(),
+            len: bytes.len(),
+            expected: "20 or 32",
+        });
+    }
+    Ok(s.to_ascii_lowercase())
+}
+
+fn eip55(hex_lower: &str) -> String {
+    let hash = Keccak256::digest(hex_lower.as_bytes());
+    hex_lower
+        .chars()
+        .enumerate()
+        .map(|(i, c)| {
+            let nibble = (hash[i / 2] >> if i % 2 == 0 { 4 } else { 0 }) & 0x0f;
+            if c.is_ascii_alphabetic() && nibble >= 8 {
+                c.to_ascii_uppercase()
+            } else {
+                c
+            }
+        })
+        .collect()
+}
+
+fn parse_evm(s: &str) -> Result<String, SubjectIdError> {
+    let body = &s[2..];
+    if body.len() != 40 || !body.bytes().all(|b| b.is_ascii_hexdigit()) {
+        return Err(SubjectIdError::Length {
+            input: s.to_string(),
+            len: body.len() / 2,
+            expected: "20 (40 hex digits)",
+        });
+    }
+    let checksummed = eip55(&body.to_ascii_lowercase());
+    let mixed = body.bytes().any(|b| b.is_ascii_lowercase())
+        && body.bytes().any(|b| b.is_ascii_uppercase());
+    if mixed && body != checksummed {
+        return Err(SubjectIdError::Eip55(s.to_string()));
+    }
+    Ok(format!("0x{}", checksummed))
+}
+
+/// Multicodec prefixes (varint) and key lengths accepted in did:key.
+const DID_KEY_CODECS: &[(&[u8], usize, &str)] = &[
+    (&[0xed, 0x01], 32, "ed25519-pub"),
+    (&[0xe7, 0x01], 33, "secp256k1-pub"),
+    (&[0x80, 0x24], 33, "p256-pub"),
+];
+
+fn parse_did_key(s: &str) -> Result<String, SubjectIdError> {
+    let bad = |message: String| SubjectIdError::DidKey {
+        input: s.to_string(),
+        message,
+    };
+    let encoded = s["did:key:".len()..]
+        .strip_prefix('z')
+        .ok_or_else(|| bad("multibase must be base58btc (`z`)".into()))?;
+    let raw = bs58::decode(encoded)
+        .into_vec()
+        .map_err(|e| bad(e.to_string()))?;
+    let (_, len, name) = DID_KEY_CODECS
+        .iter()
+        .find(|(prefix, _, _)| raw.starts_with(prefix))
+        .ok_or_else(|| bad("unsupported multicodec key type".into()))?;
+    if raw.len() != 2 + len {
+        return Err(bad(format!(
+            "{} key is {} bytes, expected {}",
+            name,
+            raw.len() - 2,
+            len
+        )));
+    }
+    Ok(s.to_string())
+}
+
+impl FromStr for SubjectId {
+    type Err = SubjectIdError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse(s)
+    }
+}
+
+impl TryFrom<String> for SubjectId {
+    type Error = SubjectIdError;
+
+    fn try_from(s: String) -> Result<Self, Self::Error> {
+        Self::parse(&s)
+    }
+}
+
+impl From<SubjectId> for String {
+    fn from(id: SubjectId) -> Self {
+        id.canonical
+    }
+}
+
+impl fmt::Display for SubjectId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.canonical)
+    }
+}
+
+impl AsRef<str> for SubjectId {
+    fn as_ref(&self) -> &str {
+        &self.canonical
+    }
+}
+
+impl PartialEq<str> for SubjectId {
+    fn eq(&self, other: &str) -> bool {
+        SubjectId::parse(other).is_ok_and(|o| o == *self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const BOSTROM: &str = "bostrom18sd2ujv24ual9c9pshtxys6j8knh6xaead9ye7";
+
+    #[test]
+    fn bech32_addresses_checksum() {
+        let id = SubjectId::parse(BOSTROM).unwrap();
+        assert_eq!(id.kind(), SubjectKind::Bostrom);
+        assert_eq!(SubjectId::parse(&BOSTROM.to_uppercase()).unwrap(), id);
+
+        // One flipped character breaks the checksum.
+        let typo = BOSTROM.replace("ye7", "ye8");
+        assert!(matches!(
+            SubjectId::parse(&typo),
+            Err(SubjectIdError::Bech32 { .. })
+        ));
+        assert!(matches!(
+            SubjectId::parse("bostrom18sd2u..."),
+            Err(SubjectIdError::Bech32 { .. })
+        ));
+        assert!(matches!(
+            SubjectId::parse("cosmos1abc"),
+            Err(SubjectIdError::UnknownScheme(_))
+        ));
+    }
+
+    #[test]
+    fn evm_anchors_enforce_eip55() {
+        // EIP-55 reference vector.
+        let good = "0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed";
+        let lower = SubjectId::parse(&good.to_lowercase()).unwrap();
+        assert_eq!(lower.as_str(), good);
+        assert_eq!(SubjectId::parse(good).unwrap(), lower);
+        assert!(matches!(
+            SubjectId::parse("0x5AAeb6053F3E94C9b9A09f33669435E7Ef1BeAed"),
+            Err(SubjectIdError::Eip55(_))
+        ));
+        assert!(SubjectId::parse("0x519f").is_err());
+    }
+
+    #[test]
+    fn did_keys_and_serde() {
+        let did = "did:key:z6MkhaXgBZDvotDkL5257faiztiGiC2QtKLGpbnnEGta2doK";
+        assert_eq!(SubjectId::parse(did).unwrap().kind(), SubjectKind::DidKey);
+        assert!(matches!(
+            SubjectId::parse("did:key:z6Mkha"),
+            Err(SubjectIdError::DidKey { .. })
+        ));
+
+        let json = format!("[\"{}\",\"{}\"]", BOSTROM, did);
+        let ids: Vec<SubjectId> = serde_json::from_str(&json).unwrap();
+        assert_eq!(serde_json::to_string(&ids).unwrap(), json);
+        let err = serde_json::from_str::<SubjectId>("\"bostrom18sd2u...\"").unwrap_err();
+        assert!(err.to_string().contains("not valid bech32"));
+    }
+}
@@ -1,5 +1,6 @@
 use serde::{Deserialize, Serialize};
-use std::{collections::HashMap, fs, path::Path};
+use sovereign_core::{RohEstimate, ROH_AXES};
+use std::collections::{BTreeMap, HashMap};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct NeurorightsPolicy {
@@ -18,15 +19,54 @@ pub struct RohModel {
 }
 
 impl RohModel {
-    pub fn load<P: AsRef<Path>>(path: P) -> anyhow::Result<Self> {
-        let text = fs::read_to_string(path)?;
-        let model: RohModel = serde_json::from_str(&text)?;
+    /// Weighted estimate over `sovereign_core::ROH_AXES`.
+    pub fn estimate(&self, axes: &BTreeMap<String, f32>, route: &str) -> RohEstimate {
+        RohEstimate::weighted(&self.weights, axes, route)
+    }
+
+    /// Parse `.rohmodel.aln`. Weights must be finite, non-negative, keyed
+    /// by `ROH_AXES` and weight at least one axis: a model that weights
+    /// nothing estimates 0 for every action and would pass them all.
+    pub fn parse(text: &str) -> anyhow::Result<Self> {
+        let model: RohModel = aln_format::from_str(text)?;
         anyhow::ensure!((model.ceiling - 0.3).abs() < 1e-6, "RoH ceiling must be 0.3");
+        for (axis, weight) in &model.weights {
+            anyhow::ensure!(
+                ROH_AXES.contains(&axis.as_str()),
+                "unknown RoH axis `{}`",
+                axis
+            );
+            anyhow::ensure!(
+                weight.is_finite() && *weight >= 0.0,
+                "RoH weight for `{}` must be finite and non-negative",
+                axis
+            );
+        }
+        anyhow::ensure!(
+            model.weights.values().any(|w| *w > 0.0),
+            "RoH model weights no axis"
+        );
         Ok(model)
     }
 }
 
-pub fn load_neurorights<P: AsRef<Path>>(path: P) -> anyhow::Result<NeurorightsPolicy> {
-    let text = fs::read_to_string(path)?;
-    Ok(serde_json::from_str(&text)?)
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rohmodel_must_weight_a_known_axis() {
+        for text in [
+            "RohModel { ceiling: 0.3, weights: {} }",
+            "RohModel { ceiling: 0.3, weights: { lifeforce_load: 0.0 } }",
+            "RohModel { ceiling: 0.3, weights: { eco: 1.0 } }",
+            "RohModel { ceiling: 0.3, weights: { qpu_roh: -0.5, lifeforce_load: 1.0 } }",
+        ] {
+            assert!(RohModel::parse(text).is_err(), "{}", text);
+        }
+        let model = RohModel::parse("RohModel { ceiling: 0.3, weights: { biospec_fatigue: 0.5 } }")
+            .unwrap();
+        let axes = BTreeMap::from([("biospec_fatigue".to_string(), 0.4)]);
+        assert!((model.estimate(&axes, "CHAT").roh.0 - 0.2).abs() < 1e-6);
+    }
 }
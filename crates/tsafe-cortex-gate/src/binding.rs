@@ -0,0 +1,182 @@
+//! Subject, route and kind coherence between a request, its action and its
+//! capability chord, checked before any chord field is trusted further.
+
+use sovereign_boundary::BostromIdentity;
+use subject_id::SubjectId;
+
+use crate::{CapabilityKind, GuardError, Request, XRActionKind};
+
+impl CapabilityKind {
+    /// Action kinds a chord of this kind may carry. Key material and
+    /// signing are never reachable through a chord.
+    pub fn permits(&self, action: &XRActionKind) -> bool {
+        use XRActionKind::*;
+        match self {
+            CapabilityKind::SummarizeText | CapabilityKind::ExplainPolicy => {
+                matches!(action, XRRouteStep | ReadNeuralShard)
+            }
+            CapabilityKind::XRRoutePlan => matches!(action, XRRouteStep | ScheduleJob),
+            CapabilityKind::DraftEvolveProposal => {
+                matches!(
+                    action,
+                    XRRouteStep | ProposeEvolve | ApplyOta | WriteNeuralShard
+                )
+            }
+        }
+    }
+}
+
+/// Binds every request to the node's sovereign subject. Alternates from
+/// `BostromIdentity` resolve to the primary address; anything else is foreign.
+pub struct SubjectBinding {
+    identity: BostromIdentity,
+}
+
+fn reject(code: &str, message: String) -> GuardError {
+    GuardError {
+        code: code.into(),
+        message,
+    }
+}
+
+impl SubjectBinding {
+    pub fn new(identity: BostromIdentity) -> Self {
+        Self { identity }
+    }
+
+    /// Canonical subject for `address`, if it speaks for this node's subject.
+    pub fn resolve(&self, address: &SubjectId) -> Option<&SubjectId> {
+        self.identity.resolve(address)
+    }
+
+    /// Canonical subject of `req`, or the first incoherence found.
+    pub fn bind(&self, req: &Request) -> Result<SubjectId, GuardError> {
+        let subject = self.identity.resolve(&req.subject_id).ok_or_else(|| {
+            reject(
+                "SUBJECT_FOREIGN",
+                format!("{} is not this node's sovereign subject", req.subject_id),
+            )
+        })?;
+        if self.identity.resolve(&req.action.subject_id) != Some(subject) {
+            return Err(reject(
+                "ACTION_SUBJECT_FOREIGN",
+                format!(
+                    "Action is for {}, request is for {}",
+                    req.action.subject_id, subject
+                ),
+            ));
+        }
+        if self.identity.resolve(&req.capability.subject_id) != Some(subject) {
+            return Err(reject(
+                "CAPABILITY_SUBJECT_FOREIGN",
+                format!("Capability chord is bound to {}", req.capability.subject_id),
+            ));
+        }
+        if req.action.route != req.route {
+            return Err(reject(
+                "ACTION_ROUTE_MISMATCH",
+                format!(
+                    "Action route {} differs from request route {}",
+                    req.action.route, req.route
+                ),
+            ));
+        }
+        if !req.capability.kind.permits(&req.action.kind) {
+            return Err(reject(
+                "CAPABILITY_KIND_MISMATCH",
+                format!(
+                    "{:?} chord cannot carry a {:?} action",
+                    req.capability.kind, req.action.kind
+                ),
+            ));
+        }
+        Ok(subject.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{CapabilityChord, XRAction};
+
+    const PRIMARY: &str = "bostrom18sd2ujv24ual9c9pshtxys6j8knh6xaead9ye7";
+    const ALTERNATE: &str = "0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed";
+    const STRANGER: &str = "bostrom13t9y7dnhf7p2vlzs0juujenefqhze3m88l0c07";
+
+    fn id(s: &str) -> SubjectId {
+        s.parse().unwrap()
+    }
+
+    fn identity() -> BostromIdentity {
+        BostromIdentity {
+            primary_address: id(PRIMARY),
+            secure_address: Some(id("bostrom1d2f5k3g5fcm43yg7lg57668m94pqlfaaemwtr4")),
+            safe_alternates: vec![id(ALTERNATE)],
+        }
+    }
+
+    fn request(subject: &str, action_subject: &str, kind: XRActionKind) -> Request {
+        let issuer = chord_seal::ChordIssuer::from_secret([7; 32]);
+        let capability = CapabilityChord::mint(
+            &issuer,
+            CapabilityKind::SummarizeText,
+            id(PRIMARY),
+            "CHAT",
+            "SuggestOnly",
+            1024,
+            60,
+        )
+        .unwrap();
+        Request {
+            subject_id: id(subject),
+            route: "CHAT".into(),
+            raw_prompt: None,
+            action: XRAction {
+                kind,
+                subject_id: id(action_subject),
+                route: "CHAT".into(),
+                requested_fields: Vec::new(),
+                lifeforce_cost: 0.0,
+                roh_before: 0.1,
+                roh_after_estimate: 0.1,
+                evolve_token: None,
+            },
+            capability,
+            purpose: None,
+            consent: None,
+        }
+    }
+
+    fn code(req: &Request) -> Option<String> {
+        SubjectBinding::new(identity())
+            .bind(req)
+            .err()
+            .map(|e| e.code)
+    }
+
+    #[test]
+    fn alternates_bind_and_foreigners_do_not() {
+        let (primary, stranger) = (PRIMARY, STRANGER);
+        let alt = ALTERNATE.to_lowercase();
+        assert_eq!(
+            code(&request(&alt, primary, XRActionKind::XRRouteStep)),
+            None
+        );
+        let binding = SubjectBinding::new(identity());
+        assert_eq!(binding.resolve(&id(&alt)), Some(&id(primary)));
+        assert_eq!(binding.resolve(&id(stranger)), None);
+
+        assert_eq!(
+            code(&request(stranger, primary, XRActionKind::XRRouteStep)).as_deref(),
+            Some("SUBJECT_FOREIGN")
+        );
+        assert_eq!(
+            code(&request(primary, stranger, XRActionKind::ReadNeuralShard)).as_deref(),
+            Some("ACTION_SUBJECT_FOREIGN")
+        );
+        assert_eq!(
+            code(&request(primary, primary, XRActionKind::SignTransaction)).as_deref(),
+            Some("CAPABILITY_KIND_MISMATCH")
+        );
+    }
+}
@@ -0,0 +1,231 @@
+use crate::guardians::{GuardError, RohSpan};
+use crate::XRAction;
+use evolve_token_verifier::EvolveGated;
+use serde::Deserialize;
+use sovereign_evolve::{change_cost, EvolveBudget, Governance};
+use std::path::{Path, PathBuf};
+
+/// The part of `citizen-stake.json` the budget reads.
+#[derive(Deserialize)]
+struct CitizenStakeRate {
+    max_evolve_rate_per_day: f32,
+}
+
+// ---- EvolveBudgetGuard ----
+
+/// Caps structural change (EVOLVE / OTA) per rolling 24 hours at the
+/// subject's `CitizenStake::max_evolve_rate_per_day`, replaying
+/// `.evolve.jsonl` under its stake roster on every check so the count
+/// cannot drift from the ledger.
+pub struct EvolveBudgetGuard {
+    ledger: PathBuf,
+    /// `None` when there is no stake roster to replay the ledger under.
+    governance: Option<Governance>,
+    max_per_day: f32,
+}
+
+impl EvolveBudgetGuard {
+    pub fn new<P: Into<PathBuf>>(ledger: P, governance: Governance, max_per_day: f32) -> Self {
+        Self {
+            ledger: ledger.into(),
+            governance: Some(governance),
+            max_per_day,
+        }
+    }
+
+    /// Budget from the subject's `citizen-stake.json`.
+    pub fn from_citizen_stake<P: AsRef<Path>, Q: Into<PathBuf>>(
+        citizen_stake: P,
+        ledger: Q,
+        governance: Governance,
+    ) -> anyhow::Result<Self> {
+        let text = std::fs::read_to_string(citizen_stake)?;
+        let stake: CitizenStakeRate = serde_json::from_str(&text)?;
+        Ok(Self::new(ledger, governance, stake.max_evolve_rate_per_day))
+    }
+
+    /// Guard for a node without a stake roster: the ledger cannot be
+    /// replayed, so every structural action is denied.
+    pub fn fail_closed<P: Into<PathBuf>>(ledger: P) -> Self {
+        Self {
+            ledger: ledger.into(),
+            governance: None,
+            max_per_day: 0.0,
+        }
+    }
+
+    /// Non-structural actions pass. A structural action is charged for the
+    /// RoH `span` the RoH guard judged, weighted as
+    /// [`proposal_cost`](sovereign_evolve::proposal_cost) weights a
+    /// proposal's effect bounds. An unreadable or invalid ledger denies.
+    pub fn check(
+        &self,
+        action: &XRAction,
+        span: &RohSpan,
+        now_unix: i64,
+    ) -> Result<(), GuardError> {
+        if !action.requires_evolve_token() {
+            return Ok(());
+        }
+        let Some(governance) = &self.governance else {
+            return Err(GuardError {
+                code: "EVOLVE_BUDGET_UNAVAILABLE".into(),
+                message: "No stake roster to replay the evolve ledger under".into(),
+            });
+        };
+        // No ledger yet means nothing has been applied, unless its journal
+        // ever anchored a head.
+        let replayed =
+            EvolveBudget::replay_path(&self.ledger, governance, self.max_per_day, now_unix);
+        let budget = match replayed {
+            Ok(b) => b,
+            Err(e) => {
+                return Err(GuardError {
+                    code: "EVOLVE_BUDGET_UNAVAILABLE".into(),
+                    message: format!("Evolve ledger cannot be replayed: {}", e),
+                })
+            }
+        };
+        let cost = change_cost(span.before(), span.after);
+        budget.check(cost).map_err(|e| {
+            let refill = match e.refills_at_unix {
+                Some(at) => chrono::DateTime::from_timestamp(at, 0)
+                    .map(|t| t.to_rfc3339())
+                    .unwrap_or_else(|| at.to_string()),
+                None => "never (change exceeds the whole daily budget)".into(),
+            };
+            GuardError {
+                code: "EVOLVE_BUDGET_EXHAUSTED".into(),
+                message: format!(
+                    "Structural evolution budget spent ({:.2} of {:.2} in 24h, change costs {:.2}); refills at {}",
+                    e.spent, e.limit, e.requested, refill
+                ),
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testutil::{governance, lifecycle, write_ledger, SUBJECT};
+    use crate::XRActionKind;
+    use sovereign_core::{ProposalState, Roh, RohEstimate};
+
+    const NOW: i64 = 1_767_225_600;
+
+    fn action(kind: XRActionKind) -> XRAction {
+        XRAction {
+            kind,
+            subject_id: SUBJECT.parse().unwrap(),
+            route: "OTA".into(),
+            requested_fields: Vec::new(),
+            lifeforce_cost: 0.0,
+            roh_before: 0.3,
+            roh_after_estimate: 0.3,
+            evolve_token: None,
+        }
+    }
+
+    fn span(before: f32, after: f32) -> RohSpan {
+        RohSpan {
+            estimate: RohEstimate {
+                roh: Roh(before),
+                contributions: Default::default(),
+            },
+            after,
+        }
+    }
+
+    fn code(guard: &EvolveBudgetGuard, action: &XRAction, roh: (f32, f32)) -> Option<String> {
+        guard
+            .check(action, &span(roh.0, roh.1), NOW)
+            .err()
+            .map(|e| e.code)
+    }
+
+    #[test]
+    fn charge_follows_the_judged_span_not_the_caller() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join(".evolve.jsonl");
+        let guard = EvolveBudgetGuard::new(&path, governance(&path), 1.5);
+
+        // Without a ledger nothing is spent; a small change fits, wherever
+        // on the RoH scale it starts.
+        assert_eq!(
+            code(&guard, &action(XRActionKind::ApplyOta), (0.3, 0.27)),
+            None
+        );
+        assert_eq!(
+            code(&guard, &action(XRActionKind::ApplyOta), (0.1, 0.07)),
+            None
+        );
+        // The caller reports no change; the span is what is charged.
+        assert_eq!(
+            code(&guard, &action(XRActionKind::ProposeEvolve), (0.3, 0.0)).as_deref(),
+            Some("EVOLVE_BUDGET_EXHAUSTED")
+        );
+        // Non-structural actions are never charged.
+        assert_eq!(
+            code(&guard, &action(XRActionKind::XRRouteStep), (0.3, 0.0)),
+            None
+        );
+    }
+
+    #[test]
+    fn applied_changes_in_the_window_spend_the_budget() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join(".evolve.jsonl");
+        let guard = EvolveBudgetGuard::new(&path, governance(&path), 2.5);
+        let old = lifecycle(
+            "p-0",
+            None,
+            ProposalState::Applied,
+            "vkernel.aln",
+            &"aa".repeat(32),
+            NOW - 90_000,
+        );
+        let recent = lifecycle(
+            "p-1",
+            old.last(),
+            ProposalState::Applied,
+            "vkernel.aln",
+            &"aa".repeat(32),
+            NOW - 60,
+        );
+        let ota = action(XRActionKind::ApplyOta);
+
+        write_ledger(&path, &old);
+        assert_eq!(code(&guard, &ota, (0.2, 0.2)), None);
+
+        write_ledger(&path, &[old, recent].concat());
+        let err = guard.check(&ota, &span(0.3, 0.15), NOW).unwrap_err();
+        assert_eq!(err.code, "EVOLVE_BUDGET_EXHAUSTED");
+        assert!(err.message.contains("refills at"), "{}", err.message);
+        assert_eq!(code(&guard, &ota, (0.2, 0.2)), None);
+    }
+
+    #[test]
+    fn unreadable_ledger_denies() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join(".evolve.jsonl");
+        std::fs::write(&path, "{not json}\n").unwrap();
+        let guard = EvolveBudgetGuard::new(&path, governance(&path), 10.0);
+        assert_eq!(
+            code(&guard, &action(XRActionKind::ApplyOta), (0.2, 0.2)).as_deref(),
+            Some("EVOLVE_BUDGET_UNAVAILABLE")
+        );
+        assert_eq!(
+            code(&guard, &action(XRActionKind::XRRouteStep), (0.2, 0.2)),
+            None
+        );
+
+        // Without a stake roster nothing structural is admitted at all.
+        std::fs::remove_file(&path).unwrap();
+        let guard = EvolveBudgetGuard::fail_closed(&path);
+        assert_eq!(
+            code(&guard, &action(XRActionKind::ApplyOta), (0.2, 0.2)).as_deref(),
+            Some("EVOLVE_BUDGET_UNAVAILABLE")
+        );
+    }
+}
@@ -0,0 +1,409 @@
+use crate::guardians::GuardError;
+use crate::{Request, XRActionKind};
+use consent_receipt::{ConsentReceipt, ConsentStore, ConsentVerifier, Purpose};
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+use std::sync::Arc;
+use subject_id::SubjectId;
+
+/// Routes on which an action drives hardware or the subject's environment.
+pub const ACTUATING_ROUTES: &[&str] = &["BCI", "XR", "NANOSWARM", "OTA"];
+
+/// One `consent.json` scope: the route/purpose pairs receipts may cover.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConsentScope {
+    pub route: String,
+    pub purpose: Purpose,
+    pub allow_actuation: bool,
+}
+
+#[derive(Deserialize)]
+struct ConsentConfig {
+    scopes: Vec<ConsentScope>,
+}
+
+fn reject(code: &str, message: String) -> GuardError {
+    GuardError {
+        code: code.into(),
+        message,
+    }
+}
+
+// ---- ConsentGuard ----
+
+/// Requires a live, subject-signed consent receipt for actuation on
+/// [`ACTUATING_ROUTES`] and for every `ReadNeuralShard`. The receipt must
+/// match the request's subject, route and declared purpose, sit inside a
+/// `consent.json` scope, and not be revoked.
+pub struct ConsentGuard {
+    /// `None` on a node without the subject's consent key.
+    verifier: Option<ConsentVerifier>,
+    store: Arc<ConsentStore>,
+    scopes: Vec<ConsentScope>,
+}
+
+impl ConsentGuard {
+    pub fn new(verifier: ConsentVerifier, store: Arc<ConsentStore>, scopes: Vec<ConsentScope>) -> Self {
+        Self { verifier: Some(verifier), store, scopes }
+    }
+
+    /// Guard for a node without the subject's consent key or `consent.json`:
+    /// every action that needs consent is refused, and nothing is revoked.
+    pub fn fail_closed(store: Arc<ConsentStore>) -> Self {
+        Self { verifier: None, store, scopes: Vec::new() }
+    }
+
+    fn verify(&self, receipt: &ConsentReceipt) -> Result<(), GuardError> {
+        let verifier = self.verifier.as_ref().ok_or_else(|| {
+            reject("CONSENT_UNAVAILABLE", "This node holds no subject consent key".into())
+        })?;
+        verifier
+            .verify(receipt)
+            .map_err(|e| reject("CONSENT_FORGED", e.to_string()))
+    }
+
+    /// Scopes from the subject's `consent.json`.
+    pub fn from_consent_file<P: AsRef<Path>>(
+        path: P,
+        verifier: ConsentVerifier,
+        store: Arc<ConsentStore>,
+    ) -> anyhow::Result<Self> {
+        let config: ConsentConfig = serde_json::from_str(&std::fs::read_to_string(path)?)?;
+        Ok(Self::new(verifier, store, config.scopes))
+    }
+
+    pub fn store(&self) -> &Arc<ConsentStore> {
+        &self.store
+    }
+
+    /// Withdraw `receipt` for `subject`: it must carry the subject's consent
+    /// signature and name `subject`, so a handle is only ever revoked by the
+    /// subject it belongs to.
+    pub fn revoke(
+        &self,
+        receipt: &ConsentReceipt,
+        subject: &SubjectId,
+        reason: &str,
+    ) -> Result<(), GuardError> {
+        self.verify(receipt)?;
+        if receipt.subject_id != *subject {
+            return Err(reject(
+                "CONSENT_SUBJECT_MISMATCH",
+                format!("Consent receipt {} belongs to another subject", receipt.receipt_id),
+            ));
+        }
+        self.store
+            .revoke(&receipt.revocation_handle, subject, reason)
+            .map_err(|e| reject("CONSENT_STORE_UNAVAILABLE", e.to_string()))
+    }
+
+    fn actuates(req: &Request) -> bool {
+        ACTUATING_ROUTES.contains(&req.route.as_str())
+            && matches!(
+                req.action.kind,
+                XRActionKind::WriteNeuralShard
+                    | XRActionKind::ApplyOta
+                    | XRActionKind::XRRouteStep
+                    | XRActionKind::ScheduleJob
+            )
+    }
+
+    /// `subject` is the canonical subject the gate bound `req` to.
+    pub fn check(&self, req: &Request, subject: &SubjectId, now_unix: i64) -> Result<(), GuardError> {
+        let reads_neural = matches!(req.action.kind, XRActionKind::ReadNeuralShard);
+        let actuates = Self::actuates(req);
+        if !reads_neural && !actuates {
+            return Ok(());
+        }
+
+        let (receipt, purpose) = match (&req.consent, &req.purpose) {
+            (Some(r), Some(p)) => (r, p),
+            _ => {
+                return Err(reject(
+                    "CONSENT_MISSING",
+                    format!("{:?} on {} needs a consent receipt and a declared purpose", req.action.kind, req.route),
+                ))
+            }
+        };
+        self.verify(receipt)?;
+        Self::check_binding(receipt, req, subject, purpose, now_unix)?;
+
+        let scope = self
+            .scopes
+            .iter()
+            .find(|s| s.route == receipt.route && s.purpose == receipt.purpose)
+            .ok_or_else(|| {
+                reject(
+                    "CONSENT_OUT_OF_SCOPE",
+                    format!("consent.json has no {} scope for {}", receipt.purpose, receipt.route),
+                )
+            })?;
+        if actuates && !(receipt.allow_actuation && scope.allow_actuation) {
+            return Err(reject(
+                "CONSENT_NO_ACTUATION",
+                format!("Consent for {} / {} is data-only", receipt.route, receipt.purpose),
+            ));
+        }
+        if reads_neural && !receipt.covers(&req.action.requested_fields) {
+            return Err(reject(
+                "CONSENT_CATEGORY_UNCOVERED",
+                "Requested neural data lies outside the receipt's data categories".into(),
+            ));
+        }
+
+        match self.store.is_revoked(&receipt.revocation_handle, subject) {
+            Ok(false) => Ok(()),
+            Ok(true) => Err(reject(
+                "CONSENT_REVOKED",
+                format!("Consent receipt {} has been revoked", receipt.receipt_id),
+            )),
+            Err(e) => Err(reject("CONSENT_STORE_UNAVAILABLE", e.to_string())),
+        }
+    }
+
+    fn check_binding(
+        receipt: &ConsentReceipt,
+        req: &Request,
+        subject: &SubjectId,
+        purpose: &Purpose,
+        now_unix: i64,
+    ) -> Result<(), GuardError> {
+        if receipt.subject_id != *subject {
+            return Err(reject(
+                "CONSENT_SUBJECT_MISMATCH",
+                "Consent receipt was given by another subject".into(),
+            ));
+        }
+        if receipt.route != req.route {
+            return Err(reject(
+                "CONSENT_ROUTE_MISMATCH",
+                format!("Consent receipt covers route {}, not {}", receipt.route, req.route),
+            ));
+        }
+        if receipt.purpose != *purpose {
+            return Err(reject(
+                "CONSENT_PURPOSE_MISMATCH",
+                format!("Consent receipt covers {}, not {}", receipt.purpose, purpose),
+            ));
+        }
+        if !receipt.is_live(now_unix) {
+            return Err(reject(
+                "CONSENT_EXPIRED",
+                format!("Consent receipt {} is outside its validity window", receipt.receipt_id),
+            ));
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{CapabilityChord, CapabilityKind, XRAction};
+    use consent_receipt::ConsentSigner;
+
+    const SUBJECT: &str = "bostrom18sd2ujv24ual9c9pshtxys6j8knh6xaead9ye7";
+    const STRANGER: &str = "bostrom13t9y7dnhf7p2vlzs0juujenefqhze3m88l0c07";
+    const NOW: i64 = 150;
+
+    fn id(s: &str) -> SubjectId {
+        s.parse().unwrap()
+    }
+
+    fn signer() -> ConsentSigner {
+        ConsentSigner::from_secret([3; 32])
+    }
+
+    fn guard(dir: &Path) -> ConsentGuard {
+        let store = ConsentStore::open(dir.join("consent.jsonl")).unwrap();
+        ConsentGuard::new(
+            signer().verifier(),
+            Arc::new(store),
+            vec![ConsentScope {
+                route: "BCI".into(),
+                purpose: Purpose::Therapy,
+                allow_actuation: false,
+            }],
+        )
+    }
+
+    fn receipt(subject: &str) -> ConsentReceipt {
+        let mut r = ConsentReceipt {
+            receipt_id: "r1".into(),
+            subject_id: id(subject),
+            route: "BCI".into(),
+            purpose: Purpose::Therapy,
+            data_categories: vec!["eeg_summary".into()],
+            allow_actuation: true,
+            not_before_unix: 100,
+            expires_at_unix: 200,
+            revocation_handle: "h1".into(),
+            signature: String::new(),
+        };
+        signer().sign(&mut r).unwrap();
+        r
+    }
+
+    fn request(kind: XRActionKind, fields: &[&str], consent: Option<ConsentReceipt>) -> Request {
+        let issuer = chord_seal::ChordIssuer::from_secret([7; 32]);
+        let capability = CapabilityChord::mint(
+            &issuer,
+            CapabilityKind::XRRoutePlan,
+            id(SUBJECT),
+            "BCI",
+            "SuggestOnly",
+            1024,
+            60,
+        )
+        .unwrap();
+        Request {
+            subject_id: id(SUBJECT),
+            route: "BCI".into(),
+            raw_prompt: None,
+            action: XRAction {
+                kind,
+                subject_id: id(SUBJECT),
+                route: "BCI".into(),
+                requested_fields: fields.iter().map(|f| f.to_string()).collect(),
+                lifeforce_cost: 0.0,
+                roh_before: 0.1,
+                roh_after_estimate: 0.1,
+                evolve_token: None,
+            },
+            capability,
+            purpose: Some(Purpose::Therapy),
+            consent,
+        }
+    }
+
+    fn code(guard: &ConsentGuard, req: &Request) -> Option<String> {
+        guard.check(req, &id(SUBJECT), NOW).err().map(|e| e.code)
+    }
+
+    #[test]
+    fn neural_reads_need_a_matching_live_receipt() {
+        let dir = tempfile::tempdir().unwrap();
+        let guard = guard(dir.path());
+        let read = |consent| request(XRActionKind::ReadNeuralShard, &["eeg_summary"], consent);
+
+        assert_eq!(code(&guard, &read(Some(receipt(SUBJECT)))), None);
+        assert_eq!(
+            code(&guard, &read(None)).as_deref(),
+            Some("CONSENT_MISSING")
+        );
+        assert_eq!(
+            guard
+                .check(&read(Some(receipt(SUBJECT))), &id(SUBJECT), 200)
+                .unwrap_err()
+                .code,
+            "CONSENT_EXPIRED"
+        );
+
+        let mut forged = receipt(SUBJECT);
+        forged.data_categories.push("dream_stage".into());
+        assert_eq!(
+            code(&guard, &read(Some(forged))).as_deref(),
+            Some("CONSENT_FORGED")
+        );
+        assert_eq!(
+            code(&guard, &read(Some(receipt(STRANGER)))).as_deref(),
+            Some("CONSENT_SUBJECT_MISMATCH")
+        );
+
+        let mut research = request(XRActionKind::ReadNeuralShard, &[], Some(receipt(SUBJECT)));
+        research.purpose = Some(Purpose::Research);
+        assert_eq!(
+            code(&guard, &research).as_deref(),
+            Some("CONSENT_PURPOSE_MISMATCH")
+        );
+        let uncovered = request(
+            XRActionKind::ReadNeuralShard,
+            &["dream_stage"],
+            Some(receipt(SUBJECT)),
+        );
+        assert_eq!(
+            code(&guard, &uncovered).as_deref(),
+            Some("CONSENT_CATEGORY_UNCOVERED")
+        );
+    }
+
+    #[test]
+    fn actuation_needs_an_actuating_scope() {
+        let dir = tempfile::tempdir().unwrap();
+        let guard = guard(dir.path());
+        let step = request(XRActionKind::XRRouteStep, &[], Some(receipt(SUBJECT)));
+        assert_eq!(code(&guard, &step).as_deref(), Some("CONSENT_NO_ACTUATION"));
+        // Nothing that neither reads neural data nor actuates needs consent.
+        assert_eq!(
+            code(&guard, &request(XRActionKind::ReadKeys, &[], None)),
+            None
+        );
+    }
+
+    #[test]
+    fn fail_closed_guard_honours_no_receipt() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = ConsentStore::open(dir.path().join("consent.jsonl")).unwrap();
+        let guard = ConsentGuard::fail_closed(Arc::new(store));
+        let read = request(
+            XRActionKind::ReadNeuralShard,
+            &["eeg_summary"],
+            Some(receipt(SUBJECT)),
+        );
+        assert_eq!(code(&guard, &read).as_deref(), Some("CONSENT_UNAVAILABLE"));
+        assert_eq!(
+            guard
+                .revoke(&receipt(SUBJECT), &id(SUBJECT), "withdrew")
+                .unwrap_err()
+                .code,
+            "CONSENT_UNAVAILABLE"
+        );
+        assert_eq!(
+            code(&guard, &request(XRActionKind::ReadKeys, &[], None)),
+            None
+        );
+    }
+
+    #[test]
+    fn only_the_owning_subject_revokes_a_receipt() {
+        let dir = tempfile::tempdir().unwrap();
+        let guard = guard(dir.path());
+        let read = request(
+            XRActionKind::ReadNeuralShard,
+            &["eeg_summary"],
+            Some(receipt(SUBJECT)),
+        );
+
+        // Revoking needs the subject's own signed receipt...
+        let mut unsigned = receipt(SUBJECT);
+        ConsentSigner::from_secret([9; 32])
+            .sign(&mut unsigned)
+            .unwrap();
+        assert_eq!(
+            guard
+                .revoke(&unsigned, &id(SUBJECT), "forged")
+                .unwrap_err()
+                .code,
+            "CONSENT_FORGED"
+        );
+        let ours = receipt(SUBJECT);
+        assert_eq!(
+            guard
+                .revoke(&ours, &id(STRANGER), "not mine")
+                .unwrap_err()
+                .code,
+            "CONSENT_SUBJECT_MISMATCH"
+        );
+        // ...nor does a stranger's revocation of the same handle reach it.
+        guard
+            .store()
+            .revoke("h1", &id(STRANGER), "guessed")
+            .unwrap();
+        assert_eq!(code(&guard, &read), None);
+
+        guard
+            .revoke(&ours, &id(SUBJECT), "subject withdrew")
+            .unwrap();
+        assert_eq!(code(&guard, &read).as_deref(), Some("CONSENT_REVOKED"));
+    }
+}
@@ -1,7 +1,8 @@
 use crate::{XRAction, XRActionKind};
 use crate::alnschemas::{NeurorightsPolicy, RohModel};
 use serde::{Deserialize, Serialize};
-use std::{collections::HashMap, fs, path::Path};
+use sovereign_core::RohEstimate;
+use std::collections::{BTreeMap, HashMap};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct GuardError {
@@ -16,28 +17,74 @@ pub struct RohGuard {
 }
 
 impl RohGuard {
-    pub fn from_file<P: AsRef<Path>>(path: P) -> anyhow::Result<Self> {
-        let model = RohModel::load(path)?;
-        Ok(Self { model })
+    pub fn new(model: RohModel) -> Self {
+        Self { model }
     }
 
-    pub fn check(&self, action: &XRAction) -> Result<(), GuardError> {
-        if action.roh_after_estimate > self.model.ceiling {
+    /// Model estimate for `action` on `route`: `lifeforce_cost` is the
+    /// `lifeforce_load` axis, the other axes are the node's own readings
+    /// (see [`NodeTelemetry`](crate::telemetry::NodeTelemetry)).
+    pub fn estimate(
+        &self,
+        action: &XRAction,
+        route: &str,
+        node_axes: &BTreeMap<String, f32>,
+    ) -> RohEstimate {
+        let mut axes = node_axes.clone();
+        axes.insert("lifeforce_load".into(), action.lifeforce_cost);
+        self.model.estimate(&axes, route)
+    }
+
+    /// Enforces the ceiling and monotone safety on the model. RoH before is
+    /// the model estimate; RoH after is that estimate moved by the change
+    /// the caller declares (`roh_after_estimate - roh_before`), so a caller
+    /// whose absolute figures are off is judged on its change alone. The
+    /// ceiling also holds against the caller's `roh_after_estimate` if that
+    /// is higher.
+    pub fn check(
+        &self,
+        action: &XRAction,
+        route: &str,
+        node_axes: &BTreeMap<String, f32>,
+    ) -> Result<RohSpan, GuardError> {
+        let estimate = self.estimate(action, route, node_axes);
+        let before = estimate.roh.0;
+        let after = before + (action.roh_after_estimate - action.roh_before);
+        let peak = estimate.floor(action.roh_after_estimate).0.max(after);
+        if peak > self.model.ceiling {
             return Err(GuardError {
                 code: "ROH_CEILING".into(),
                 message: format!(
-                    "RoH estimate {} exceeds ceiling {}",
-                    action.roh_after_estimate, self.model.ceiling
+                    "RoH estimate {} exceeds ceiling {} (model {:?})",
+                    peak, self.model.ceiling, estimate.contributions
                 ),
             });
         }
-        if action.roh_after_estimate > action.roh_before {
+        if after.is_nan() || after > before {
             return Err(GuardError {
                 code: "ROH_MONOTONE".into(),
-                message: "RoH monotone safety violated (would increase)".into(),
+                message: format!(
+                    "RoH monotone safety violated (would increase from {} to {})",
+                    before, after
+                ),
             });
         }
-        Ok(())
+        Ok(RohSpan { estimate, after })
+    }
+}
+
+/// RoH across one action as the [`RohGuard`] judged it.
+#[derive(Debug, Clone)]
+pub struct RohSpan {
+    /// Model estimate at the node's operating point; its `roh` is RoH before.
+    pub estimate: RohEstimate,
+    /// RoH after the change the action declares.
+    pub after: f32,
+}
+
+impl RohSpan {
+    pub fn before(&self) -> f32 {
+        self.estimate.roh.0
     }
 }
 
@@ -48,10 +95,8 @@ pub struct NeurorightsGuard {
 }
 
 impl NeurorightsGuard {
-    pub fn from_file<P: AsRef<Path>>(path: P) -> anyhow::Result<Self> {
-        let text = fs::read_to_string(path)?;
-        let policy: NeurorightsPolicy = serde_json::from_str(&text)?;
-        Ok(Self { policy })
+    pub fn new(policy: NeurorightsPolicy) -> Self {
+        Self { policy }
     }
 
     pub fn check_action(&self, action: &XRAction, route: &str) -> Result<(), GuardError> {
@@ -90,10 +135,8 @@ pub struct EcoGuard {
 }
 
 impl EcoGuard {
-    pub fn from_file<P: AsRef<Path>>(path: P) -> anyhow::Result<Self> {
-        let text = fs::read_to_string(path)?;
-        let envs: HashMap<String, EcoEnvelope> = serde_json::from_str(&text)?;
-        Ok(Self { envelopes_by_route: envs })
+    pub fn new(envelopes_by_route: HashMap<String, EcoEnvelope>) -> Self {
+        Self { envelopes_by_route }
     }
 
     pub fn check(&self, action: &XRAction, route: &str) -> Result<(), GuardError> {
@@ -108,3 +151,60 @@ impl EcoGuard {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sovereign_core::ROH_AXES;
+
+    fn guard() -> RohGuard {
+        RohGuard::new(RohModel {
+            ceiling: 0.3,
+            weights: ROH_AXES.iter().map(|a| (a.to_string(), 0.1)).collect(),
+        })
+    }
+
+    fn action(lifeforce_cost: f32, roh_before: f32, roh_after_estimate: f32) -> XRAction {
+        XRAction {
+            kind: XRActionKind::ApplyOta,
+            subject_id: crate::testutil::SUBJECT.parse().unwrap(),
+            route: "OTA".into(),
+            requested_fields: Vec::new(),
+            lifeforce_cost,
+            roh_before,
+            roh_after_estimate,
+            evolve_token: None,
+        }
+    }
+
+    fn code(action: &XRAction, fatigue: f32) -> Option<String> {
+        let axes = BTreeMap::from([("biospec_fatigue".to_string(), fatigue)]);
+        guard().check(action, "OTA", &axes).err().map(|e| e.code)
+    }
+
+    #[test]
+    fn monotone_compares_the_model_before_and_after() {
+        // The node is above what the caller believes; an action that
+        // declares no change is not a rise.
+        assert_eq!(code(&action(0.5, 0.05, 0.05), 0.9), None);
+        let axes = BTreeMap::from([("biospec_fatigue".to_string(), 0.9)]);
+        let span = guard()
+            .check(&action(0.5, 0.2, 0.15), "OTA", &axes)
+            .unwrap();
+        assert!((span.before() - span.after - 0.05).abs() < 1e-6);
+
+        assert_eq!(
+            code(&action(0.0, 0.05, 0.1), 0.0).as_deref(),
+            Some("ROH_MONOTONE")
+        );
+        assert_eq!(
+            code(&action(0.0, 0.1, f32::NAN), 0.0).as_deref(),
+            Some("ROH_MONOTONE")
+        );
+        // A caller estimate above the ceiling is still refused.
+        assert_eq!(
+            code(&action(0.0, 0.4, 0.35), 0.0).as_deref(),
+            Some("ROH_CEILING")
+        );
+    }
+}
@@ -1,13 +1,36 @@
 use serde::{Deserialize, Serialize};
+use sovereign_core::RohEstimate;
+use std::sync::Arc;
 use uuid::Uuid;
 
+use subject_id::SubjectId;
+
+use chord_seal::{
+    extend_tag, Caveat, ChordIssuer, ChordRef, ChordStore, ChordUsage, ChordVerifier, Grant,
+    SealError, StoreError,
+};
+
+use consent_receipt::{ConsentReceipt, Purpose};
+use donutloop::{digest_hex, DonutloopEntry, DonutloopWriter, EntryDraft, LedgerError, Verdict};
+use evolve_token_verifier::{BostromClient, EvolveGated, EvolveGuard, EvolveToken};
+
 pub mod alnschemas;
+pub mod binding;
+pub mod budget;
+pub mod consent;
 pub mod guardians;
 pub mod firewall;
+pub mod policy;
+pub mod telemetry;
+#[cfg(test)]
+mod testutil;
 
-use alnschemas::{NeurorightsPolicy, RohModel};
+use binding::SubjectBinding;
+use budget::EvolveBudgetGuard;
+use consent::ConsentGuard;
 use firewall::{FirewallDecision, MetaFirewall};
-use guardians::{EcoGuard, NeurorightsGuard, RohGuard};
+use policy::SharedPolicy;
+use telemetry::NodeTelemetry;
 
 /// Shared error type used by all guards.
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -33,12 +56,33 @@ pub enum XRActionKind {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct XRAction {
     pub kind: XRActionKind,
-    pub subject_id: String,
+    pub subject_id: SubjectId,
     pub route: String,          // "BCI", "OTA", "GOV", "CHAT", "XR"
     pub requested_fields: Vec<String>,
     pub lifeforce_cost: f32,
     pub roh_before: f32,
     pub roh_after_estimate: f32,
+    /// EVOLVE token authorizing structural / OTA actions.
+    #[serde(default)]
+    pub evolve_token: Option<EvolveToken>,
+}
+
+impl EvolveGated for XRAction {
+    fn requires_evolve_token(&self) -> bool {
+        matches!(self.kind, XRActionKind::ProposeEvolve | XRActionKind::ApplyOta)
+    }
+
+    fn evolve_token(&self) -> Option<&EvolveToken> {
+        self.evolve_token.as_ref()
+    }
+
+    fn evolve_scope(&self) -> String {
+        format!("{:?}", self.kind)
+    }
+
+    fn roh_span(&self) -> (f32, f32) {
+        (self.roh_before, self.roh_after_estimate)
+    }
 }
 
 /// Capability kinds for AI / tool calls.
@@ -50,19 +94,117 @@ pub enum CapabilityKind {
     XRRoutePlan,
 }
 
-/// Short‑lived, typed capability descriptor.
+/// Short‑lived, typed capability descriptor, sealed by the node's issuer key.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CapabilityChord {
     pub id: Uuid,
     pub kind: CapabilityKind,
-    pub subject_id: String,
+    pub subject_id: SubjectId,
+    /// Route the chord is valid on: "BCI", "OTA", "GOV", "CHAT", "XR".
+    pub route: String,
     pub max_tokens: u32,
     pub expires_at_unix: i64,
     /// "SuggestOnly", "ConfigOnly", etc.
     pub actuation_rights: String,
+    /// Hex Ed25519 seal over the claims; dropped once caveats are appended.
+    #[serde(default)]
+    pub seal: String,
+    /// Restrictions appended by holders delegating a narrower chord.
+    #[serde(default)]
+    pub caveats: Vec<Caveat>,
+    /// HMAC tag chain over the claims and each caveat.
+    #[serde(default)]
+    pub caveat_tag: String,
+}
+
+/// Signed view of a chord: all fields except the seal.
+#[derive(Serialize)]
+struct ChordClaims<'a> {
+    id: &'a Uuid,
+    kind: &'a CapabilityKind,
+    subject_id: &'a str,
+    route: &'a str,
+    max_tokens: u32,
+    expires_at_unix: i64,
+    actuation_rights: &'a str,
 }
 
 impl CapabilityChord {
+    /// Mint a chord bound to `subject_id`, `route` and `kind`, valid for `ttl_secs`.
+    pub fn mint(
+        issuer: &ChordIssuer,
+        kind: CapabilityKind,
+        subject_id: SubjectId,
+        route: impl Into<String>,
+        actuation_rights: impl Into<String>,
+        max_tokens: u32,
+        ttl_secs: i64,
+    ) -> Result<Self, SealError> {
+        let mut chord = Self {
+            id: Uuid::new_v4(),
+            kind,
+            subject_id,
+            route: route.into(),
+            max_tokens,
+            expires_at_unix: chrono::Utc::now().timestamp() + ttl_secs,
+            actuation_rights: actuation_rights.into(),
+            seal: String::new(),
+            caveats: Vec::new(),
+            caveat_tag: String::new(),
+        };
+        chord.seal = issuer.seal(&chord.claims())?;
+        chord.caveat_tag = issuer.caveat_tag(&chord.claims())?;
+        Ok(chord)
+    }
+
+    /// Narrower copy for a sub-agent. Caveats that would widen the current
+    /// grant are refused here as well as at verification.
+    pub fn attenuate(&self, caveat: Caveat) -> Result<Self, SealError> {
+        let mut grant = self.base_grant();
+        for c in &self.caveats {
+            grant.restrict(c)?;
+        }
+        grant.restrict(&caveat)?;
+        let mut child = self.clone();
+        child.caveat_tag = extend_tag(&self.caveat_tag, &caveat)?;
+        child.caveats.push(caveat);
+        child.seal.clear();
+        Ok(child)
+    }
+
+    fn base_grant(&self) -> Grant {
+        Grant {
+            expires_at_unix: self.expires_at_unix,
+            route: self.route.clone(),
+            max_tokens: Some(self.max_tokens),
+            max_calls: None,
+            domains: None,
+        }
+    }
+
+    fn claims(&self) -> ChordClaims<'_> {
+        ChordClaims {
+            id: &self.id,
+            kind: &self.kind,
+            subject_id: self.subject_id.as_str(),
+            route: &self.route,
+            max_tokens: self.max_tokens,
+            expires_at_unix: self.expires_at_unix,
+            actuation_rights: &self.actuation_rights,
+        }
+    }
+
+    /// Verify the seal or caveat chain and return what the chord permits.
+    pub fn grant(&self, verifier: &ChordVerifier) -> Result<Grant, SealError> {
+        verifier.grant(
+            &self.claims(),
+            &self.seal,
+            self.base_grant(),
+            &self.caveats,
+            &self.caveat_tag,
+        )
+    }
+
     pub fn is_expired(&self) -> bool {
         let now = chrono::Utc::now().timestamp();
         now > self.expires_at_unix
@@ -72,8 +214,8 @@ impl CapabilityChord {
 /// High‑level request into Tsafe Cortex Gate.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Request {
-    /// Bostrom / zeta / 0x519f...
-    pub subject_id: String,
+    /// Bostrom / zeta / 0x519f... / did:key, checked when deserialized.
+    pub subject_id: SubjectId,
     /// "BCI", "OTA", "GOV", "CHAT", "XR"
     pub route: String,
     /// Only for LLM‑mediated flows.
@@ -81,6 +223,12 @@ pub struct Request {
     /// Typed, non‑text action.
     pub action: XRAction,
     pub capability: CapabilityChord,
+    /// Purpose the data or actuation is for; must match the receipt.
+    #[serde(default)]
+    pub purpose: Option<Purpose>,
+    /// Subject-signed consent, required for actuation and neural reads.
+    #[serde(default)]
+    pub consent: Option<ConsentReceipt>,
 }
 
 /// Authorized action with optional constraints (redactions, rate limits).
@@ -88,6 +236,10 @@ pub struct Request {
 pub struct AuthorizedAction {
     pub action: XRAction,
     pub constraints: Vec<String>,
+    /// Hash of the policy snapshot that authorized the action.
+    pub policy_bundle_hash: String,
+    /// RoH model estimate with per-axis contributions, for audit.
+    pub roh_estimate: RohEstimate,
 }
 
 /// Structured rejection reason.
@@ -100,66 +252,269 @@ pub struct RejectionReason {
 /// Final authorization result.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum AuthorizationResult {
-    Authorized(AuthorizedAction),
+    Authorized(Box<AuthorizedAction>),
     Rejected(RejectionReason),
 }
 
-/// Minimal placeholder: later, append‑only writer to shards/ledger/donutloop.aln.
+/// Hash-linked audit writer for shards/ledger/donutloop.aln.
 pub struct DonutloopLogger {
-    log_path: std::path::PathBuf,
+    writer: Arc<DonutloopWriter>,
 }
 
 impl DonutloopLogger {
-    pub fn new<P: AsRef<std::path::Path>>(path: P) -> Self {
-        Self { log_path: path.as_ref().to_path_buf() }
+    /// Open the ledger. Each entry is stamped with the hash of the policy
+    /// bundle passed alongside it, since the gate's policy can be swapped.
+    pub fn open<P: AsRef<std::path::Path>>(path: P) -> Result<Self, LedgerError> {
+        Ok(Self::from_writer(Arc::new(DonutloopWriter::open(path, "")?)))
+    }
+
+    pub fn from_writer(writer: Arc<DonutloopWriter>) -> Self {
+        Self { writer }
+    }
+
+    /// The one writer for this ledger, for other loggers on the node (e.g.
+    /// the soul-non-tradeable shield) to append through.
+    pub fn writer(&self) -> &Arc<DonutloopWriter> {
+        &self.writer
+    }
+
+    pub fn log_allow(&self, req: &Request, bundle: &str) -> Result<DonutloopEntry, LedgerError> {
+        self.append(req, bundle, Verdict::Allow, "ALLOW", "authorized")
+    }
+
+    pub fn log_reject(
+        &self,
+        req: &Request,
+        bundle: &str,
+        code: &str,
+    ) -> Result<DonutloopEntry, LedgerError> {
+        self.append(req, bundle, Verdict::Reject, code, "rejected by guard")
     }
 
-    pub fn log_allow(&self, _req: &Request) {
-        // TODO: append hash‑linked entry to .donutloop.aln
+    /// Record a consent withdrawal; `request_digest` is over the handle.
+    pub fn log_revoked(
+        &self,
+        subject_id: &SubjectId,
+        handle: &str,
+        reason: &str,
+        bundle: &str,
+    ) -> Result<DonutloopEntry, LedgerError> {
+        self.writer.append_under(EntryDraft {
+            subject_id: subject_id.to_string(),
+            route: "CONSENT".into(),
+            verdict: Verdict::Revoke,
+            guard_code: "CONSENT_REVOKED".into(),
+            reason: reason.to_string(),
+            request_digest: digest_hex(handle.as_bytes()),
+        }, bundle)
     }
 
-    pub fn log_reject(&self, _req: &Request, _code: &str) {
-        // TODO: append rejection entry to .donutloop.aln
+    pub fn log_blocked(
+        &self,
+        req: &Request,
+        bundle: &str,
+        reason: &str,
+    ) -> Result<DonutloopEntry, LedgerError> {
+        self.append(req, bundle, Verdict::Blocked, "FIREWALL", reason)
     }
 
-    pub fn log_blocked(&self, _req: &Request, _reason: &str) {
-        // TODO: append firewall‑block entry
+    fn append(
+        &self,
+        req: &Request,
+        bundle: &str,
+        verdict: Verdict,
+        code: &str,
+        reason: &str,
+    ) -> Result<DonutloopEntry, LedgerError> {
+        let request_digest = digest_hex(&serde_json::to_vec(req)?);
+        self.writer.append_under(EntryDraft {
+            subject_id: req.subject_id.to_string(),
+            route: req.route.clone(),
+            verdict,
+            guard_code: code.to_string(),
+            reason: reason.to_string(),
+            request_digest,
+        }, bundle)
     }
 }
 
+/// Everything a [`TsafeCortexGate`] enforces with, wired by the node at boot.
+pub struct TsafeCortexGateConfig {
+    pub firewall: MetaFirewall,
+    pub policy: SharedPolicy,
+    pub evolve_guard: EvolveGuard<Box<dyn BostromClient + Send + Sync>>,
+    pub evolve_budget: EvolveBudgetGuard,
+    pub consent: ConsentGuard,
+    pub binding: SubjectBinding,
+    pub telemetry: NodeTelemetry,
+    pub chord_verifier: ChordVerifier,
+    pub chord_store: Arc<ChordStore>,
+    pub donutlogger: DonutloopLogger,
+}
+
 /// Main Tsafe Cortex Gate type for XR / AI‑chat integration.
 pub struct TsafeCortexGate {
     firewall: MetaFirewall,
-    roh_guard: RohGuard,
-    nr_guard: NeurorightsGuard,
-    eco_guard: EcoGuard,
+    /// RoH, neurorights and eco guards; swapped whole by `PolicyReloader`.
+    policy: SharedPolicy,
+    evolve_guard: EvolveGuard<Box<dyn BostromClient + Send + Sync>>,
+    evolve_budget: EvolveBudgetGuard,
+    consent: ConsentGuard,
+    binding: SubjectBinding,
+    /// Node-side RoH axis readings; requests never supply their own.
+    telemetry: NodeTelemetry,
+    chord_verifier: ChordVerifier,
+    chord_store: Arc<ChordStore>,
     donutlogger: DonutloopLogger,
 }
 
 impl TsafeCortexGate {
-    pub fn new(
-        firewall: MetaFirewall,
-        roh_guard: RohGuard,
-        nr_guard: NeurorightsGuard,
-        eco_guard: EcoGuard,
-        donutlogger: DonutloopLogger,
-    ) -> Self {
-        Self { firewall, roh_guard, nr_guard, eco_guard, donutlogger }
+    pub fn new(cfg: TsafeCortexGateConfig) -> Self {
+        let TsafeCortexGateConfig {
+            firewall,
+            policy,
+            evolve_guard,
+            evolve_budget,
+            consent,
+            binding,
+            telemetry,
+            chord_verifier,
+            chord_store,
+            donutlogger,
+        } = cfg;
+        Self {
+            firewall,
+            policy,
+            evolve_guard,
+            evolve_budget,
+            consent,
+            binding,
+            telemetry,
+            chord_verifier,
+            chord_store,
+            donutlogger,
+        }
+    }
+
+    /// Charge LLM tokens spent under `cap` against its (possibly delegated)
+    /// `max_tokens`. Fails once the chord is revoked or over budget.
+    pub fn charge_tokens(&self, cap: &CapabilityChord, tokens: u64) -> Result<ChordUsage, StoreError> {
+        let grant = cap
+            .grant(&self.chord_verifier)
+            .map_err(|e| StoreError::Revoked(format!("{} ({})", cap.id, e)))?;
+        let subject = self
+            .binding
+            .resolve(&cap.subject_id)
+            .ok_or_else(|| StoreError::Revoked(format!("{} (foreign subject)", cap.id)))?;
+        let (id, kind) = (cap.id.to_string(), format!("{:?}", cap.kind));
+        let chord = ChordRef { id: &id, subject_id: subject, kind: &kind };
+        self.chord_store.consume(chord, 0, tokens, None, grant.max_tokens)
     }
 
+    /// Withdraw every consent receipt carrying `receipt`'s revocation
+    /// handle. Only `subject_id`'s own signed receipts can be withdrawn. The
+    /// revocation is durable and in the donutloop before this returns, and
+    /// the very next request presenting such a receipt is rejected.
+    pub fn revoke_consent(
+        &self,
+        subject_id: &SubjectId,
+        receipt: &ConsentReceipt,
+        reason: &str,
+    ) -> Result<DonutloopEntry, GuardError> {
+        self.consent
+            .revoke(receipt, subject_id, reason)
+            .map_err(|e| GuardError {
+                code: e.code,
+                message: e.message,
+            })?;
+        let handle = &receipt.revocation_handle;
+        let policy = self.policy.load_full();
+        self.donutlogger
+            .log_revoked(subject_id, handle, reason, &policy.hash)
+            .map_err(|e| GuardError {
+                code: "DONUTLOOP_UNAVAILABLE".into(),
+                message: format!("Revocation applied but not recorded: {}", e),
+            })
+    }
+
+    /// The chord must carry a valid seal (or caveat chain), cover the
+    /// request route and not be revoked or exhausted. Revocations are
+    /// matched against `subject`, the canonical subject the request bound to.
+    /// Nothing is charged here; [`Self::charge_call`] does once every guard
+    /// has passed.
+    fn check_chord(&self, req: &Request, subject: &SubjectId) -> Result<Grant, RejectionReason> {
+        let cap = &req.capability;
+        let grant = cap.grant(&self.chord_verifier).map_err(|err| {
+            let code = match err {
+                SealError::Missing => "CAPABILITY_UNSIGNED",
+                SealError::Widens(_) => "CAPABILITY_CAVEAT_WIDENS",
+                _ => "CAPABILITY_FORGED",
+            };
+            RejectionReason { code: code.into(), message: err.to_string() }
+        })?;
+        if !grant.covers_route(&req.route) {
+            return Err(RejectionReason {
+                code: "CAPABILITY_ROUTE_MISMATCH".into(),
+                message: format!("Capability chord is bound to route {}", grant.route),
+            });
+        }
+        // Revocation is checked on every request, not only at expiry.
+        self.charge_call(cap, subject, &grant, false)?;
+        Ok(grant)
+    }
+
+    /// Check (`charge == false`) or charge one call to `cap` under `grant`.
+    fn charge_call(
+        &self,
+        cap: &CapabilityChord,
+        subject: &SubjectId,
+        grant: &Grant,
+        charge: bool,
+    ) -> Result<(), RejectionReason> {
+        let (id, kind) = (cap.id.to_string(), format!("{:?}", cap.kind));
+        let chord = ChordRef { id: &id, subject_id: subject, kind: &kind };
+        let result = if charge {
+            self.chord_store.consume(chord, 1, 0, grant.max_calls, grant.max_tokens)
+        } else {
+            self.chord_store.check(chord, 1, 0, grant.max_calls, grant.max_tokens)
+        };
+        match result {
+            Ok(_) => Ok(()),
+            Err(StoreError::Revoked(_)) => Err(RejectionReason {
+                code: "CAPABILITY_REVOKED".into(),
+                message: format!("Capability chord {} has been revoked", cap.id),
+            }),
+            Err(StoreError::Exhausted { limit, .. }) => Err(RejectionReason {
+                code: "CAPABILITY_EXHAUSTED".into(),
+                message: format!("Capability chord {} exhausted its {}", cap.id, limit),
+            }),
+            Err(err) => Err(RejectionReason {
+                code: "CAPABILITY_STORE_UNAVAILABLE".into(),
+                message: err.to_string(),
+            }),
+        }
+    }
+
+    /// Rejections are returned even if their audit entry cannot be written;
+    /// an authorization is only granted once its entry is on disk.
+    ///
+    /// The whole request is decided under the policy snapshot current when
+    /// it arrived, and every entry names that snapshot.
     pub fn authorize(&self, req: Request) -> AuthorizationResult {
+        let policy = self.policy.load_full();
+        let bundle = policy.hash.as_str();
         // 1. Tsafe / NeuralTrust‑style firewall over text.
         if let Some(prompt) = &req.raw_prompt {
             match self.firewall.evaluate_prompt(prompt, &req.route) {
                 FirewallDecision::Block => {
-                    self.donutlogger.log_blocked(&req, "firewall_block");
+                    let _ = self.donutlogger.log_blocked(&req, bundle, "firewall_block");
                     return AuthorizationResult::Rejected(RejectionReason {
                         code: "FIREWALL_BLOCK".into(),
                         message: "Prompt blocked by Tsafe Cortex Gate firewall".into(),
                     });
                 }
                 FirewallDecision::Quarantine => {
-                    self.donutlogger.log_blocked(&req, "firewall_quarantine");
+                    let _ = self.donutlogger.log_blocked(&req, bundle, "firewall_quarantine");
                     return AuthorizationResult::Rejected(RejectionReason {
                         code: "FIREWALL_QUARANTINE".into(),
                         message: "Prompt requires human review".into(),
@@ -169,9 +524,40 @@ impl TsafeCortexGate {
             }
         }
 
-        // 2. CapabilityChord gate (actuation is never granted to CHAT / SuggestOnly).
-        if req.capability.is_expired() {
-            self.donutlogger.log_reject(&req, "CAPABILITY_EXPIRED");
+        // 1b. Without a verified policy bundle the node is CHAT-only.
+        if let Some(reason) = &policy.chat_only {
+            if req.route != "CHAT" || !matches!(req.action.kind, XRActionKind::XRRouteStep) {
+                let _ = self.donutlogger.log_reject(&req, bundle, "POLICY_BUNDLE_UNVERIFIED");
+                return AuthorizationResult::Rejected(RejectionReason {
+                    code: "POLICY_BUNDLE_UNVERIFIED".into(),
+                    message: format!("Node is fail-closed to CHAT only: {}", reason),
+                });
+            }
+        }
+
+        // 2. Subject / route / kind binding across request, action and chord.
+        //    Later guards see the canonical subject, not the address sent.
+        let subject = match self.binding.bind(&req) {
+            Ok(subject) => subject,
+            Err(err) => {
+                let _ = self.donutlogger.log_reject(&req, bundle, &err.code);
+                return AuthorizationResult::Rejected(RejectionReason {
+                    code: err.code,
+                    message: err.message,
+                });
+            }
+        };
+
+        // 3. CapabilityChord gate (actuation is never granted to CHAT / SuggestOnly).
+        let grant = match self.check_chord(&req, &subject) {
+            Ok(grant) => grant,
+            Err(reason) => {
+                let _ = self.donutlogger.log_reject(&req, bundle, &reason.code);
+                return AuthorizationResult::Rejected(reason);
+            }
+        };
+        if chrono::Utc::now().timestamp() > grant.expires_at_unix {
+            let _ = self.donutlogger.log_reject(&req, bundle, "CAPABILITY_EXPIRED");
             return AuthorizationResult::Rejected(RejectionReason {
                 code: "CAPABILITY_EXPIRED".into(),
                 message: "Capability chord is expired".into(),
@@ -180,46 +566,109 @@ impl TsafeCortexGate {
         if req.capability.actuation_rights == "SuggestOnly"
             && matches!(req.action.kind, XRActionKind::ApplyOta | XRActionKind::ProposeEvolve)
         {
-            self.donutlogger.log_reject(&req, "CAPABILITY_SUGGEST_ONLY");
+            let _ = self.donutlogger.log_reject(&req, bundle, "CAPABILITY_SUGGEST_ONLY");
             return AuthorizationResult::Rejected(RejectionReason {
                 code: "CAPABILITY_SUGGEST_ONLY".into(),
                 message: "This capability cannot actuate OTA or EVOLVE".into(),
             });
         }
 
-        // 3. Neurorights guard (mental privacy, dreamstate, soulnontradeable).
-        if let Err(err) = self.nr_guard.check_action(&req.action, &req.route) {
-            self.donutlogger.log_reject(&req, &err.code);
+        // 4. Neurorights guard (mental privacy, dreamstate, soulnontradeable).
+        if let Err(err) = policy.neurorights.check_action(&req.action, &req.route) {
+            let _ = self.donutlogger.log_reject(&req, bundle, &err.code);
+            return AuthorizationResult::Rejected(RejectionReason {
+                code: err.code,
+                message: err.message,
+            });
+        }
+
+        // 4b. Consent receipts (purpose-bound, revocable) for actuation and neural reads.
+        if let Err(err) = self.consent.check(&req, &subject, chrono::Utc::now().timestamp()) {
+            let _ = self.donutlogger.log_reject(&req, bundle, &err.code);
             return AuthorizationResult::Rejected(RejectionReason {
                 code: err.code,
                 message: err.message,
             });
         }
 
-        // 4. RoH guard (0.3 ceiling, monotone safety).
-        if let Err(err) = self.roh_guard.check(&req.action) {
-            self.donutlogger.log_reject(&req, &err.code);
+        // 5. RoH guard (0.3 ceiling, monotone safety), on the model estimate
+        //    from node telemetry rather than the RoH the caller reports.
+        let roh_span = match self
+            .telemetry
+            .axes()
+            .and_then(|axes| policy.roh.check(&req.action, &req.route, &axes))
+        {
+            Ok(span) => span,
+            Err(err) => {
+                let _ = self.donutlogger.log_reject(&req, bundle, &err.code);
+                return AuthorizationResult::Rejected(RejectionReason {
+                    code: err.code,
+                    message: err.message,
+                });
+            }
+        };
+
+        // 6. Eco / lifeforce envelopes via .ocpuenv, .lifeforce.aln.
+        if let Err(err) = policy.eco.check(&req.action, &req.route) {
+            let _ = self.donutlogger.log_reject(&req, bundle, &err.code);
             return AuthorizationResult::Rejected(RejectionReason {
                 code: err.code,
                 message: err.message,
             });
         }
 
-        // 5. Eco / lifeforce envelopes via .ocpuenv, .lifeforce.aln.
-        if let Err(err) = self.eco_guard.check(&req.action, &req.route) {
-            self.donutlogger.log_reject(&req, &err.code);
+        // 6b. Daily structural-evolution budget (CitizenStake rate limit).
+        if let Err(err) =
+            self.evolve_budget
+                .check(&req.action, &roh_span, chrono::Utc::now().timestamp())
+        {
+            let _ = self.donutlogger.log_reject(&req, bundle, &err.code);
             return AuthorizationResult::Rejected(RejectionReason {
                 code: err.code,
                 message: err.message,
             });
         }
 
-        // TODO: 6. EVOLVE token verification for structural / OTA actions.
+        // 7. EVOLVE token verification for structural / OTA actions.
+        if let Err(err) =
+            self.evolve_guard
+                .check(&req.action, subject.as_str(), chrono::Utc::now().timestamp())
+        {
+            let _ = self.donutlogger.log_reject(&req, bundle, &err.code);
+            return AuthorizationResult::Rejected(RejectionReason {
+                code: err.code,
+                message: err.message,
+            });
+        }
 
-        self.donutlogger.log_allow(&req);
-        AuthorizationResult::Authorized(AuthorizedAction {
+        // 8. Every guard passed: spend the EVOLVE token and charge the chord
+        //    call. The token goes back if the call or the allow entry fails;
+        //    a call charged for an unrecorded allow only errs on the safe side.
+        if let Err(err) = self.evolve_guard.spend(&req.action) {
+            let _ = self.donutlogger.log_reject(&req, bundle, &err.code);
+            return AuthorizationResult::Rejected(RejectionReason {
+                code: err.code,
+                message: err.message,
+            });
+        }
+        if let Err(reason) = self.charge_call(&req.capability, &subject, &grant, true) {
+            self.evolve_guard.release(&req.action);
+            let _ = self.donutlogger.log_reject(&req, bundle, &reason.code);
+            return AuthorizationResult::Rejected(reason);
+        }
+        if let Err(err) = self.donutlogger.log_allow(&req, bundle) {
+            self.evolve_guard.release(&req.action);
+            return AuthorizationResult::Rejected(RejectionReason {
+                code: "DONUTLOOP_UNAVAILABLE".into(),
+                message: format!("Authorization not recorded: {}", err),
+            });
+        }
+        AuthorizationResult::Authorized(Box::new(AuthorizedAction {
             action: req.action,
-            constraints: Vec::new(),
-        })
+            // Token budget after any delegation caveats.
+            constraints: grant.max_tokens.map(|m| format!("max_tokens={}", m)).into_iter().collect(),
+            policy_bundle_hash: policy.hash.clone(),
+            roh_estimate: roh_span.estimate,
+        }))
     }
 }
@@ -0,0 +1,686 @@
+//! Hot-swappable policy snapshot for the gate.
+//!
+//! The gate reads one `Arc<PolicySnapshot>` per request, so a request that
+//! started under one bundle finishes under it even if a reload lands midway.
+//! A reload only swaps once the new bundle parses, lints clean and, for any
+//! changed SOVEREIGNCONFIG shard, is pinned by an approved EVOLVE record.
+//!
+//! SOVEREIGNCONFIG shards are only ever read out of a [`VerifiedBundle`]. If
+//! the bundle is unsigned or does not match its manifest, the node runs a
+//! fail-closed snapshot that admits nothing but CHAT route steps.
+
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::thread::JoinHandle;
+use std::time::Duration;
+
+use arc_swap::ArcSwap;
+use donutloop::{anchor_path, digest_hex, policy_bundle_hash_bytes, Anchor};
+use policy_bundle::{BundleError, StakeAnchor, VerifiedBundle, SOVEREIGN_SHARDS};
+use policy_lint::{Diagnostic, Severity, LINTED_FILES};
+use sovereign_core::{EvolutionProposal, ProposalState, ROH_AXES};
+use sovereign_evolve::Governance;
+use sovereign_specs::evolve_ledger::verify_ledger;
+use thiserror::Error;
+
+use crate::alnschemas::{NeurorightsPolicy, RohModel};
+use crate::guardians::{EcoEnvelope, EcoGuard, NeurorightsGuard, RohGuard};
+
+pub const ROHMODEL: &str = "rohmodel.aln";
+pub const NEURORIGHTS: &str = "neurorights.json";
+pub const ECOENV: &str = "ecoenv.json";
+
+/// Files compiled into a snapshot, in hashing order.
+const BUNDLE_FILES: [&str; 3] = [ROHMODEL, NEURORIGHTS, ECOENV];
+
+/// Bundle files that are SOVEREIGNCONFIG shards and so need EVOLVE to change.
+const SOVEREIGN_FILES: [&str; 2] = [ROHMODEL, NEURORIGHTS];
+
+/// Guards compiled from one policy bundle, plus the hash that names it.
+pub struct PolicySnapshot {
+    /// `donutloop::policy_bundle_hash` over the exact bytes compiled here.
+    pub hash: String,
+    pub roh: RohGuard,
+    pub neurorights: NeurorightsGuard,
+    pub eco: EcoGuard,
+    /// Why the node is in fail-closed CHAT-only mode, if it is.
+    pub chat_only: Option<String>,
+    /// SHA-256 hex of each file, keyed by name.
+    digests: BTreeMap<String, String>,
+    /// Bundle hash over every shard the lint checked, so a change to a
+    /// linted-only shard (`tsafe.aln`, `vkernel.aln`) is re-linted too.
+    linted: String,
+    /// Sequence and hash of the signed manifest compiled here; a reload
+    /// never goes back to an older one. Empty hash when none was verified.
+    sequence: u64,
+    manifest_hash: String,
+}
+
+/// Shared, atomically replaceable snapshot held by the gate and the reloader.
+pub type SharedPolicy = Arc<ArcSwap<PolicySnapshot>>;
+
+/// Raw bundle bytes, read once so hash, digests, parse and lint all agree.
+struct BundleBytes {
+    sequence: u64,
+    manifest_hash: String,
+    files: Vec<(&'static str, Vec<u8>)>,
+    /// Every shard `policy_lint` checks, from the same read as `files`.
+    linted: BTreeMap<&'static str, Vec<u8>>,
+}
+
+impl BundleBytes {
+    /// Sovereign files come from the verified bundle, the rest from `dir`.
+    /// With `current`, a manifest older than the one it was compiled from is
+    /// refused as a rollback.
+    fn read(
+        dir: &Path,
+        anchor: &StakeAnchor,
+        current: Option<&PolicySnapshot>,
+    ) -> Result<Self, ReloadError> {
+        let verified = VerifiedBundle::open(dir, anchor)?;
+        if let Some(current) = current.filter(|c| !c.manifest_hash.is_empty()) {
+            verified.ensure_not_older(current.sequence, &current.manifest_hash)?;
+        }
+        let files = BUNDLE_FILES
+            .iter()
+            .map(|name| {
+                let bytes = if SOVEREIGN_FILES.contains(name) {
+                    Ok(verified.bytes(name)?.to_vec())
+                } else {
+                    fs::read(dir.join(name))
+                        .map_err(|e| ReloadError::Parse(format!("{}: {}", name, e)))
+                };
+                bytes.map(|bytes| (*name, bytes))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        let mut linted = BTreeMap::new();
+        for name in LINTED_FILES {
+            let bytes = if let Some((_, bytes)) = files.iter().find(|(n, _)| *n == name) {
+                Some(bytes.clone())
+            } else if SOVEREIGN_SHARDS.contains(&name) {
+                // A sovereign shard on disk is always in the manifest.
+                verified.bytes(name).ok().map(<[u8]>::to_vec)
+            } else {
+                match fs::read(dir.join(name)) {
+                    Ok(bytes) => Some(bytes),
+                    Err(e) if e.kind() == std::io::ErrorKind::NotFound => None,
+                    Err(e) => return Err(ReloadError::Parse(format!("{}: {}", name, e))),
+                }
+            };
+            if let Some(bytes) = bytes {
+                linted.insert(name, bytes);
+            }
+        }
+        Ok(Self {
+            sequence: verified.sequence(),
+            manifest_hash: verified.manifest_hash().to_string(),
+            files,
+            linted,
+        })
+    }
+
+    fn lint_errors(&self) -> Vec<Diagnostic> {
+        policy_lint::lint_files(&self.linted)
+            .into_iter()
+            .filter(|d| d.severity == Severity::Error)
+            .collect()
+    }
+
+    fn hash(&self) -> String {
+        policy_bundle_hash_bytes(&self.files)
+    }
+
+    fn lint_hash(&self) -> String {
+        policy_bundle_hash_bytes(&self.linted.iter().collect::<Vec<_>>())
+    }
+
+    fn text(&self, name: &str) -> Result<&str, ReloadError> {
+        let (_, bytes) = self
+            .files
+            .iter()
+            .find(|(n, _)| *n == name)
+            .expect("name is one of BUNDLE_FILES");
+        std::str::from_utf8(bytes).map_err(|e| ReloadError::Parse(format!("{}: {}", name, e)))
+    }
+}
+
+impl PolicySnapshot {
+    /// Compile the bundle in `dir` (`rohmodel.aln`, `neurorights.json`,
+    /// `ecoenv.json`) once its signatures verify against `anchor`.
+    pub fn load<P: AsRef<Path>>(dir: P, anchor: &StakeAnchor) -> Result<Self, ReloadError> {
+        Self::compile(&BundleBytes::read(dir.as_ref(), anchor, None)?)
+    }
+
+    /// Built-in snapshot for a node without a verified bundle: every
+    /// neuroright on, the compiled 0.3 RoH ceiling, and only CHAT route steps
+    /// admitted.
+    pub fn fail_closed(reason: impl Into<String>) -> Self {
+        Self {
+            hash: "fail-closed".into(),
+            // Every axis weighted, so no reading is free while unverified.
+            roh: RohGuard::new(RohModel {
+                ceiling: 0.3,
+                weights: ROH_AXES
+                    .iter()
+                    .map(|axis| (axis.to_string(), 0.1))
+                    .collect(),
+            }),
+            neurorights: NeurorightsGuard::new(NeurorightsPolicy {
+                mentalprivacy: true,
+                cognitiveliberty: true,
+                forbiddecisionuse: true,
+                dreamstatesensitive: true,
+                soulnontradeable: true,
+                storagescope: "local".into(),
+            }),
+            eco: EcoGuard::new(HashMap::new()),
+            chat_only: Some(reason.into()),
+            digests: BTreeMap::new(),
+            linted: String::new(),
+            sequence: 0,
+            manifest_hash: String::new(),
+        }
+    }
+
+    /// `load`, or the fail-closed snapshot if the bundle does not verify.
+    pub fn load_or_fail_closed<P: AsRef<Path>>(dir: P, anchor: &StakeAnchor) -> Self {
+        Self::load(dir, anchor).unwrap_or_else(|e| Self::fail_closed(e.to_string()))
+    }
+
+    fn compile(bundle: &BundleBytes) -> Result<Self, ReloadError> {
+        let parse = |name: &str, e: &dyn std::fmt::Display| {
+            ReloadError::Parse(format!("{}: {:#}", name, e))
+        };
+        let roh = RohModel::parse(bundle.text(ROHMODEL)?).map_err(|e| parse(ROHMODEL, &e))?;
+        let nr: NeurorightsPolicy =
+            serde_json::from_str(bundle.text(NEURORIGHTS)?).map_err(|e| parse(NEURORIGHTS, &e))?;
+        let eco: HashMap<String, EcoEnvelope> =
+            serde_json::from_str(bundle.text(ECOENV)?).map_err(|e| parse(ECOENV, &e))?;
+        Ok(Self {
+            hash: bundle.hash(),
+            roh: RohGuard::new(roh),
+            neurorights: NeurorightsGuard::new(nr),
+            eco: EcoGuard::new(eco),
+            chat_only: None,
+            digests: bundle
+                .files
+                .iter()
+                .map(|(name, bytes)| (name.to_string(), digest_hex(bytes)))
+                .collect(),
+            linted: bundle.lint_hash(),
+            sequence: bundle.sequence,
+            manifest_hash: bundle.manifest_hash.clone(),
+        })
+    }
+
+    pub fn shared(self) -> SharedPolicy {
+        Arc::new(ArcSwap::from_pointee(self))
+    }
+}
+
+#[derive(Debug, Error)]
+pub enum ReloadError {
+    #[error("policy bundle does not verify: {0}")]
+    Bundle(#[from] BundleError),
+    #[error("policy bundle does not parse: {0}")]
+    Parse(String),
+    #[error("policy bundle fails lint: {}", .0.iter().map(|d| d.to_string()).collect::<Vec<_>>().join("; "))]
+    Lint(Vec<Diagnostic>),
+    #[error("{shard} changed to {digest} without an approved EVOLVE proposal")]
+    Unapproved { shard: String, digest: String },
+}
+
+/// Source of EVOLVE approvals for SOVEREIGNCONFIG shard changes.
+pub trait EvolveApprovals: Send + Sync {
+    /// Whether an approved proposal pins `shard` to content `digest`.
+    fn approves(&self, shard: &str, digest: &str) -> bool;
+}
+
+/// Approvals read from `.evolve.jsonl`. Nothing is approved unless the
+/// whole ledger verifies under the stake roster (`verify_ledger`) and agrees
+/// with its head anchor.
+/// A proposal then approves a shard when its latest transition is
+/// `QuorumReached` or `Applied`, it is an EVOLVE-class SOVEREIGNCONFIG
+/// change, its `affected_shards` pin the shard to exactly that content
+/// digest, and the quorum certificate it names re-tallies under the stake
+/// roster.
+pub struct EvolveLedgerApprovals {
+    path: PathBuf,
+    governance: Governance,
+}
+
+impl EvolveLedgerApprovals {
+    pub fn new<P: Into<PathBuf>>(path: P, governance: Governance) -> Self {
+        Self {
+            path: path.into(),
+            governance,
+        }
+    }
+
+    /// Latest transition of every proposal, or `None` if the ledger is
+    /// unreadable, does not verify, or was cut short of its anchor.
+    fn verified_latest(&self) -> Option<HashMap<String, EvolutionProposal>> {
+        let text = fs::read_to_string(&self.path).ok()?;
+        let report = verify_ledger(&text, &self.governance);
+        if !report.is_clean() {
+            return None;
+        }
+        if let Some(anchor) = Anchor::load(anchor_path(&self.path)).ok()? {
+            let short = report.records != anchor.seq + 1;
+            if short || report.head.as_ref() != Some(&anchor.hexstamp) {
+                return None;
+            }
+        }
+        let mut latest = HashMap::new();
+        for line in text.lines().filter(|l| !l.trim().is_empty()) {
+            let record: EvolutionProposal = serde_json::from_str(line).ok()?;
+            latest.insert(record.proposal_id.clone(), record);
+        }
+        Some(latest)
+    }
+}
+
+impl EvolveApprovals for EvolveLedgerApprovals {
+    fn approves(&self, shard: &str, digest: &str) -> bool {
+        let Some(latest) = self.verified_latest() else {
+            return false;
+        };
+        latest.values().any(|r| {
+            matches!(
+                r.decision,
+                ProposalState::QuorumReached | ProposalState::Applied
+            ) && r.token_kind == "Evolve"
+                && r.class_label == "SOVEREIGNCONFIG"
+                && r.affected_shards
+                    .iter()
+                    .any(|a| a.shard == shard && a.sha256 == digest)
+                && self.governance.verify_quorum(r).is_ok()
+        })
+    }
+}
+
+/// Approves nothing; for a node with no usable stake roster to tally
+/// EVOLVE quorums against.
+pub struct NoApprovals;
+
+impl EvolveApprovals for NoApprovals {
+    fn approves(&self, _shard: &str, _digest: &str) -> bool {
+        false
+    }
+}
+
+/// Watches a bundle directory and swaps the shared snapshot when a new,
+/// acceptable bundle appears.
+pub struct PolicyReloader {
+    dir: PathBuf,
+    anchor: StakeAnchor,
+    current: SharedPolicy,
+    approvals: Box<dyn EvolveApprovals>,
+}
+
+impl PolicyReloader {
+    pub fn new<P: Into<PathBuf>>(
+        dir: P,
+        anchor: StakeAnchor,
+        current: SharedPolicy,
+        approvals: Box<dyn EvolveApprovals>,
+    ) -> Self {
+        Self {
+            dir: dir.into(),
+            anchor,
+            current,
+            approvals,
+        }
+    }
+
+    /// One reload attempt. `Ok(None)` if the bundle is unchanged, the new
+    /// hash once swapped. A bundle that stops verifying swaps in the
+    /// fail-closed snapshot; on any other error the current one stays.
+    ///
+    /// Leaving fail-closed mode is an ordinary reload, so sovereign shards
+    /// that differ from the last verified bundle still need EVOLVE approval.
+    pub fn reload(&self) -> Result<Option<String>, ReloadError> {
+        let current = self.current.load();
+        let bundle = match BundleBytes::read(&self.dir, &self.anchor, Some(&current)) {
+            Ok(bundle) => bundle,
+            Err(ReloadError::Bundle(e)) => {
+                if current.chat_only.is_none() {
+                    let mut closed = PolicySnapshot::fail_closed(e.to_string());
+                    closed.digests = current.digests.clone();
+                    closed.sequence = current.sequence;
+                    closed.manifest_hash = current.manifest_hash.clone();
+                    self.current.store(Arc::new(closed));
+                }
+                return Err(ReloadError::Bundle(e));
+            }
+            Err(e) => return Err(e),
+        };
+        let hash = bundle.hash();
+        if hash == current.hash && bundle.lint_hash() == current.linted {
+            return Ok(None);
+        }
+        let next = PolicySnapshot::compile(&bundle)?;
+
+        let errors = bundle.lint_errors();
+        if !errors.is_empty() {
+            return Err(ReloadError::Lint(errors));
+        }
+
+        for shard in SOVEREIGN_FILES {
+            let digest = &next.digests[shard];
+            if current.digests.get(shard) != Some(digest) && !self.approvals.approves(shard, digest)
+            {
+                return Err(ReloadError::Unapproved {
+                    shard: shard.to_string(),
+                    digest: digest.clone(),
+                });
+            }
+        }
+
+        self.current.store(Arc::new(next));
+        Ok(Some(hash))
+    }
+
+    /// Poll the bundle every `interval` on a background thread.
+    pub fn spawn(self, interval: Duration) -> JoinHandle<()> {
+        std::thread::spawn(move || loop {
+            std::thread::sleep(interval);
+            match self.reload() {
+                Ok(Some(hash)) => eprintln!("policy: now enforcing bundle {}", hash),
+                Ok(None) => {}
+                Err(e) => eprintln!("policy: reload refused, keeping current bundle: {}", e),
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testutil::{governance, governance_of, proposal, write_ledger};
+    use k256::ecdsa::SigningKey;
+    use policy_bundle::{AnchorMultisig, AnchorSigner, BundleManifest, MANIFEST_FILE};
+
+    const SUBJECT: &str = "bostrom18sd2ujv24ual9c9pshtxys6j8knh6xaead9ye7";
+
+    fn key() -> SigningKey {
+        SigningKey::from_slice(&[7; 32]).unwrap()
+    }
+
+    fn anchor() -> StakeAnchor {
+        StakeAnchor {
+            subject_id: SUBJECT.into(),
+            multisig: AnchorMultisig {
+                threshold: 1,
+                signers: vec![AnchorSigner {
+                    label: "steward".into(),
+                    pubkey: hex::encode(key().verifying_key().to_encoded_point(true).as_bytes()),
+                }],
+            },
+        }
+    }
+
+    /// Re-pack and co-sign the bundle under the next sequence number, as a
+    /// stake quorum would after EVOLVE.
+    fn sign(dir: &Path) {
+        let sequence = BundleManifest::read(dir).map_or(1, |m| m.sequence + 1);
+        let mut manifest = BundleManifest::pack(dir, SUBJECT, sequence).unwrap();
+        manifest.sign(&key());
+        manifest.write(dir).unwrap();
+    }
+
+    const ROHMODEL_TEXT: &str = r#"RohModel { ceiling: 0.3, weights: {
+        lifeforce_load: 0.1, biospec_fatigue: 0.08, qpu_roh: 0.07, route_context_risk: 0.05 } }"#;
+    const NEURORIGHTS_TEXT: &str = r#"{"mentalprivacy": true, "cognitiveliberty": true,
+        "forbiddecisionuse": true, "dreamstatesensitive": true, "soulnontradeable": true,
+        "storagescope": "local"}"#;
+
+    fn bundle() -> tempfile::TempDir {
+        let dir = tempfile::tempdir().unwrap();
+        let files = [
+            (ROHMODEL, ROHMODEL_TEXT),
+            (NEURORIGHTS, NEURORIGHTS_TEXT),
+            (
+                ECOENV,
+                r#"{"CHAT": {"max_power_watts": 5.0, "max_lifeforce_delta": 0.1}}"#,
+            ),
+            (
+                "tsafe.aln",
+                r#"TsafeKernel { axes: [ { name: "lifeforce_load", min: 0.0, max: 0.8 },
+                   { name: "roh_global", min: 0.0, max: 0.3 } ] }"#,
+            ),
+            (
+                "vkernel.aln",
+                r#"ViabilityKernel { mode: "Normal", constraints: [ { expr: "roh_global <= 0.3" } ] }"#,
+            ),
+        ];
+        for (name, text) in files {
+            fs::write(dir.path().join(name), text).unwrap();
+        }
+        sign(dir.path());
+        dir
+    }
+
+    struct Approves(Option<String>);
+
+    impl EvolveApprovals for Approves {
+        fn approves(&self, shard: &str, digest: &str) -> bool {
+            self.0.as_deref() == Some(&format!("{}@sha256:{}", shard, digest))
+        }
+    }
+
+    fn reloader(dir: &Path, approved: Option<String>) -> (SharedPolicy, PolicyReloader) {
+        let shared = PolicySnapshot::load(dir, &anchor()).unwrap().shared();
+        let reloader = PolicyReloader::new(
+            dir,
+            anchor(),
+            Arc::clone(&shared),
+            Box::new(Approves(approved)),
+        );
+        (shared, reloader)
+    }
+
+    #[test]
+    fn eco_change_swaps_and_in_flight_snapshot_survives() {
+        let dir = bundle();
+        let (shared, reloader) = reloader(dir.path(), None);
+        let in_flight = shared.load_full();
+        assert!(reloader.reload().unwrap().is_none());
+
+        fs::write(
+            dir.path().join(ECOENV),
+            r#"{"CHAT": {"max_power_watts": 5.0, "max_lifeforce_delta": 0.05}}"#,
+        )
+        .unwrap();
+        let hash = reloader.reload().unwrap().unwrap();
+        assert_eq!(shared.load().hash, hash);
+        assert_ne!(in_flight.hash, hash);
+    }
+
+    #[test]
+    fn sovereign_change_needs_pinned_evolve_and_clean_lint() {
+        let dir = bundle();
+        let changed = NEURORIGHTS_TEXT.replace("\"local\"", "\"local-only\"");
+        let pin = format!("{}@sha256:{}", NEURORIGHTS, digest_hex(changed.as_bytes()));
+
+        let (shared, unapproved) = reloader(dir.path(), None);
+        let before = shared.load().hash.clone();
+        fs::write(dir.path().join(NEURORIGHTS), &changed).unwrap();
+        sign(dir.path());
+        assert!(matches!(
+            unapproved.reload(),
+            Err(ReloadError::Unapproved { .. })
+        ));
+        assert_eq!(shared.load().hash, before);
+
+        fs::write(dir.path().join(NEURORIGHTS), NEURORIGHTS_TEXT).unwrap();
+        sign(dir.path());
+        let (shared, approved) = reloader(dir.path(), Some(pin));
+        fs::write(dir.path().join(NEURORIGHTS), &changed).unwrap();
+        fs::remove_file(dir.path().join("vkernel.aln")).unwrap();
+        sign(dir.path());
+        assert!(matches!(approved.reload(), Err(ReloadError::Lint(_))));
+
+        fs::write(
+            dir.path().join("vkernel.aln"),
+            r#"ViabilityKernel { mode: "Normal", constraints: [ { expr: "roh_global <= 0.3" } ] }"#,
+        )
+        .unwrap();
+        sign(dir.path());
+        assert!(approved.reload().unwrap().is_some());
+        assert_ne!(shared.load().hash, before);
+    }
+
+    #[test]
+    fn unverified_bundle_fails_closed_until_resigned() {
+        let dir = bundle();
+        let (shared, reloader) = reloader(dir.path(), None);
+        let before = shared.load().hash.clone();
+
+        // A sovereign edit that bypassed the stake quorum.
+        fs::write(
+            dir.path().join(ROHMODEL),
+            "RohModel { ceiling: 0.3, weights: { lifeforce_load: 0.01 } }",
+        )
+        .unwrap();
+        assert!(matches!(reloader.reload(), Err(ReloadError::Bundle(_))));
+        assert!(shared.load().chat_only.is_some());
+        assert!(PolicySnapshot::load_or_fail_closed(dir.path(), &anchor())
+            .chat_only
+            .is_some());
+
+        // Restoring the signed bytes recovers without any new approval.
+        fs::write(dir.path().join(ROHMODEL), ROHMODEL_TEXT).unwrap();
+        assert_eq!(reloader.reload().unwrap(), Some(before));
+        assert!(shared.load().chat_only.is_none());
+    }
+
+    #[test]
+    fn older_signed_bundle_is_refused_as_rollback() {
+        let dir = bundle();
+        let (shared, reloader) = reloader(dir.path(), None);
+        let old_manifest = fs::read(dir.path().join(MANIFEST_FILE)).unwrap();
+        let old_eco = fs::read(dir.path().join(ECOENV)).unwrap();
+
+        fs::write(
+            dir.path().join(ECOENV),
+            r#"{"CHAT": {"max_power_watts": 5.0, "max_lifeforce_delta": 0.05}}"#,
+        )
+        .unwrap();
+        sign(dir.path());
+        let newer = reloader.reload().unwrap().unwrap();
+
+        // Replaying the earlier, validly signed manifest is a rollback.
+        fs::write(dir.path().join(MANIFEST_FILE), &old_manifest).unwrap();
+        fs::write(dir.path().join(ECOENV), &old_eco).unwrap();
+        assert!(matches!(
+            reloader.reload(),
+            Err(ReloadError::Bundle(BundleError::Rollback {
+                current: 2,
+                found: 1
+            }))
+        ));
+        assert!(shared.load().chat_only.is_some());
+        assert!(reloader.reload().is_err());
+
+        fs::write(
+            dir.path().join(ECOENV),
+            r#"{"CHAT": {"max_power_watts": 5.0, "max_lifeforce_delta": 0.05}}"#,
+        )
+        .unwrap();
+        sign(dir.path());
+        assert_eq!(reloader.reload().unwrap(), Some(newer));
+    }
+
+    #[test]
+    fn linted_only_shard_change_is_relinted() {
+        let dir = bundle();
+        let (shared, reloader) = reloader(dir.path(), None);
+        let before = shared.load().hash.clone();
+
+        // tsafe.aln is not compiled into the snapshot, but the lint reads it.
+        fs::write(
+            dir.path().join("tsafe.aln"),
+            r#"TsafeKernel { axes: [ { name: "roh_global", min: 0.0, max: 0.5 } ] }"#,
+        )
+        .unwrap();
+        sign(dir.path());
+        assert!(matches!(reloader.reload(), Err(ReloadError::Lint(_))));
+        assert_eq!(shared.load().hash, before);
+    }
+
+    #[test]
+    fn ledger_approval_requires_quorum_on_a_verified_ledger() {
+        const T: i64 = 1_767_225_600;
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join(".evolve.jsonl");
+        let draft = proposal("p-1", None, ProposalState::Draft, ROHMODEL, "bb", T);
+        let review = proposal(
+            "p-1",
+            Some(&draft),
+            ProposalState::UnderReview,
+            ROHMODEL,
+            "bb",
+            T,
+        );
+        let quorum = proposal(
+            "p-1",
+            Some(&review),
+            ProposalState::QuorumReached,
+            ROHMODEL,
+            "bb",
+            T,
+        );
+        let other = proposal(
+            "p-2",
+            Some(&quorum),
+            ProposalState::Draft,
+            ROHMODEL,
+            "aa",
+            T,
+        );
+        let approvals = EvolveLedgerApprovals::new(&path, governance(&path));
+
+        // Drafts and proposals still under review approve nothing.
+        write_ledger(&path, &[draft.clone(), review.clone()]);
+        assert!(!approvals.approves(ROHMODEL, "bb"));
+
+        write_ledger(
+            &path,
+            &[draft.clone(), review.clone(), quorum.clone(), other],
+        );
+        assert!(approvals.approves(ROHMODEL, "bb"));
+        assert!(!approvals.approves(ROHMODEL, "aa"));
+        assert!(!approvals.approves(ROHMODEL, "cc"));
+
+        // The certificate is re-tallied: not under another roster, and not
+        // once it is gone.
+        let stranger = SigningKey::from_slice(&[8; 32]).unwrap();
+        let elsewhere = EvolveLedgerApprovals::new(&path, governance_of(&stranger, &path));
+        assert!(!elsewhere.approves(ROHMODEL, "bb"));
+        let certificates = sovereign_evolve::certificate_dir(&path);
+        fs::rename(&certificates, dir.path().join("moved")).unwrap();
+        assert!(!approvals.approves(ROHMODEL, "bb"));
+        fs::rename(dir.path().join("moved"), &certificates).unwrap();
+        assert!(approvals.approves(ROHMODEL, "bb"));
+
+        // An unsigned or edited line poisons the whole ledger.
+        let mut forged = quorum.clone();
+        forged.affected_shards[0].sha256 = "dd".into();
+        write_ledger(&path, &[draft.clone(), review.clone(), forged]);
+        assert!(!approvals.approves(ROHMODEL, "dd"));
+        assert!(!approvals.approves(ROHMODEL, "bb"));
+
+        // So does a ledger cut short of its head anchor.
+        write_ledger(&path, &[draft, review, quorum.clone()]);
+        Anchor {
+            seq: 3,
+            hexstamp: "ee".repeat(32),
+        }
+        .store(anchor_path(&path))
+        .unwrap();
+        assert!(!approvals.approves(ROHMODEL, "bb"));
+    }
+}
@@ -0,0 +1,83 @@
+use crate::guardians::GuardError;
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+
+/// The part of the biospec `.lifeforce.aln` record the RoH model reads.
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RuntimeMetrics {
+    fatigue_index: f32,
+    /// QPU risk reading; absent on nodes without a QPU.
+    #[serde(default)]
+    qpu_roh: f32,
+}
+
+// ---- NodeTelemetry ----
+
+/// Node-side readings for the RoH axes a request must not report itself.
+/// `.lifeforce.aln` is re-read on every check, so the estimate follows the
+/// node's runtime metrics as they are written.
+pub struct NodeTelemetry {
+    lifeforce: PathBuf,
+}
+
+impl NodeTelemetry {
+    pub fn new<P: Into<PathBuf>>(lifeforce: P) -> Self {
+        Self {
+            lifeforce: lifeforce.into(),
+        }
+    }
+
+    /// `biospec_fatigue` and `qpu_roh` as the node measures them. Missing or
+    /// unreadable metrics are an error; the RoH guard does not estimate
+    /// without them.
+    pub fn axes(&self) -> Result<BTreeMap<String, f32>, GuardError> {
+        let metrics: RuntimeMetrics = std::fs::read_to_string(&self.lifeforce)
+            .map_err(anyhow::Error::from)
+            .and_then(|text| Ok(aln_format::from_str(&text)?))
+            .map_err(|e| GuardError {
+                code: "ROH_TELEMETRY_UNAVAILABLE".into(),
+                message: format!("Cannot read {}: {}", self.lifeforce.display(), e),
+            })?;
+        Ok(BTreeMap::from([
+            ("biospec_fatigue".to_string(), metrics.fatigue_index),
+            ("qpu_roh".to_string(), metrics.qpu_roh),
+        ]))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reads_fatigue_and_qpu_from_the_lifeforce_envelope() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("host.lifeforce.aln");
+        std::fs::write(
+            &path,
+            "RuntimeMetrics { currentDutyCycle: 12.5, fatigueIndex: 0.2, ecoImpact: 0.1 }",
+        )
+        .unwrap();
+        let axes = NodeTelemetry::new(&path).axes().unwrap();
+        assert_eq!(axes["biospec_fatigue"], 0.2);
+        assert_eq!(axes["qpu_roh"], 0.0);
+
+        std::fs::write(
+            &path,
+            "RuntimeMetrics { currentDutyCycle: 12.5, fatigueIndex: 0.2, ecoImpact: 0.1, qpuRoh: 0.15 }",
+        )
+        .unwrap();
+        assert_eq!(NodeTelemetry::new(&path).axes().unwrap()["qpu_roh"], 0.15);
+    }
+
+    #[test]
+    fn missing_envelope_is_unavailable() {
+        let dir = tempfile::tempdir().unwrap();
+        let err = NodeTelemetry::new(dir.path().join("host.lifeforce.aln"))
+            .axes()
+            .unwrap_err();
+        assert_eq!(err.code, "ROH_TELEMETRY_UNAVAILABLE");
+    }
+}
@@ -0,0 +1,161 @@
+//! Signed `.evolve.jsonl` records for tests that replay the EVOLVE ledger.
+
+use std::fs;
+use std::path::Path;
+
+use k256::ecdsa::SigningKey;
+use sovereign_core::capabilities::{RightsProfile, SovereignClassification};
+use sovereign_core::{
+    AffectedShard, EvolutionKind, EvolutionProposal, HexStamp, NeurorightsPosture, ProposalState,
+    Roh, EVOLVE_SCHEMA_VERSION,
+};
+use sovereign_evolve::{
+    actor_id, proposal_hexstamp, sign_transition, Governance, QuorumCertificate, QuorumEngine,
+    StakeRoster, Vote,
+};
+
+pub const SUBJECT: &str = "bostrom18sd2ujv24ual9c9pshtxys6j8knh6xaead9ye7";
+
+pub fn key() -> SigningKey {
+    SigningKey::from_slice(&[7; 32]).unwrap()
+}
+
+/// `key` as the only stakeholder, with certificates next to `ledger`.
+pub fn governance_of(key: &SigningKey, ledger: &Path) -> Governance {
+    let roster = StakeRoster::parse(&format!(
+        r#"StakeRoster {{
+  subject_id: "{}",
+  multisig: {{
+    threshold: 1,
+    signers: [{{ label: "host", pubkey: "{}", stake_points: 1 }}]
+  }}
+}}"#,
+        SUBJECT,
+        actor_id(key)
+    ))
+    .unwrap();
+    Governance::for_ledger(QuorumEngine::new(roster), ledger)
+}
+
+/// Governance under which [`proposal`] reaches quorum.
+pub fn governance(ledger: &Path) -> Governance {
+    governance_of(&key(), ledger)
+}
+
+/// [`key`]'s approval of `r`, the certificate [`proposal`] names.
+pub fn certificate(r: &EvolutionProposal) -> QuorumCertificate {
+    governance(Path::new(""))
+        .engine()
+        .tally(r, &[Vote::cast(r, true, &key())])
+        .unwrap()
+}
+
+pub fn posture() -> NeurorightsPosture {
+    NeurorightsPosture {
+        mental_privacy: true,
+        dream_state_sensitive: true,
+        forbid_decision_use: true,
+        soul_non_tradeable: true,
+    }
+}
+
+pub fn rights() -> RightsProfile {
+    RightsProfile {
+        mental_privacy: true,
+        dream_state_sensitive: true,
+        forbid_decision_use: true,
+        soul_non_tradeable: true,
+        extra_flags: Vec::new(),
+    }
+}
+
+/// One signed transition of an EVOLVE-class SOVEREIGNCONFIG proposal that
+/// pins `shard` to content `sha256`, chained after `prev`. Past review it carries consent, a
+/// Bostrom tx and a quorum certificate; `Applied` also a snapshot.
+pub fn proposal(
+    id: &str,
+    prev: Option<&EvolutionProposal>,
+    decision: ProposalState,
+    shard: &str,
+    sha256: &str,
+    timestamp_unix: i64,
+) -> EvolutionProposal {
+    let past_review = !matches!(decision, ProposalState::Draft | ProposalState::UnderReview);
+    let (bostrom_tx, consent_proofs) = if past_review {
+        ("0xTX".to_string(), vec!["sig".to_string()])
+    } else {
+        (String::new(), Vec::new())
+    };
+    let mut r = EvolutionProposal {
+        schema_version: EVOLVE_SCHEMA_VERSION,
+        proposal_id: id.into(),
+        prev_hexstamp: prev.map(|p| p.hexstamp.clone()),
+        subject_id: SUBJECT.into(),
+        description: "tighten RoH weights".into(),
+        kind: EvolutionKind::KernelChange,
+        effect_bounds: (Roh(0.0), Roh(0.06)),
+        roh_before: Roh(0.2),
+        roh_after: Roh(0.1),
+        decision,
+        timestamp_unix,
+        token_kind: "Evolve".into(),
+        class_label: "SOVEREIGNCONFIG".into(),
+        bostrom_tx,
+        consent_proofs,
+        affected_shards: vec![AffectedShard {
+            shard: shard.into(),
+            sha256: sha256.into(),
+        }],
+        neurorights_posture: Some(posture()),
+        sovereign_classification: Some(SovereignClassification::evolve_structural(
+            "SOVEREIGNCONFIG",
+            rights(),
+        )),
+        actor: actor_id(&key()),
+        actor_signature: String::new(),
+        snapshot: (decision == ProposalState::Applied).then(|| HexStamp("dd".repeat(32))),
+        quorum_certificate: None,
+        hexstamp: HexStamp(String::new()),
+    };
+    if past_review {
+        r.quorum_certificate = Some(certificate(&r).digest());
+    }
+    r.hexstamp = proposal_hexstamp(&r);
+    r.actor_signature = sign_transition(&r.hexstamp, &key());
+    r
+}
+
+/// Every transition from `Draft` up to `last`, chained after `prev`.
+pub fn lifecycle(
+    id: &str,
+    prev: Option<&EvolutionProposal>,
+    last: ProposalState,
+    shard: &str,
+    sha256: &str,
+    timestamp_unix: i64,
+) -> Vec<EvolutionProposal> {
+    use ProposalState::*;
+    let mut records: Vec<EvolutionProposal> = Vec::new();
+    for state in [Draft, UnderReview, QuorumReached, Applied] {
+        let prev = records.last().or(prev);
+        records.push(proposal(id, prev, state, shard, sha256, timestamp_unix));
+        if state == last {
+            break;
+        }
+    }
+    records
+}
+
+/// Write `records` as the ledger at `path`, with the certificates they name.
+pub fn write_ledger(path: &Path, records: &[EvolutionProposal]) {
+    let text: String = records
+        .iter()
+        .map(|r| serde_json::to_string(r).unwrap() + "\n")
+        .collect();
+    fs::write(path, text).unwrap();
+    for r in records.iter().filter(|r| r.quorum_certificate.is_some()) {
+        governance(path)
+            .record_certificate(r, &certificate(r))
+            .unwrap();
+    }
+}
@@ -2,9 +2,12 @@ use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::path::Path;
 
+use anyhow::Context;
+use subject_id::SubjectId;
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct DidConfig {
-    pub subject_did: String,          // host DID
+    pub subject_did: SubjectId,       // host DID, validated on load
     pub citizen_class: String,        // e.g. "CITIZEN", "LAB_ONLY"
     pub jurisdiction_capsule: String, // e.g. "phoenix-az-us"
 }
@@ -41,8 +44,10 @@ pub struct GovernanceStatic {
 impl GovernanceStatic {
     pub fn load_from_dir<P: AsRef<Path>>(dir: P) -> anyhow::Result<Self> {
         let dir = dir.as_ref();
+        // A malformed subject_did fails here, naming the file and the id.
         let did: DidConfig =
-            serde_json::from_str(&std::fs::read_to_string(dir.join("did.json"))?)?;
+            serde_json::from_str(&std::fs::read_to_string(dir.join("did.json"))?)
+                .context("did.json")?;
         let consent: ConsentConfig =
             serde_json::from_str(&std::fs::read_to_string(dir.join("consent.json"))?)?;
         let citizen_stake: CitizenStake =
@@ -34,10 +34,9 @@ pub fn load_neurorights<P: AsRef<Path>>(path: P) -> anyhow::Result<NeurorightsPo
 }
 
 pub fn load_rohmodel<P: AsRef<Path>>(path: P) -> anyhow::Result<RohModel> {
-    Ok(serde_json::from_str(&fs::read_to_string(path)?)?)
+    aln_format::from_path(path)
 }
 
 pub fn load_tsafe_kernel<P: AsRef<Path>>(path: P) -> anyhow::Result<TsafeKernel> {
-    // For now assume JSON-compatible representation of .tsafe.aln.
-    Ok(serde_json::from_str(&fs::read_to_string(path)?)?)
+    aln_format::from_path(path)
 }
@@ -0,0 +1,224 @@
+//! Linear constraint expressions as written in `.vkernel.aln`:
+//!
+//! ```text
+//! constraint := linear ("<=" | ">=") linear
+//! linear     := ["-"] term (("+" | "-") term)*
+//! term       := NUMBER | AXIS | NUMBER "*" AXIS | AXIS "*" NUMBER
+//! ```
+//!
+//! Both sides may mention axes and constants; everything is moved into the
+//! normalized form `Σ aᵢ·xᵢ <= b`.
+
+use std::collections::BTreeMap;
+
+use crate::CompileError;
+
+#[derive(Debug, Clone, PartialEq)]
+enum Tok {
+    Num(f64),
+    Axis(String),
+    Plus,
+    Minus,
+    Star,
+    Le,
+    Ge,
+}
+
+/// One constraint in normalized `Σ aᵢ·xᵢ <= b` form, keyed by axis name.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LinearConstraint {
+    pub coeffs: BTreeMap<String, f64>,
+    pub bound: f64,
+}
+
+pub fn parse_constraint(src: &str) -> Result<LinearConstraint, CompileError> {
+    let toks = tokenize(src)?;
+    let split = toks
+        .iter()
+        .position(|(t, _)| matches!(t, Tok::Le | Tok::Ge))
+        .ok_or_else(|| CompileError::new(src, 1, "missing `<=` or `>=`"))?;
+    if toks[split + 1..]
+        .iter()
+        .any(|(t, _)| matches!(t, Tok::Le | Tok::Ge))
+    {
+        return Err(CompileError::new(
+            src,
+            toks[split].1,
+            "chained comparisons are not supported",
+        ));
+    }
+
+    let (lhs_c, lhs_k) = linear(src, &toks[..split], toks[split].1)?;
+    let end_col = src.chars().count() + 1;
+    let (rhs_c, rhs_k) = linear(src, &toks[split + 1..], end_col)?;
+
+    // lhs <= rhs  ==>  lhs - rhs <= 0 ; lhs >= rhs ==> rhs - lhs <= 0
+    let sign = if toks[split].0 == Tok::Le { 1.0 } else { -1.0 };
+    let mut coeffs = BTreeMap::new();
+    for (axis, a) in lhs_c {
+        *coeffs.entry(axis).or_insert(0.0) += sign * a;
+    }
+    for (axis, a) in rhs_c {
+        *coeffs.entry(axis).or_insert(0.0) -= sign * a;
+    }
+    coeffs.retain(|_, a: &mut f64| *a != 0.0);
+    if coeffs.is_empty() {
+        return Err(CompileError::new(
+            src,
+            1,
+            "constraint does not mention any axis",
+        ));
+    }
+    Ok(LinearConstraint {
+        coeffs,
+        bound: sign * (rhs_k - lhs_k),
+    })
+}
+
+fn tokenize(src: &str) -> Result<Vec<(Tok, usize)>, CompileError> {
+    let chars: Vec<char> = src.chars().collect();
+    let mut out = Vec::new();
+    let mut i = 0;
+    while i < chars.len() {
+        let c = chars[i];
+        let col = i + 1;
+        match c {
+            ' ' | '\t' => i += 1,
+            '+' => {
+                out.push((Tok::Plus, col));
+                i += 1;
+            }
+            '-' => {
+                out.push((Tok::Minus, col));
+                i += 1;
+            }
+            '*' => {
+                out.push((Tok::Star, col));
+                i += 1;
+            }
+            '<' | '>' => {
+                if chars.get(i + 1) != Some(&'=') {
+                    return Err(CompileError::new(
+                        src,
+                        col,
+                        "strict comparisons are not supported; use `<=` or `>=`",
+                    ));
+                }
+                out.push((if c == '<' { Tok::Le } else { Tok::Ge }, col));
+                i += 2;
+            }
+            c if c.is_ascii_digit() || c == '.' => {
+                let start = i;
+                while i < chars.len()
+                    && (chars[i].is_ascii_digit()
+                        || chars[i] == '.'
+                        || matches!(chars[i], 'e' | 'E')
+                        || (matches!(chars[i], '+' | '-') && matches!(chars[i - 1], 'e' | 'E')))
+                {
+                    i += 1;
+                }
+                let text: String = chars[start..i].iter().collect();
+                let n = text.parse::<f64>().map_err(|_| {
+                    CompileError::new(src, col, format!("invalid number `{}`", text))
+                })?;
+                out.push((Tok::Num(n), col));
+            }
+            c if c.is_ascii_alphabetic() || c == '_' => {
+                let start = i;
+                while i < chars.len() && (chars[i].is_ascii_alphanumeric() || chars[i] == '_') {
+                    i += 1;
+                }
+                out.push((Tok::Axis(chars[start..i].iter().collect()), col));
+            }
+            other => {
+                return Err(CompileError::new(
+                    src,
+                    col,
+                    format!("unexpected character `{}`", other),
+                ));
+            }
+        }
+    }
+    Ok(out)
+}
+
+/// Parse one side into (axis coefficients, constant).
+fn linear(
+    src: &str,
+    toks: &[(Tok, usize)],
+    end_col: usize,
+) -> Result<(BTreeMap<String, f64>, f64), CompileError> {
+    let mut coeffs = BTreeMap::new();
+    let mut constant = 0.0;
+    let mut i = 0;
+    let col_at = |i: usize| toks.get(i).map(|t| t.1).unwrap_or(end_col);
+
+    if toks.is_empty() {
+        return Err(CompileError::new(src, end_col, "empty side of comparison"));
+    }
+
+    let mut sign = 1.0;
+    if toks[0].0 == Tok::Minus {
+        sign = -1.0;
+        i += 1;
+    }
+    loop {
+        // term
+        let (coef, axis) = match (toks.get(i).map(|t| &t.0), toks.get(i + 1).map(|t| &t.0)) {
+            (Some(Tok::Num(n)), Some(Tok::Star)) => match toks.get(i + 2).map(|t| &t.0) {
+                Some(Tok::Axis(a)) => {
+                    i += 3;
+                    (*n, Some(a.clone()))
+                }
+                _ => {
+                    return Err(CompileError::new(
+                        src,
+                        col_at(i + 2),
+                        "expected axis after `*`",
+                    ))
+                }
+            },
+            (Some(Tok::Axis(a)), Some(Tok::Star)) => match toks.get(i + 2).map(|t| &t.0) {
+                Some(Tok::Num(n)) => {
+                    i += 3;
+                    (*n, Some(a.clone()))
+                }
+                _ => {
+                    return Err(CompileError::new(
+                        src,
+                        col_at(i + 2),
+                        "expected number after `*`",
+                    ))
+                }
+            },
+            (Some(Tok::Num(n)), _) => {
+                i += 1;
+                (*n, None)
+            }
+            (Some(Tok::Axis(a)), _) => {
+                i += 1;
+                (1.0, Some(a.clone()))
+            }
+            _ => {
+                return Err(CompileError::new(
+                    src,
+                    col_at(i),
+                    "expected a number or axis",
+                ))
+            }
+        };
+        match axis {
+            Some(a) => *coeffs.entry(a).or_insert(0.0) += sign * coef,
+            None => constant += sign * coef,
+        }
+
+        match toks.get(i).map(|t| &t.0) {
+            None => break,
+            Some(Tok::Plus) => sign = 1.0,
+            Some(Tok::Minus) => sign = -1.0,
+            Some(_) => return Err(CompileError::new(src, col_at(i), "expected `+` or `-`")),
+        }
+        i += 1;
+    }
+    Ok((coeffs, constant))
+}
@@ -0,0 +1,417 @@
+//! Viability-kernel compiler: turns `.vkernel.aln` constraint strings such as
+//! `"lifeforce_load + 0.5 * qpu_coherence <= 1.0"` into a typed `Ax <= b`
+//! polytope over named axes, and evaluates axis snapshots against it.
+//!
+//! Pure logic, no I/O: loaders hand in the expression strings.
+
+mod expr;
+
+use std::collections::BTreeMap;
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+pub use expr::{parse_constraint, LinearConstraint};
+
+/// Slack allowed when comparing `a·x` against `b`, so that shard values like
+/// `0.1 + 0.2 <= 0.30` are not rejected on float rounding alone.
+pub const TOLERANCE: f64 = 1e-6;
+
+/// Named-axis telemetry snapshot, e.g. `{"roh_global": 0.12, "qpu_roh": 0.05}`.
+pub type AxisSnapshot = BTreeMap<String, f64>;
+
+#[derive(Debug, Clone, PartialEq, thiserror::Error)]
+#[error("invalid constraint `{expr}` at column {column}: {message}")]
+pub struct CompileError {
+    pub expr: String,
+    pub column: usize,
+    pub message: String,
+}
+
+impl CompileError {
+    pub(crate) fn new(expr: &str, column: usize, message: impl Into<String>) -> Self {
+        Self {
+            expr: expr.to_string(),
+            column,
+            message: message.into(),
+        }
+    }
+}
+
+/// One row of `Ax <= b`, dense over [`Polytope::axes`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct Halfspace {
+    pub coeffs: Vec<f64>,
+    pub bound: f64,
+    /// Original expression, kept for audit and diagnostics.
+    pub source: String,
+}
+
+/// A constraint the snapshot does not satisfy, with the amount it overshoots.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Violation {
+    pub row: usize,
+    pub constraint: String,
+    pub lhs: f64,
+    pub bound: f64,
+    /// `lhs - bound`, always positive.
+    pub excess: f64,
+}
+
+impl fmt::Display for Violation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "`{}` violated: {:.4} > {:.4} (excess {:.4})",
+            self.constraint, self.lhs, self.bound, self.excess
+        )
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, thiserror::Error)]
+pub enum ViabilityError {
+    #[error("no viability constraints loaded")]
+    EmptyRegion,
+    #[error("snapshot is missing axis `{0}`")]
+    MissingAxis(String),
+    #[error("{} viability constraint(s) violated; first: {}", .0.len(), .0[0])]
+    Violated(Vec<Violation>),
+}
+
+/// Compiled viability region `{ x | Ax <= b }`.
+///
+/// Serializes as its list of constraint expressions and recompiles on load,
+/// so the shard text stays the single source of truth.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(try_from = "Vec<String>", into = "Vec<String>")]
+pub struct Polytope {
+    axes: Vec<String>,
+    rows: Vec<Halfspace>,
+}
+
+impl Polytope {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Compile a list of constraint expressions.
+    pub fn compile<I, S>(exprs: I) -> Result<Self, CompileError>
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        let mut p = Self::new();
+        for e in exprs {
+            p.push_constraint(e.as_ref())?;
+        }
+        Ok(p)
+    }
+
+    /// Compile one expression and append it as a new row.
+    pub fn push_constraint(&mut self, expr: &str) -> Result<(), CompileError> {
+        let lc = parse_constraint(expr)?;
+        let mut coeffs = vec![0.0; self.axes.len()];
+        for (axis, a) in lc.coeffs {
+            let idx = self.axis_index_or_insert(&axis, &mut coeffs);
+            coeffs[idx] = a;
+        }
+        self.rows.push(Halfspace {
+            coeffs,
+            bound: lc.bound,
+            source: expr.trim().to_string(),
+        });
+        Ok(())
+    }
+
+    /// Append `min <= axis <= max` box bounds (e.g. from `.tsafe.aln` axes).
+    /// The axis name is used verbatim, never parsed as an expression.
+    pub fn push_axis_bounds(&mut self, axis: &str, min: f64, max: f64) {
+        for (sign, bound, source) in [
+            (1.0, max, format!("{} <= {}", axis, max)),
+            (-1.0, -min, format!("{} >= {}", axis, min)),
+        ] {
+            let mut coeffs = vec![0.0; self.axes.len()];
+            let idx = self.axis_index_or_insert(axis, &mut coeffs);
+            coeffs[idx] = sign;
+            self.rows.push(Halfspace {
+                coeffs,
+                bound,
+                source,
+            });
+        }
+    }
+
+    fn axis_index_or_insert(&mut self, axis: &str, pending: &mut Vec<f64>) -> usize {
+        if let Some(i) = self.axes.iter().position(|a| a == axis) {
+            return i;
+        }
+        self.axes.push(axis.to_string());
+        for row in &mut self.rows {
+            row.coeffs.push(0.0);
+        }
+        pending.push(0.0);
+        self.axes.len() - 1
+    }
+
+    pub fn axes(&self) -> &[String] {
+        &self.axes
+    }
+
+    pub fn rows(&self) -> &[Halfspace] {
+        &self.rows
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.rows.is_empty()
+    }
+
+    /// Snapshot with every axis of the polytope at zero.
+    pub fn zero_snapshot(&self) -> AxisSnapshot {
+        self.axes.iter().map(|a| (a.clone(), 0.0)).collect()
+    }
+
+    fn point(&self, snapshot: &AxisSnapshot) -> Result<Vec<f64>, ViabilityError> {
+        self.axes
+            .iter()
+            .map(|a| {
+                snapshot
+                    .get(a)
+                    .copied()
+                    .ok_or_else(|| ViabilityError::MissingAxis(a.clone()))
+            })
+            .collect()
+    }
+
+    /// Every violated row, in declaration order. Empty means the snapshot is viable.
+    pub fn violations(&self, snapshot: &AxisSnapshot) -> Result<Vec<Violation>, ViabilityError> {
+        let x = self.point(snapshot)?;
+        Ok(self
+            .rows
+            .iter()
+            .enumerate()
+            .filter_map(|(i, row)| {
+                let lhs: f64 = row.coeffs.iter().zip(&x).map(|(a, v)| a * v).sum();
+                // Written so a NaN on either side counts as a violation.
+                let inside = lhs <= row.bound + TOLERANCE;
+                (!inside).then(|| Violation {
+                    row: i,
+                    constraint: row.source.clone(),
+                    lhs,
+                    bound: row.bound,
+                    excess: lhs - row.bound,
+                })
+            })
+            .collect())
+    }
+
+    /// `Ok(())` iff the snapshot lies inside the polytope. An empty polytope
+    /// fails closed rather than admitting every point.
+    pub fn check(&self, snapshot: &AxisSnapshot) -> Result<(), ViabilityError> {
+        if self.is_empty() {
+            return Err(ViabilityError::EmptyRegion);
+        }
+        let v = self.violations(snapshot)?;
+        if v.is_empty() {
+            Ok(())
+        } else {
+            Err(ViabilityError::Violated(v))
+        }
+    }
+
+    /// Largest `t` in `[0, 1]` such that `base + t * delta` lies inside the
+    /// polytope, i.e. how far a workload can go along its requested deltas.
+    /// Axes missing from `delta` do not move. `None` if no `t` works, which
+    /// includes a `base` that is already outside and not brought back in.
+    pub fn max_scale(
+        &self,
+        base: &AxisSnapshot,
+        delta: &AxisSnapshot,
+    ) -> Result<Option<f64>, ViabilityError> {
+        if self.is_empty() {
+            return Err(ViabilityError::EmptyRegion);
+        }
+        let x = self.point(base)?;
+        let d: Vec<f64> = self
+            .axes
+            .iter()
+            .map(|a| delta.get(a).copied().unwrap_or(0.0))
+            .collect();
+        let (mut lo, mut hi) = (0.0f64, 1.0f64);
+        for row in &self.rows {
+            let at_base: f64 = row.coeffs.iter().zip(&x).map(|(a, v)| a * v).sum();
+            let slope: f64 = row.coeffs.iter().zip(&d).map(|(a, v)| a * v).sum();
+            let slack = row.bound + TOLERANCE - at_base;
+            // A NaN or infinite reading, or a NaN bound, admits no scale:
+            // `f64::min` would otherwise skip the row.
+            if !at_base.is_finite() || !slope.is_finite() || slack.is_nan() {
+                return Ok(None);
+            }
+            if slope.abs() <= f64::EPSILON {
+                if slack < 0.0 {
+                    return Ok(None);
+                }
+            } else if slope > 0.0 {
+                hi = hi.min(slack / slope);
+            } else {
+                lo = lo.max(slack / slope);
+            }
+        }
+        Ok((lo <= hi).then_some(hi))
+    }
+}
+
+impl TryFrom<Vec<String>> for Polytope {
+    type Error = CompileError;
+
+    fn try_from(exprs: Vec<String>) -> Result<Self, Self::Error> {
+        Self::compile(exprs)
+    }
+}
+
+impl From<Polytope> for Vec<String> {
+    fn from(p: Polytope) -> Self {
+        p.rows.into_iter().map(|r| r.source).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn shipped() -> Polytope {
+        Polytope::compile([
+            "roh_global <= 0.30",
+            "qpu_roh <= 0.20",
+            "qpu_roh + roh_global <= 0.30",
+            "lifeforce_load + 0.5 * qpu_coherence <= 1.0",
+            "qpu_eco_impact <= 0.50",
+        ])
+        .unwrap()
+    }
+
+    fn snap(pairs: &[(&str, f64)]) -> AxisSnapshot {
+        pairs.iter().map(|(k, v)| (k.to_string(), *v)).collect()
+    }
+
+    #[test]
+    fn compiles_shipped_vkernel_constraints() {
+        let p = shipped();
+        assert_eq!(p.rows().len(), 5);
+        assert_eq!(p.axes().len(), 5);
+        let row = &p.rows()[3];
+        let coh = p.axes().iter().position(|a| a == "qpu_coherence").unwrap();
+        assert_eq!(row.coeffs[coh], 0.5);
+        assert_eq!(row.bound, 1.0);
+    }
+
+    #[test]
+    fn reports_failed_constraint_and_excess() {
+        let p = shipped();
+        let s = snap(&[
+            ("roh_global", 0.2),
+            ("qpu_roh", 0.15),
+            ("lifeforce_load", 0.1),
+            ("qpu_coherence", 0.2),
+            ("qpu_eco_impact", 0.1),
+        ]);
+        match p.check(&s) {
+            Err(ViabilityError::Violated(v)) => {
+                assert_eq!(v.len(), 1);
+                assert_eq!(v[0].constraint, "qpu_roh + roh_global <= 0.30");
+                assert!((v[0].excess - 0.05).abs() < 1e-9);
+            }
+            other => panic!("expected violation, got {:?}", other),
+        }
+
+        let ok = snap(&[
+            ("roh_global", 0.1),
+            ("qpu_roh", 0.2),
+            ("lifeforce_load", 0.7),
+            ("qpu_coherence", 0.6),
+            ("qpu_eco_impact", 0.5),
+        ]);
+        assert_eq!(p.check(&ok), Ok(()));
+    }
+
+    #[test]
+    fn normalizes_both_sides_and_ge() {
+        let p = Polytope::compile(["2 * a - 1 >= b - 0.5"]).unwrap();
+        // b - 2a <= -0.5
+        assert_eq!(p.axes(), &["a".to_string(), "b".to_string()]);
+        assert_eq!(p.rows()[0].coeffs, vec![-2.0, 1.0]);
+        assert_eq!(p.rows()[0].bound, -0.5);
+    }
+
+    #[test]
+    fn max_scale_stops_at_the_first_binding_row() {
+        let p = shipped();
+        let base = snap(&[
+            ("roh_global", 0.1),
+            ("qpu_roh", 0.1),
+            ("lifeforce_load", 0.5),
+            ("qpu_coherence", 0.2),
+            ("qpu_eco_impact", 0.1),
+        ]);
+        // qpu_roh + roh_global has 0.1 of slack; the delta moves it by 0.4.
+        let delta = snap(&[("qpu_roh", 0.2), ("roh_global", 0.2)]);
+        let t = p.max_scale(&base, &delta).unwrap().unwrap();
+        assert!((t - 0.25).abs() < 1e-4);
+
+        let small = snap(&[("qpu_eco_impact", 0.1)]);
+        assert_eq!(p.max_scale(&base, &small).unwrap(), Some(1.0));
+
+        let mut outside = base.clone();
+        outside.insert("qpu_eco_impact".into(), 0.9);
+        assert_eq!(p.max_scale(&outside, &small).unwrap(), None);
+    }
+
+    #[test]
+    fn max_scale_refuses_non_finite_readings() {
+        let p = shipped();
+        let base = snap(&[
+            ("roh_global", 0.1),
+            ("qpu_roh", 0.1),
+            ("lifeforce_load", 0.5),
+            ("qpu_coherence", 0.2),
+            ("qpu_eco_impact", 0.1),
+        ]);
+        let small = snap(&[("qpu_eco_impact", 0.1)]);
+        for bad in [f64::NAN, f64::INFINITY] {
+            let mut broken = base.clone();
+            broken.insert("qpu_roh".into(), bad);
+            assert_eq!(p.max_scale(&broken, &small).unwrap(), None);
+            let delta = snap(&[("roh_global", bad)]);
+            assert_eq!(p.max_scale(&base, &delta).unwrap(), None);
+        }
+
+        let mut nan_bound = Polytope::new();
+        nan_bound.push_axis_bounds("qpu_roh", 0.0, f64::NAN);
+        let (at, delta) = (snap(&[("qpu_roh", 0.0)]), snap(&[("qpu_roh", 0.1)]));
+        assert_eq!(nan_bound.max_scale(&at, &delta).unwrap(), None);
+    }
+
+    #[test]
+    fn axis_bounds_take_names_verbatim_and_nan_fails_closed() {
+        let mut p = Polytope::new();
+        p.push_axis_bounds("2fast", 0.0, 1.0);
+        p.push_axis_bounds("qpu-roh", 0.0, 0.2);
+        assert_eq!(p.axes(), ["2fast", "qpu-roh"]);
+        p.check(&snap(&[("2fast", 0.5), ("qpu-roh", 0.1)])).unwrap();
+        assert!(p.check(&snap(&[("2fast", 0.5), ("qpu-roh", 0.3)])).is_err());
+        assert!(p.check(&snap(&[("2fast", f64::NAN), ("qpu-roh", 0.1)])).is_err());
+    }
+
+    #[test]
+    fn rejects_malformed_expressions() {
+        let err = Polytope::compile(["roh_global < 0.3"]).unwrap_err();
+        assert_eq!(err.column, 12);
+        assert!(Polytope::compile(["0.3 <= 0.4"]).is_err());
+        assert!(Polytope::compile(["roh_global +"]).is_err());
+        let missing = shipped().check(&snap(&[("roh_global", 0.1)]));
+        assert!(matches!(missing, Err(ViabilityError::MissingAxis(_))));
+        assert_eq!(
+            Polytope::new().check(&AxisSnapshot::new()),
+            Err(ViabilityError::EmptyRegion)
+        );
+    }
+}
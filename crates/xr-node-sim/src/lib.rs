@@ -78,11 +78,8 @@ impl SimPolicyEngine {
         let nr_raw = fs::read_to_string(dir.join("neurorights.json"))?;
         let neurorights: NeurorightsPolicy = serde_json::from_str(&nr_raw)?;
 
-        let roh_raw = fs::read_to_string(dir.join("rohmodel.aln"))?;
-        let roh: RohModel = serde_json::from_str(&roh_raw)?;
-
-        let tsafe_raw = fs::read_to_string(dir.join("tsafe.aln"))?;
-        let tsafe: TsafeKernel = serde_json::from_str(&tsafe_raw)?;
+        let roh: RohModel = aln_format::from_path(dir.join("rohmodel.aln"))?;
+        let tsafe: TsafeKernel = aln_format::from_path(dir.join("tsafe.aln"))?;
 
         Ok(Self {
             neurorights,